@@ -29,7 +29,7 @@ specialize!(CommonCause => Cause);
 impl Cause for CommonCause {}
 
 /// Caused by a user
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UserIdCause {
     /// Short description of the cause
@@ -43,7 +43,7 @@ register_class!("hudson.model.Cause$UserIdCause" => UserIdCause);
 impl Cause for UserIdCause {}
 
 /// Caused remotely
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteCause {
     /// Short description of the cause
@@ -57,7 +57,7 @@ register_class!("hudson.model.Cause$RemoteCause" => RemoteCause);
 impl Cause for RemoteCause {}
 
 /// Caused by another project
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpstreamCause {
     /// Short description of the cause
@@ -69,11 +69,11 @@ pub struct UpstreamCause {
     /// URL to the upstream `Build`
     pub upstream_url: String,
 }
-register_class!("hudson.model.Cause$RemoteCause" => UpstreamCause);
+register_class!("hudson.model.Cause$UpstreamCause" => UpstreamCause);
 impl Cause for UpstreamCause {}
 
 /// Caused by a timer
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct TimerTriggerCause {
     /// Short description of the cause
@@ -83,7 +83,7 @@ register_class!("hudson.triggers.TimerTrigger$TimerTriggerCause" => TimerTrigger
 impl Cause for TimerTriggerCause {}
 
 /// Caused by a SCM change
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SCMTriggerCause {
     /// Short description of the cause
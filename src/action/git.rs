@@ -7,7 +7,7 @@ use serde::{self, Deserialize, Serialize};
 use crate::helpers::Class;
 
 /// Describe a git branch
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct Branch {
     /// SHA1 of the branch
     #[serde(rename = "SHA1")]
@@ -17,7 +17,7 @@ pub struct Branch {
 }
 
 /// Revision from git
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct Revision {
     /// SHA1 of the revision
     #[serde(rename = "SHA1")]
@@ -49,7 +49,7 @@ specialize!(CommonBranchBuild => BranchBuild);
 impl BranchBuild for CommonBranchBuild {}
 
 /// Build from a git branch
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GitBranchBuild {
     /// Revision
@@ -65,7 +65,7 @@ register_class!("hudson.plugins.git.util.Build" => GitBranchBuild);
 impl BranchBuild for GitBranchBuild {}
 
 /// HashMap of builds by branch name
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct BuildsByBranch {
     /// HashMap of builds by branch name
     #[serde(flatten)]
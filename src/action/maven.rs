@@ -1,13 +1,14 @@
 //! Types related to maven
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::client::{self, Result};
 use crate::client_internals::path::Path;
+use crate::client_internals::ResponseExt;
 use crate::Jenkins;
 
 /// Artifact produced by a build
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Artifact {
     /// Artifact ID
@@ -30,7 +31,7 @@ pub struct Artifact {
 }
 
 /// Short Maven Artifact Record that is returned when getting a maven build
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ShortMavenArtifactRecord {
     /// URL to the full report
@@ -44,7 +45,7 @@ impl ShortMavenArtifactRecord {
     ) -> Result<MavenArtifactRecord> {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::MavenArtifactRecord { .. } = path {
-            let response = jenkins_client.get(&path).await?.json().await?;
+            let response = jenkins_client.get(&path).await?.parse_json().await?;
             Ok(response)
         } else {
             Err(client::Error::InvalidUrl {
@@ -57,7 +58,7 @@ impl ShortMavenArtifactRecord {
 }
 
 /// Describe the artifacts produced by a build
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MavenArtifactRecord {
     /// URL to this record
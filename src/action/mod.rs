@@ -32,8 +32,48 @@ pub struct CommonAction {
 specialize!(CommonAction => Action);
 impl Action for CommonAction {}
 
+impl CommonAction {
+    /// Read the action as a type provided by the user, for actions of plugins not modeled by
+    /// this crate. The type must implement [`Class`](../helpers/trait.Class.html) with the
+    /// `_class` of the action
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use jenkins_api::action::CommonAction;
+    /// # use jenkins_api::helpers::Class;
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct ReleaseAction {
+    ///     release_version: String,
+    /// }
+    /// impl Class for ReleaseAction {
+    ///     fn with_class() -> &'static str {
+    ///         "org.example.ReleaseAction"
+    ///     }
+    /// }
+    ///
+    /// # fn example(action: &CommonAction) -> jenkins_api::client::Result<()> {
+    /// let release: ReleaseAction = action.decode()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode<T>(&self) -> crate::client::Result<T>
+    where
+        T: Class + for<'de> Deserialize<'de>,
+    {
+        if self.class.as_deref() != Some(T::with_class()) {
+            return Err(crate::client::Error::UnexpectedClass {
+                expected: T::with_class().to_string(),
+                found: self.class.clone(),
+            }
+            .into());
+        }
+        Ok(T::deserialize(&self.extra_fields)?)
+    }
+}
+
 /// An action holding parameters
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ParametersAction {
     /// The list of parameters
@@ -43,7 +83,7 @@ register_class!("hudson.model.ParametersAction" => ParametersAction);
 impl Action for ParametersAction {}
 
 /// An action listing causes
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CauseAction {
     /// The list of causes
@@ -53,7 +93,7 @@ register_class!("hudson.model.CauseAction" => CauseAction);
 impl Action for CauseAction {}
 
 /// An action describing a Git change
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GitBuildData {
     /// Name of the SCM
@@ -69,21 +109,21 @@ register_class!("hudson.plugins.git.util.BuildData" => GitBuildData);
 impl Action for GitBuildData {}
 
 /// An action for a git tag
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitTagAction {}
 register_class!("hudson.plugins.git.GitTagAction" => GitTagAction);
 impl Action for GitTagAction {}
 
 /// An action for a repo tag
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RepoTagAction {}
 register_class!("hudson.plugins.repo.TagAction" => RepoTagAction);
 impl Action for RepoTagAction {}
 
 /// An action on time in queue
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeInQueueAction {
     /// Time spent in queue (milliseconds)
@@ -95,14 +135,14 @@ register_class!("jenkins.metrics.impl.TimeInQueueAction" => TimeInQueueAction);
 impl Action for TimeInQueueAction {}
 
 /// An action from pipelines
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EnvActionImpl {}
 register_class!("org.jenkinsci.plugins.workflow.cps.EnvActionImpl" => EnvActionImpl);
 impl Action for EnvActionImpl {}
 
 /// An action from pipelines
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct FlowGraphAction {
     /// Nodes of the pipeline
@@ -112,7 +152,7 @@ register_class!("org.jenkinsci.plugins.workflow.job.views.FlowGraphAction" => Fl
 impl Action for FlowGraphAction {}
 
 /// An action with maven artifacts
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MavenArtifactRecord {
     /// URL to the artifacts
@@ -130,7 +170,7 @@ register_class!("hudson.maven.reporters.MavenArtifactRecord" => MavenArtifactRec
 impl Action for MavenArtifactRecord {}
 
 /// An action with maven artifacts
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MavenAggregatedArtifactRecord {
     /// List of artifact records
@@ -140,7 +180,7 @@ register_class!("hudson.maven.reporters.MavenAggregatedArtifactRecord" => MavenA
 impl Action for MavenAggregatedArtifactRecord {}
 
 /// An action with a surefire test report
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SurefireReport {
     /// Number of tests failed
@@ -156,7 +196,7 @@ register_class!("hudson.maven.reporters.SurefireReport" => SurefireReport);
 impl Action for SurefireReport {}
 
 /// An action with a surefire test report aggregated from other reports
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SurefireAggregatedReport {
     /// Number of tests failed
@@ -172,7 +212,7 @@ register_class!("hudson.maven.reporters.SurefireAggregatedReport" => SurefireAgg
 impl Action for SurefireAggregatedReport {}
 
 /// An action marking an approval on a pipeline
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PipelineApproverAction {
     /// User ID
@@ -180,3 +220,226 @@ pub struct PipelineApproverAction {
 }
 register_class!("org.jenkinsci.plugins.workflow.support.steps.input.ApproverAction" => PipelineApproverAction);
 impl Action for PipelineApproverAction {}
+
+/// A badge added to a `Build` by the badge plugin
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeAction {
+    /// ID of the badge
+    pub id: Option<String>,
+    /// Icon of the badge, as a path or an icon name
+    #[serde(alias = "iconPath")]
+    pub icon: Option<String>,
+    /// Text of the badge
+    pub text: Option<String>,
+    /// Link of the badge
+    pub link: Option<String>,
+}
+register_class!("com.jenkinsci.plugins.badge.action.BadgeAction" => BadgeAction);
+impl Action for BadgeAction {}
+
+/// A summary added to the page of a `Build` by the badge plugin
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeSummaryAction {
+    /// Icon of the summary, as a path or an icon name
+    #[serde(alias = "iconPath")]
+    pub icon: Option<String>,
+    /// Text of the summary
+    #[serde(alias = "summaryText")]
+    pub text: Option<String>,
+}
+register_class!("com.jenkinsci.plugins.badge.action.BadgeSummaryAction" => BadgeSummaryAction);
+impl Action for BadgeSummaryAction {}
+
+/// Priority of a queued item, given by the Priority Sorter plugin
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritySorterAction {
+    /// Priority of the item, lower numbers are built first
+    pub priority: i32,
+    /// Weight of the item, used to order items with the sorting strategy of the plugin
+    pub weight: Option<f64>,
+}
+register_class!("jenkins.advancedqueue.PrioritySorterAction" => PrioritySorterAction);
+impl Action for PrioritySorterAction {}
+
+/// Causes of the failure of a `Build` identified by the Build Failure Analyzer plugin
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureCauseBuildAction {
+    /// Causes found in the log of the build
+    pub found_failure_causes: Vec<crate::failure_cause::FoundFailureCause>,
+}
+register_class!("com.sonyericsson.jenkins.plugins.bfa.model.FailureCauseBuildAction" => FailureCauseBuildAction);
+impl Action for FailureCauseBuildAction {}
+
+/// A SonarQube analysis run during a `Build`, added by the SonarQube Scanner plugin
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SonarAnalysisAction {
+    /// Name of the SonarQube installation configured in Jenkins
+    pub installation_name: Option<String>,
+    /// ID of the Compute Engine task processing the analysis on the SonarQube server
+    pub ce_task_id: Option<String>,
+    /// URL of the SonarQube server
+    pub server_url: Option<String>,
+    /// URL of the dashboard of the analysed project
+    #[serde(alias = "sonarqubeDashboardUrl")]
+    pub url: Option<String>,
+    /// Status of the quality gate, like `OK` or `ERROR`, when it is reported
+    pub quality_gate_status: Option<String>,
+    /// Was the analysis skipped
+    #[serde(default)]
+    pub skipped: bool,
+}
+register_class!("hudson.plugins.sonar.action.SonarAnalysisAction" => SonarAnalysisAction);
+impl Action for SonarAnalysisAction {}
+
+/// Disk space used by a `Job`, computed by the Disk Usage plugin. Sizes are in bytes
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDiskUsageAction {
+    /// Size of the directory of the job on the controller, without its builds
+    pub disk_usage_without_builds: Option<u64>,
+    /// Size of the directories of the job and its sub-jobs on the controller, without their
+    /// builds
+    pub all_disk_usage_without_builds: Option<u64>,
+    /// Size of the directory of the job on the controller, with its builds
+    pub job_root_dir_disk_usage: Option<u64>,
+    /// Size of the workspaces of the job on agents
+    pub all_slave_workspaces: Option<u64>,
+    /// Size of the workspaces of the job on the controller or in custom locations
+    pub all_custom_or_non_slave_workspaces: Option<u64>,
+    /// Size of the builds of the job, by kind of build like `all` or `locked`
+    #[serde(default)]
+    pub builds_disk_usage: std::collections::BTreeMap<String, u64>,
+}
+register_class!("hudson.plugins.disk_usage.ProjectDiskUsageAction" => ProjectDiskUsageAction);
+impl Action for ProjectDiskUsageAction {}
+
+impl ProjectDiskUsageAction {
+    /// Size of the builds of the job
+    pub fn builds(&self) -> u64 {
+        self.builds_disk_usage.get("all").copied().unwrap_or(0)
+    }
+
+    /// Size of the workspaces of the job, on agents and on the controller
+    pub fn workspaces(&self) -> u64 {
+        self.all_slave_workspaces.unwrap_or(0)
+            + self.all_custom_or_non_slave_workspaces.unwrap_or(0)
+    }
+
+    /// Total disk space used by the job: its directory with its builds, and its workspaces
+    pub fn total(&self) -> u64 {
+        let job = self
+            .job_root_dir_disk_usage
+            .unwrap_or_else(|| self.disk_usage_without_builds.unwrap_or(0) + self.builds());
+        job + self.workspaces()
+    }
+}
+
+/// Disk space used by a `Build`, computed by the Disk Usage plugin. Sizes are in bytes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDiskUsageAction {
+    /// Size of the directory of the build
+    pub disk_usage: Option<u64>,
+    /// Size of the directory of the build, with the builds of its sub-jobs like matrix
+    /// configurations
+    pub all_disk_usage: Option<u64>,
+}
+register_class!("hudson.plugins.disk_usage.BuildDiskUsageAction" => BuildDiskUsageAction);
+impl Action for BuildDiskUsageAction {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_badges() {
+        let actions: Vec<CommonAction> = serde_json::from_str(
+            r#"[
+                {"_class": "hudson.model.CauseAction", "causes": []},
+                {
+                    "_class": "com.jenkinsci.plugins.badge.action.BadgeAction",
+                    "iconPath": "/plugin/badge/images/warning.gif",
+                    "text": "flaky",
+                    "link": "https://example.com/flaky"
+                },
+                {
+                    "_class": "com.jenkinsci.plugins.badge.action.BadgeSummaryAction",
+                    "iconPath": "info.gif",
+                    "summaryText": "deployed to staging"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let badges: Vec<BadgeAction> = actions
+            .iter()
+            .filter_map(|action| action.as_variant().ok())
+            .collect();
+        assert_eq!(badges.len(), 1);
+        assert_eq!(
+            badges[0].icon.as_deref(),
+            Some("/plugin/badge/images/warning.gif")
+        );
+        assert_eq!(badges[0].text.as_deref(), Some("flaky"));
+        assert_eq!(badges[0].link.as_deref(), Some("https://example.com/flaky"));
+
+        let summary: BadgeSummaryAction = actions[2].as_variant().unwrap();
+        assert_eq!(summary.text.as_deref(), Some("deployed to staging"));
+    }
+
+    #[test]
+    fn can_read_sonar_analysis() {
+        let action: CommonAction = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.plugins.sonar.action.SonarAnalysisAction",
+            "ceTaskId": "AYtD1xJ4Q9M2",
+            "installationName": "sonarcloud",
+            "new": false,
+            "serverUrl": "https://sonarcloud.io",
+            "skipped": false,
+            "sonarqubeDashboardUrl": "https://sonarcloud.io/dashboard?id=app"
+        }))
+        .unwrap();
+
+        let analysis: SonarAnalysisAction = action.as_variant().unwrap();
+        assert_eq!(analysis.ce_task_id.as_deref(), Some("AYtD1xJ4Q9M2"));
+        assert_eq!(
+            analysis.url.as_deref(),
+            Some("https://sonarcloud.io/dashboard?id=app")
+        );
+        assert_eq!(analysis.quality_gate_status, None);
+    }
+
+    #[test]
+    fn can_decode_user_provided_action() {
+        #[derive(Deserialize, Debug)]
+        #[serde(rename_all = "camelCase")]
+        struct ReleaseAction {
+            release_version: String,
+        }
+        impl Class for ReleaseAction {
+            fn with_class() -> &'static str {
+                "org.example.ReleaseAction"
+            }
+        }
+
+        let actions: Vec<CommonAction> = serde_json::from_value(serde_json::json!([
+            {"_class": "org.example.ReleaseAction", "releaseVersion": "1.4.2"},
+            {"_class": "hudson.model.CauseAction", "causes": []}
+        ]))
+        .unwrap();
+
+        let release: ReleaseAction = actions[0].decode().unwrap();
+        assert_eq!(release.release_version, "1.4.2");
+        let error = actions[1].decode::<ReleaseAction>().unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<crate::client::Error>(),
+            Some(crate::client::Error::UnexpectedClass { found: Some(found), .. })
+                if found == "hudson.model.CauseAction"
+        ));
+    }
+}
@@ -29,7 +29,7 @@ specialize!(CommonParameter => Parameter);
 impl Parameter for CommonParameter {}
 
 /// A boolean parameter
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct BooleanParameterValue {
     /// The parameter name
@@ -41,7 +41,7 @@ register_class!("hudson.model.BooleanParameterValue" => BooleanParameterValue);
 impl Parameter for BooleanParameterValue {}
 
 /// A file parameter
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct FileParameterValue {
     /// The parameter name
@@ -51,7 +51,7 @@ register_class!("hudson.model.FileParameterValue" => FileParameterValue);
 impl Parameter for FileParameterValue {}
 
 /// A password parameter
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PasswordParameterValue {
     /// The parameter name
@@ -61,7 +61,7 @@ register_class!("hudson.model.PasswordParameterValue" => PasswordParameterValue)
 impl Parameter for PasswordParameterValue {}
 
 /// A run parameter
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RunParameterValue {
     /// The parameter name
@@ -75,7 +75,7 @@ register_class!("hudson.model.RunParameterValue" => RunParameterValue);
 impl Parameter for RunParameterValue {}
 
 /// A string parameter
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct StringParameterValue {
     /// The parameter name
@@ -87,7 +87,7 @@ register_class!("hudson.model.StringParameterValue" => StringParameterValue);
 impl Parameter for StringParameterValue {}
 
 /// A text parameter
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct TextParameterValue {
     /// The parameter name
@@ -14,6 +14,16 @@ pub struct CommonPipelineNode {
     /// _class provided by Jenkins
     #[serde(rename = "_class")]
     pub class: Option<String>,
+    /// ID of the node in the execution of the pipeline
+    #[serde(default)]
+    pub id: String,
+    /// Name of the node, like the description of its step
+    pub display_name: Option<String>,
+    /// Name of the function of the step, like `sh`
+    pub display_function_name: Option<String>,
+    /// IDs of the nodes executed just before this one
+    #[serde(default)]
+    pub parents: Vec<String>,
 
     #[cfg(not(feature = "extra-fields-visibility"))]
     #[serde(flatten)]
@@ -27,35 +37,35 @@ specialize!(CommonPipelineNode => PipelineNode);
 impl PipelineNode for CommonPipelineNode {}
 
 /// Beginning of a flow
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FlowStartNode {}
 register_class!("org.jenkinsci.plugins.workflow.graph.FlowStartNode" => FlowStartNode);
 impl PipelineNode for FlowStartNode {}
 
 /// Beginning of a step
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StepStartNode {}
 register_class!("org.jenkinsci.plugins.workflow.cps.nodes.StepStartNode" => StepStartNode);
 impl PipelineNode for StepStartNode {}
 
 /// A step
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StepAtomNode {}
 register_class!("org.jenkinsci.plugins.workflow.cps.nodes.StepAtomNode" => StepAtomNode);
 impl PipelineNode for StepAtomNode {}
 
 /// End of a step
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StepEndNode {}
 register_class!("org.jenkinsci.plugins.workflow.cps.nodes.StepEndNode" => StepEndNode);
 impl PipelineNode for StepEndNode {}
 
 /// End of a flow
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FlowEndNode {}
 register_class!("org.jenkinsci.plugins.workflow.graph.FlowEndNode" => FlowEndNode);
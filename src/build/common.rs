@@ -1,12 +1,20 @@
+use std::time::Duration;
 use std::{fmt::Display, marker::PhantomData};
 
+use futures_util::stream::Stream;
+
 use serde::{self, Deserialize, Serialize};
 
-use crate::helpers::Class;
+use crate::helpers::{Class, JenkinsClass};
 
 use crate::action::CommonAction;
+use crate::build::{
+    AnalysisResult, AnalysisTool, ArtifactSize, BuildGraph, BuildSummary, ConsoleChunk, Issue,
+    IssueSet, RootCause, TimestampedLine, TimestampsQuery,
+};
 use crate::client::{self, Result};
 use crate::client_internals::path::Path;
+use crate::client_internals::ResponseExt;
 use crate::job::{CommonJob, Job};
 use crate::Jenkins;
 
@@ -41,21 +49,13 @@ where
 {
     /// Get the full details of a `Build` matching the `ShortBuild`
     pub async fn get_full_build(&self, jenkins_client: &Jenkins) -> Result<T> {
-        let path = jenkins_client.url_to_path(&self.url);
-        if let Path::Build { .. } = path {
-            let response = jenkins_client.get(&path).await?.json().await?;
-            return Ok(response);
-        } else if let Path::InFolder { path: sub_path, .. } = &path {
-            if let Path::Build { .. } = sub_path.as_ref() {
-                let response = jenkins_client.get(&path).await?.json().await?;
-                return Ok(response);
-            }
-        }
-        Err(client::Error::InvalidUrl {
-            url: self.url.clone(),
-            expected: client::error::ExpectedType::Build,
-        }
-        .into())
+        let path = super::build_path(jenkins_client, &self.url)?;
+        jenkins_client.get(&path).await?.parse_json().await
+    }
+
+    /// Delete the `Build` matching the `ShortBuild`
+    pub async fn delete(&self, jenkins_client: &Jenkins) -> Result<()> {
+        super::delete::delete_build(jenkins_client, &self.url).await
     }
 }
 
@@ -75,6 +75,68 @@ pub enum BuildStatus {
     Aborted,
 }
 
+impl BuildStatus {
+    /// Rank of the status, from the best (`Success`) to the worst (`Aborted`), following
+    /// Jenkins' `Result` ordinals
+    pub(crate) fn severity(self) -> u8 {
+        match self {
+            BuildStatus::Success => 0,
+            BuildStatus::Unstable => 1,
+            BuildStatus::Failure => 2,
+            BuildStatus::NotBuilt => 3,
+            BuildStatus::Aborted => 4,
+        }
+    }
+}
+
+/// State of a build, derived from its `building`, `in_progress` and `result` fields
+///
+/// | `building` | `in_progress` | `result` | state |
+/// |------------|---------------|----------|-------|
+/// | `true` | any | `None` | `Running` |
+/// | any | `Some(true)` or `building` | `Some(status)` | `Finishing(status)` |
+/// | `false` | `Some(true)` | `None` | `Resuming` |
+/// | `false` | `Some(false)` or `None` | `Some(status)` | `Completed(status)` |
+/// | `false` | `Some(false)` or `None` | `None` | `Pending` |
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildState {
+    /// The build hasn't started yet
+    Pending,
+    /// The build is running
+    Running,
+    /// The build is still in progress without running, like a pipeline being resumed after a
+    /// restart of Jenkins
+    Resuming,
+    /// The build has a result, but is still running, like post build steps or the end of a
+    /// pipeline
+    Finishing(BuildStatus),
+    /// The build completed with a result
+    Completed(BuildStatus),
+}
+
+impl BuildState {
+    /// Derive the state of a build from its fields
+    pub fn from_fields(
+        building: bool,
+        in_progress: Option<bool>,
+        result: Option<BuildStatus>,
+    ) -> Self {
+        let in_progress = building || in_progress == Some(true);
+        match (in_progress, building, result) {
+            (_, true, None) => BuildState::Running,
+            (true, _, Some(status)) => BuildState::Finishing(status),
+            (true, false, None) => BuildState::Resuming,
+            (false, _, Some(status)) => BuildState::Completed(status),
+            (false, _, None) => BuildState::Pending,
+        }
+    }
+
+    /// Is the build completed
+    pub fn is_completed(self) -> bool {
+        matches!(self, BuildState::Completed(_))
+    }
+}
+
 /// A file archived by a `Build`
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -87,6 +149,20 @@ pub struct Artifact {
     pub relative_path: String,
 }
 
+/// Links to a `Build` provided by the Display URL plugin, redirecting to the UI configured in
+/// Jenkins (classic or Blue Ocean)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayUrls {
+    /// Link to the build
+    pub run: String,
+    /// Link to the changes of the build
+    pub changes: String,
+    /// Link to the test results of the build
+    pub tests: String,
+    /// Link to the artifacts of the build
+    pub artifacts: String,
+}
+
 /// Helper type to act on a build
 #[derive(Debug, PartialEq, Clone)]
 pub enum BuildNumber {
@@ -165,6 +241,27 @@ into_buildnumber!(i16);
 into_buildnumber!(i32);
 into_buildnumber!(i64);
 
+/// Path to the console text of the build at `build`, in the same folders
+fn console_text_path(build: Path<'_>) -> Path<'_> {
+    match build {
+        Path::Build {
+            job_name,
+            number,
+            configuration,
+        } => Path::ConsoleText {
+            job_name,
+            number,
+            configuration,
+            folder_name: None,
+        },
+        Path::InFolder { folder_name, path } => Path::InFolder {
+            folder_name,
+            path: Box::new(console_text_path(*path)),
+        },
+        path => path,
+    }
+}
+
 /// Trait implemented by specializations of `Build` and providing common methods
 pub trait Build {
     /// Type of the job that triggered this build
@@ -195,7 +292,7 @@ pub trait Build {
                         configuration,
                     })
                     .await?
-                    .json()
+                    .parse_json()
                     .await?;
                 return Ok(response);
             } else if let Path::InFolder {
@@ -218,7 +315,7 @@ pub trait Build {
                             }),
                         })
                         .await?
-                        .json()
+                        .parse_json()
                         .await?;
                     return Ok(response);
                 }
@@ -237,56 +334,152 @@ pub trait Build {
         jenkins_client: &Jenkins,
     ) -> impl std::future::Future<Output = Result<String>> {
         async move {
-            let path = jenkins_client.url_to_path(self.url());
-            if let Path::Build {
-                job_name,
-                number,
-                configuration,
-            } = path
-            {
-                let response = jenkins_client
-                    .get(&Path::ConsoleText {
-                        job_name,
-                        number,
-                        configuration,
-                        folder_name: None,
-                    })
-                    .await?
-                    .text()
-                    .await?;
-                return Ok(response);
-            } else if let Path::InFolder {
-                path: sub_path,
-                folder_name,
-            } = &path
-            {
-                if let Path::Build {
-                    job_name,
-                    number,
-                    configuration,
-                } = sub_path.as_ref()
-                {
-                    let response = jenkins_client
-                        .get(&Path::ConsoleText {
-                            job_name: job_name.clone(),
-                            number: number.clone(),
-                            configuration: configuration.clone(),
-                            folder_name: Some(folder_name.clone()),
-                        })
-                        .await?
-                        .text()
-                        .await?;
-                    return Ok(response);
-                }
-            }
+            let build = super::build_path(jenkins_client, self.url())?;
+            let response = jenkins_client
+                .get(&console_text_path(build))
+                .await?
+                .text()
+                .await?;
+            Ok(response)
+        }
+    }
 
-            Err(client::Error::InvalidUrl {
-                url: self.url().to_string(),
-                expected: client::error::ExpectedType::Build,
-            }
-            .into())
+    /// Get the console output of this `Build` from the byte `start_offset`, with the offset to
+    /// get the next chunk from and if more output is expected
+    ///
+    /// This is the progressive log API used by the Jenkins UI, to follow the output of a running
+    /// build at the pace of the caller.
+    fn get_console_chunk(
+        &self,
+        jenkins_client: &Jenkins,
+        start_offset: u64,
+    ) -> impl std::future::Future<Output = Result<ConsoleChunk>> {
+        super::console::get_console_chunk(jenkins_client, self.url(), start_offset)
+    }
+
+    /// Stream the console output of this `Build` as it is logged, checking for more output
+    /// every `interval` while the build is running. The stream ends with the build
+    fn get_console_stream<'a>(
+        &'a self,
+        jenkins_client: &'a Jenkins,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<String>> + 'a {
+        super::console::console_stream(jenkins_client, self.url(), interval)
+    }
+
+    /// Abort this `Build`, like the stop button of Jenkins. A pipeline gets the time to run its
+    /// `post` sections
+    fn stop(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
+        super::stop::stop_build(jenkins_client, self.url(), super::stop::STOP)
+    }
+
+    /// Terminate this pipeline `Build` without running its `post` sections, for a build that
+    /// didn't stop after `stop`
+    fn term(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
+        super::stop::stop_build(jenkins_client, self.url(), super::stop::TERM)
+    }
+
+    /// Kill this pipeline `Build` immediately, for a build that didn't stop after `term`. It may
+    /// leave its workspace and executors in an inconsistent state
+    fn kill(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
+        super::stop::stop_build(jenkins_client, self.url(), super::stop::KILL)
+    }
+
+    /// Delete this `Build`, with its logs and artifacts
+    fn delete(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
+        super::delete::delete_build(jenkins_client, self.url())
+    }
+
+    /// Get the artifacts of this `Build` with their size and modification time, read with a
+    /// `HEAD` request on each artifact
+    fn get_artifact_sizes(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Vec<ArtifactSize>>> {
+        super::download::get_artifact_sizes(jenkins_client, self.url())
+    }
+
+    /// Get the timestamps of the lines of the console of this `Build`, with the Timestamper plugin
+    fn get_timestamps(
+        &self,
+        jenkins_client: &Jenkins,
+        query: &TimestampsQuery,
+    ) -> impl std::future::Future<Output = Result<Vec<TimestampedLine>>> {
+        super::timestamps::get_timestamps(jenkins_client, self.url(), query)
+    }
+
+    /// Get the static analysis tools whose results were recorded on this `Build` by the Warnings
+    /// Next Generation plugin
+    fn get_analysis_tools(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Vec<AnalysisTool>>> {
+        super::warnings::get_analysis_tools(jenkins_client, self.url())
+    }
+
+    /// Get the summary of the issues found on this `Build` by the static analysis tool `tool`,
+    /// like `checkstyle`
+    fn get_analysis_result<'a>(
+        &'a self,
+        jenkins_client: &'a Jenkins,
+        tool: &'a str,
+    ) -> impl std::future::Future<Output = Result<AnalysisResult>> + 'a {
+        super::warnings::get_analysis_result(jenkins_client, self.url(), tool)
+    }
+
+    /// Get the issues found on this `Build` by the static analysis tool `tool`
+    fn get_issues<'a>(
+        &'a self,
+        jenkins_client: &'a Jenkins,
+        tool: &'a str,
+        issues: IssueSet,
+    ) -> impl std::future::Future<Output = Result<Vec<Issue>>> + 'a {
+        super::warnings::get_issues(jenkins_client, self.url(), tool, issues)
+    }
+
+    /// Get the links to this `Build` provided by the Display URL plugin
+    fn display_urls(&self) -> DisplayUrls {
+        let redirect = format!("{}/display/redirect", self.url().trim_end_matches('/'));
+        DisplayUrls {
+            changes: format!("{}?page=changes", redirect),
+            tests: format!("{}?page=tests", redirect),
+            artifacts: format!("{}?page=artifacts", redirect),
+            run: redirect,
         }
     }
+
+    /// Get a compact summary of this `Build`, with its result, culprits and the number of its
+    /// changes, tests and artifacts, in one request
+    fn summarize(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<BuildSummary>> {
+        super::summary::summarize(jenkins_client, self.url())
+    }
+
+    /// Get the graph of builds triggered downstream of this `Build`
+    ///
+    /// Downstream builds are found by looking at the downstream projects of each job, for builds
+    /// with an upstream cause pointing to the triggering build. Only the builds still listed on
+    /// those jobs (usually the last 100) are considered.
+    fn get_downstream_builds(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<BuildGraph>> {
+        async move { super::graph::get_downstream_builds(jenkins_client, self.url()).await }
+    }
+
+    /// Get the cause that started the chain of upstream builds that triggered this `Build`,
+    /// like a user, a timer or a SCM change
+    ///
+    /// Upstream causes are followed up to 32 builds, and until a build is seen twice; the last
+    /// cause followed is then returned. Returns `None` if a build of the chain has no cause.
+    fn get_root_cause(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Option<RootCause>>> {
+        super::graph::get_root_cause(jenkins_client, self.url())
+    }
 }
 
 macro_rules! build_with_common_fields_and_impl {
@@ -351,6 +544,7 @@ macro_rules! build_with_common_fields_and_impl {
             /// Timestamp of the build start
             pub timestamp: u64,
             /// Are the logs kept?
+            #[serde(default)]
             pub keep_log: bool,
             /// Build result
             pub result: Option<BuildStatus>,
@@ -362,9 +556,15 @@ macro_rules! build_with_common_fields_and_impl {
             pub description: Option<String>,
             /// Is this build currently running
             pub building: bool,
+            /// Is this build in progress, even when not running like a pipeline being resumed.
+            /// Not provided by older versions of Jenkins
+            #[serde(default)]
+            pub in_progress: Option<bool>,
             /// Build number in string format
             pub id: String,
-            /// ID while in the build queue
+            /// ID while in the build queue, -1 when not known, like for builds started before
+            /// Jenkins 1.601
+            #[serde(default = "crate::helpers::unknown_queue_id")]
             pub queue_id: i32,
             /// Build actions
             pub actions: Vec<CommonAction>,
@@ -385,6 +585,79 @@ macro_rules! build_with_common_fields_and_impl {
                 &self.url
             }
         }
+        impl $name {
+            /// Find the first artifact of this build whose file name matches the glob
+            /// `pattern`, where `*` matches any characters and `?` one character
+            pub fn get_artifact_by_name(&self, pattern: &str) -> Option<&Artifact> {
+                self.artifacts
+                    .iter()
+                    .find(|artifact| crate::build::download::matches_glob(pattern, &artifact.file_name))
+            }
+
+            /// State of this build, derived from `building`, `in_progress` and `result`
+            pub fn state(&self) -> crate::build::BuildState {
+                crate::build::BuildState::from_fields(self.building, self.in_progress, self.result)
+            }
+
+            /// Estimated time until this build completes, if it is building and its duration
+            /// can be estimated. A build taking longer than estimated has an ETA of zero
+            pub fn eta(&self) -> Option<std::time::Duration> {
+                crate::build::progress::eta(self.building, self.timestamp, self.estimated_duration)
+            }
+
+            /// Estimated progress of this build in percent, capped at 99% while it is building
+            pub fn progress_percent(&self) -> Option<u32> {
+                crate::build::progress::progress_percent(
+                    self.building,
+                    self.timestamp,
+                    self.estimated_duration,
+                )
+            }
+
+            /// Causes of the failure of this build identified by the Build Failure Analyzer
+            /// plugin
+            pub fn found_failure_causes(&self) -> Vec<crate::failure_cause::FoundFailureCause> {
+                self.actions
+                    .iter()
+                    .filter_map(|action| {
+                        action
+                            .as_variant::<crate::action::FailureCauseBuildAction>()
+                            .ok()
+                    })
+                    .flat_map(|action| action.found_failure_causes)
+                    .collect()
+            }
+
+            /// Disk space used by this build, computed by the Disk Usage plugin
+            pub fn disk_usage(&self) -> Option<crate::action::BuildDiskUsageAction> {
+                self.actions
+                    .iter()
+                    .find_map(|action| action.as_variant().ok())
+            }
+
+            /// SonarQube analyses run during this build
+            pub fn sonar_analyses(&self) -> Vec<crate::action::SonarAnalysisAction> {
+                self.actions
+                    .iter()
+                    .filter_map(|action| {
+                        action
+                            .as_variant::<crate::action::SonarAnalysisAction>()
+                            .ok()
+                    })
+                    .collect()
+            }
+
+            /// Refresh this build every `interval`, yielding it each time until it completes
+            pub fn watch<'a>(
+                &self,
+                jenkins_client: &'a crate::Jenkins,
+                interval: std::time::Duration,
+            ) -> impl futures_util::Stream<Item = crate::client::Result<Self>> + 'a {
+                crate::build::progress::watch(jenkins_client, self.url.clone(), interval, |build: &Self| {
+                    build.building
+                })
+            }
+        }
     };
 }
 
@@ -410,4 +683,141 @@ build_with_common_fields_and_impl!(
 );
 specialize!(CommonBuild => Build);
 
-impl CommonBuild {}
+impl CommonBuild {
+    /// `_class` of the build, to check its kind without parsing it as a specialization
+    pub fn jenkins_class(&self) -> Option<JenkinsClass> {
+        self.class.as_deref().map(JenkinsClass::from)
+    }
+}
+
+#[cfg(test)]
+/// A finished build at `url` with one artifact, for the tests
+pub(crate) fn build_at(url: &str) -> CommonBuild {
+    serde_json::from_value(serde_json::json!({
+        "url": url,
+        "number": 1,
+        "duration": 10,
+        "estimatedDuration": 10,
+        "timestamp": 1_500_000_000_000u64,
+        "keepLog": false,
+        "result": "SUCCESS",
+        "displayName": "#1",
+        "building": false,
+        "id": "1",
+        "queueId": 2,
+        "actions": [],
+        "artifacts": [{
+            "displayPath": "app installer.bin",
+            "fileName": "app installer.bin",
+            "relativePath": "dist/app installer.bin"
+        }]
+    }))
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_build_without_optional_fields() {
+        let build: CommonBuild = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.model.FreeStyleBuild",
+            "url": "http://jenkins/job/legacy/3/",
+            "number": 3,
+            "duration": 10,
+            "estimatedDuration": 10,
+            "timestamp": 1_200_000_000_000u64,
+            "result": "SUCCESS",
+            "displayName": "#3",
+            "building": false,
+            "id": "3",
+            "actions": [],
+            "artifacts": []
+        }))
+        .unwrap();
+
+        assert_eq!(build.queue_id, -1);
+        assert!(!build.keep_log);
+        assert_eq!(build.in_progress, None);
+    }
+
+    #[test]
+    fn can_get_display_urls() {
+        let build = build_at("http://jenkins/job/my%20job/3/");
+
+        assert_eq!(
+            build.display_urls(),
+            DisplayUrls {
+                run: "http://jenkins/job/my%20job/3/display/redirect".to_string(),
+                changes: "http://jenkins/job/my%20job/3/display/redirect?page=changes".to_string(),
+                tests: "http://jenkins/job/my%20job/3/display/redirect?page=tests".to_string(),
+                artifacts: "http://jenkins/job/my%20job/3/display/redirect?page=artifacts"
+                    .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn can_get_console_of_matrix_run_in_folder() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock(
+                "GET",
+                "/job/my%20folder/job/matrix/label=linux/1/consoleText/api/json",
+            )
+            .match_query(mockito::Matcher::Any)
+            .with_body("console output")
+            .create();
+
+        let build = build_at(&format!(
+            "{}/job/my%20folder/job/matrix/label=linux/1/",
+            server.url()
+        ));
+        let console = build.get_console(&jenkins_client).await;
+
+        assert_eq!(console.unwrap(), "console output");
+        mock.assert();
+    }
+
+    #[test]
+    fn can_derive_build_state() {
+        let build: CommonBuild = serde_json::from_value(serde_json::json!({
+            "url": "http://jenkins/job/app/4/",
+            "number": 4,
+            "duration": 0,
+            "estimatedDuration": 10,
+            "timestamp": 1_500_000_000_000u64,
+            "keepLog": false,
+            "result": null,
+            "displayName": "#4",
+            "building": false,
+            "inProgress": true,
+            "id": "4",
+            "queueId": 3,
+            "actions": [],
+            "artifacts": []
+        }))
+        .unwrap();
+        assert_eq!(build.state(), BuildState::Resuming);
+
+        assert_eq!(
+            BuildState::from_fields(true, None, None),
+            BuildState::Running
+        );
+        assert_eq!(
+            BuildState::from_fields(true, Some(true), Some(BuildStatus::Failure)),
+            BuildState::Finishing(BuildStatus::Failure)
+        );
+        assert_eq!(
+            BuildState::from_fields(false, None, Some(BuildStatus::Success)),
+            BuildState::Completed(BuildStatus::Success)
+        );
+        assert_eq!(
+            BuildState::from_fields(false, Some(false), None),
+            BuildState::Pending
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream, StreamExt};
+
+use super::build_path;
+use crate::client::Result;
+use crate::client_internals::path::Path;
+use crate::Jenkins;
+
+/// A chunk of the console output of a build, read with the progressive log API of Jenkins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleChunk {
+    /// Text of the console output from the requested offset
+    pub text: String,
+    /// Offset to request the next chunk from
+    pub next_offset: u64,
+    /// Will there be more output, because the build is still running
+    pub more_data: bool,
+}
+
+/// Get the console output of the build at `url`, from the byte `start`
+pub(crate) async fn get_console_chunk(
+    jenkins_client: &Jenkins,
+    url: &str,
+    start: u64,
+) -> Result<ConsoleChunk> {
+    let build = build_path(jenkins_client, url)?;
+    get_progressive_text(jenkins_client, build, start).await
+}
+
+/// Get the output logged at `path` with the progressive log API of Jenkins, from the byte `start`
+pub(crate) async fn get_progressive_text(
+    jenkins_client: &Jenkins,
+    path: Path<'_>,
+    start: u64,
+) -> Result<ConsoleChunk> {
+    let response = jenkins_client
+        .get_endpoint_with_params(
+            &Path::ProgressiveText {
+                build: Box::new(path),
+            },
+            [("start", start)],
+        )
+        .await?;
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let more_data = header("X-More-Data").is_some_and(|value| value == "true");
+    let next_offset = header("X-Text-Size").and_then(|value| value.parse().ok());
+    let text = response.text().await?;
+    Ok(ConsoleChunk {
+        next_offset: next_offset.unwrap_or(start + text.len() as u64),
+        text,
+        more_data,
+    })
+}
+
+/// Follow the console output of the build at `url` with the progressive log API, waiting
+/// `interval` before asking for more output while the build is running
+pub(crate) fn console_stream<'a>(
+    jenkins_client: &'a Jenkins,
+    url: &'a str,
+    interval: Duration,
+) -> impl Stream<Item = Result<String>> + 'a {
+    // offset of the next chunk, and whether to wait before getting it
+    stream::unfold(Some((0, false)), move |state| async move {
+        let (start, wait) = state?;
+        if wait {
+            tokio::time::sleep(interval).await;
+        }
+        match get_console_chunk(jenkins_client, url, start).await {
+            Ok(chunk) => {
+                let next = chunk.more_data.then_some((chunk.next_offset, true));
+                Some((Ok(chunk.text), next))
+            }
+            Err(error) => Some((Err(error), None)),
+        }
+    })
+    .filter(|text| std::future::ready(!matches!(text, Ok(text) if text.is_empty())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_console_chunks() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/folder/job/app/12/", server.url());
+
+        let first = server
+            .mock("GET", "/job/folder/job/app/12/logText/progressiveText")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "start".to_string(),
+                "0".to_string(),
+            ))
+            .with_header("X-Text-Size", "14")
+            .with_header("X-More-Data", "true")
+            .with_body("Started by me\n")
+            .create();
+        let last = server
+            .mock("GET", "/job/folder/job/app/12/logText/progressiveText")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "start".to_string(),
+                "14".to_string(),
+            ))
+            .with_header("X-Text-Size", "30")
+            .with_body("Finished: SUCCESS\n")
+            .create();
+
+        let chunk = get_console_chunk(&jenkins_client, &url, 0).await.unwrap();
+        assert_eq!(
+            chunk,
+            ConsoleChunk {
+                text: "Started by me\n".to_string(),
+                next_offset: 14,
+                more_data: true,
+            }
+        );
+        let chunk = get_console_chunk(&jenkins_client, &url, chunk.next_offset)
+            .await
+            .unwrap();
+        assert_eq!(chunk.next_offset, 30);
+        assert!(!chunk.more_data);
+
+        first.assert();
+        last.assert();
+    }
+
+    #[tokio::test]
+    async fn can_stream_console() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/app/12/", server.url());
+
+        let mut chunk = |start: &str, size: &str| {
+            server
+                .mock("GET", "/job/app/12/logText/progressiveText")
+                .match_query(mockito::Matcher::UrlEncoded(
+                    "start".to_string(),
+                    start.to_string(),
+                ))
+                .with_header("X-Text-Size", size)
+        };
+        let first = chunk("0", "14")
+            .with_header("X-More-Data", "true")
+            .with_body("Started by me\n")
+            .create();
+        let waiting = chunk("14", "14")
+            .with_header("X-More-Data", "true")
+            .expect(1)
+            .create();
+        let last = chunk("14", "32").with_body("Finished: SUCCESS\n").create();
+
+        let texts = console_stream(&jenkins_client, &url, Duration::from_millis(10))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(texts, vec!["Started by me\n", "Finished: SUCCESS\n"]);
+        first.assert();
+        waiting.assert();
+        last.assert();
+    }
+}
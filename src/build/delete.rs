@@ -0,0 +1,229 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use super::build_path;
+use crate::client::{self, Result};
+use crate::client_internals::path::Path;
+use crate::Jenkins;
+
+/// A set of build numbers, in the syntax used by Jenkins like `1-3,5,8-10`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildRange {
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+impl BuildRange {
+    /// Is the build `number` in this range
+    pub fn contains(&self, number: u32) -> bool {
+        self.ranges.iter().any(|range| range.contains(&number))
+    }
+
+    /// Lowest build number of this range
+    pub fn min(&self) -> u32 {
+        self.ranges
+            .iter()
+            .map(|range| *range.start())
+            .min()
+            .unwrap_or_default()
+    }
+}
+
+impl From<RangeInclusive<u32>> for BuildRange {
+    fn from(range: RangeInclusive<u32>) -> Self {
+        BuildRange {
+            ranges: vec![range],
+        }
+    }
+}
+
+impl FromStr for BuildRange {
+    type Err = client::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || client::Error::InvalidBuildRange {
+            range: s.to_string(),
+        };
+        let parse = |number: &str| number.trim().parse::<u32>().map_err(|_| invalid());
+
+        let ranges = s
+            .split(',')
+            .map(|part| {
+                let (start, end) = match part.split_once('-') {
+                    Some((start, end)) => (parse(start)?, parse(end)?),
+                    None => (parse(part)?, parse(part)?),
+                };
+                if start > end {
+                    return Err(invalid());
+                }
+                Ok(start..=end)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(BuildRange { ranges })
+    }
+}
+
+impl fmt::Display for BuildRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = self
+            .ranges
+            .iter()
+            .map(|range| {
+                if range.start() == range.end() {
+                    range.start().to_string()
+                } else {
+                    format!("{}-{}", range.start(), range.end())
+                }
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+/// Delete the build at `url`
+pub(crate) async fn delete_build(jenkins_client: &Jenkins, url: &str) -> Result<()> {
+    let _ = jenkins_client
+        .post(&Path::BuildDelete {
+            build: Box::new(build_path(jenkins_client, url)?),
+        })
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_build_range() {
+        let range: BuildRange = "1-3, 5,8-10".parse().unwrap();
+
+        assert!(range.contains(2));
+        assert!(range.contains(5));
+        assert!(!range.contains(4));
+        assert!(!range.contains(11));
+        assert_eq!(range.min(), 1);
+        assert_eq!(range.to_string(), "1-3,5,8-10");
+        assert!("3-1".parse::<BuildRange>().is_err());
+        assert!("1-a".parse::<BuildRange>().is_err());
+    }
+
+    #[tokio::test]
+    async fn can_delete_build() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server
+            .mock("POST", "/job/folder/job/app/12/doDelete")
+            .create();
+
+        delete_build(
+            &jenkins_client,
+            &format!("{}/job/folder/job/app/12/", server.url()),
+        )
+        .await
+        .unwrap();
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_delete_builds_in_range() {
+        use crate::job::Job;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let job = crate::job::TestJob::new("app", &server.url(), "/job/app/");
+
+        let builds = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "allBuilds": (1..8).rev().map(|number| serde_json::json!({
+                        "number": number,
+                        "url": format!("{}/job/app/{}/", server.url(), number),
+                    })).collect::<Vec<_>>()
+                })
+                .to_string(),
+            )
+            .create();
+        let deletions = [6, 4, 3]
+            .iter()
+            .map(|number| {
+                server
+                    .mock("POST", format!("/job/app/{}/doDelete", number).as_str())
+                    .create()
+            })
+            .collect::<Vec<_>>();
+
+        let deleted = job
+            .delete_builds_in_range(&jenkins_client, &"3-4,6".parse().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, vec![6, 4, 3]);
+        builds.assert();
+        for deletion in deletions {
+            deletion.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn can_report_partial_deletion_of_builds_in_range() {
+        use crate::job::Job;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let job = crate::job::TestJob::new("app", &server.url(), "/job/app/");
+
+        let builds = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "allBuilds": (1..5).rev().map(|number| serde_json::json!({
+                        "number": number,
+                        "url": format!("{}/job/app/{}/", server.url(), number),
+                    })).collect::<Vec<_>>()
+                })
+                .to_string(),
+            )
+            .create();
+        let deletion = server.mock("POST", "/job/app/4/doDelete").create();
+        let kept = server
+            .mock("POST", "/job/app/3/doDelete")
+            .with_status(400)
+            .create();
+        let not_reached = server
+            .mock("POST", "/job/app/2/doDelete")
+            .expect(0)
+            .create();
+
+        let error = job
+            .delete_builds_in_range(&jenkins_client, &"2-4".parse().unwrap())
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<client::Error>() {
+            Some(client::Error::BuildDeletionFailed { build, deleted, .. }) => {
+                assert_eq!(*build, 3);
+                assert_eq!(deleted, &vec![4]);
+            }
+            _ => panic!("unexpected error {}", error),
+        }
+        builds.assert();
+        deletion.assert();
+        kept.assert();
+        not_reached.assert();
+    }
+}
@@ -0,0 +1,589 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use log::warn;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, LAST_MODIFIED};
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::build_path;
+use crate::build::{Artifact, Build};
+use crate::client::{self, AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::path::Path;
+use crate::client_internals::{InternalAdvancedQueryParams, ResponseExt};
+use crate::Jenkins;
+
+/// Progress of the download of an `Artifact`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Number of bytes downloaded
+    pub downloaded: u64,
+    /// Size of the artifact, if known
+    pub total: Option<u64>,
+}
+
+/// Download of an `Artifact`, using HTTP range requests to resume it when the connection drops
+pub struct ArtifactDownload<'a> {
+    jenkins_client: &'a Jenkins,
+    build_url: String,
+    relative_path: String,
+    chunk_size: Option<u64>,
+    max_retries: u32,
+    progress: Option<Box<dyn FnMut(DownloadProgress) + Send + 'a>>,
+}
+
+impl fmt::Debug for ArtifactDownload<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArtifactDownload")
+            .field("build_url", &self.build_url)
+            .field("relative_path", &self.relative_path)
+            .field("chunk_size", &self.chunk_size)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
+
+impl Artifact {
+    /// Prepare the download of this artifact of `build`
+    pub fn download_with<'a, B: Build>(
+        &self,
+        jenkins_client: &'a Jenkins,
+        build: &B,
+    ) -> ArtifactDownload<'a> {
+        ArtifactDownload {
+            jenkins_client,
+            build_url: build.url().to_string(),
+            relative_path: self.relative_path.clone(),
+            chunk_size: None,
+            max_retries: 3,
+            progress: None,
+        }
+    }
+
+    /// Download this artifact of `build` in memory
+    pub async fn download<B: Build>(&self, jenkins_client: &Jenkins, build: &B) -> Result<Vec<u8>> {
+        self.download_with(jenkins_client, build).to_vec().await
+    }
+
+    /// Download this artifact of `build` to the file at `path`, writing the data as it is
+    /// received, returning the number of bytes written
+    pub async fn download_to<B, P>(
+        &self,
+        jenkins_client: &Jenkins,
+        build: &B,
+        path: P,
+    ) -> Result<u64>
+    where
+        B: Build,
+        P: AsRef<std::path::Path>,
+    {
+        self.download_with(jenkins_client, build)
+            .to_file(path)
+            .await
+    }
+}
+
+/// Does `name` match the glob `pattern`, where `*` matches any characters and `?` one character
+pub(crate) fn matches_glob(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    // position of the last `*` in the pattern, and of the name when it was reached
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                // let the last `*` match one more character
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Size and modification time of an `Artifact`, read with a `HEAD` request as Jenkins doesn't
+/// send them with the build
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactSize {
+    /// The artifact
+    pub artifact: Artifact,
+    /// Size of the file in bytes, if Jenkins sends it
+    pub size: Option<u64>,
+    /// When the file was last modified, if Jenkins sends it
+    pub last_modified: Option<SystemTime>,
+}
+
+/// Get the size and modification time of `artifact` of the build at `build`
+pub(crate) async fn get_artifact_size(
+    jenkins_client: &Jenkins,
+    build: &Path<'_>,
+    artifact: Artifact,
+) -> Result<ArtifactSize> {
+    let response = jenkins_client
+        .head(&Path::Artifact {
+            build: Box::new(build.clone()),
+            relative_path: &artifact.relative_path,
+        })
+        .await?;
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    Ok(ArtifactSize {
+        size: header(CONTENT_LENGTH).and_then(|length| length.parse().ok()),
+        last_modified: header(LAST_MODIFIED).and_then(|date| httpdate::parse_http_date(date).ok()),
+        artifact,
+    })
+}
+
+/// Get the artifacts of the build at `url`, with their size
+pub(crate) async fn get_artifact_sizes(
+    jenkins_client: &Jenkins,
+    url: &str,
+) -> Result<Vec<ArtifactSize>> {
+    #[derive(Deserialize)]
+    struct Artifacts {
+        artifacts: Vec<Artifact>,
+    }
+
+    let build = build_path(jenkins_client, url)?;
+    let Artifacts { artifacts } = jenkins_client
+        .get_with_params(
+            &build,
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                TreeBuilder::new()
+                    .with_field(
+                        TreeBuilder::object("artifacts")
+                            .with_subfield("displayPath")
+                            .with_subfield("fileName")
+                            .with_subfield("relativePath"),
+                    )
+                    .build(),
+            )),
+        )
+        .await?
+        .parse_json()
+        .await?;
+    let mut sized = Vec::with_capacity(artifacts.len());
+    for artifact in artifacts {
+        sized.push(get_artifact_size(jenkins_client, &build, artifact).await?);
+    }
+    Ok(sized)
+}
+
+/// Parse the total size from a `Content-Range` header like `bytes 0-1023/4096`
+fn total_from_content_range(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Errors while reading the response from Jenkins can be recovered by resuming the download
+fn is_resumable(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .map(|error| !error.is_status() && !error.is_builder())
+        .unwrap_or(false)
+}
+
+impl<'a> ArtifactDownload<'a> {
+    /// Download the artifact in chunks of `chunk_size` bytes, one request per chunk. By default
+    /// the artifact is downloaded in one request, resumed from where it stopped on errors
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = Some(chunk_size.max(1));
+        self
+    }
+
+    /// Number of times the download is resumed after an error without receiving any data,
+    /// 3 by default
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Call `progress` each time data is received
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: FnMut(DownloadProgress) + Send + 'a,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Download the artifact to a file, returning the number of bytes written
+    pub async fn to_file<P: AsRef<std::path::Path>>(self, path: P) -> Result<u64> {
+        let mut file = tokio::fs::File::create(path).await?;
+        let written = self.to_writer(&mut file).await?;
+        file.flush().await?;
+        Ok(written)
+    }
+
+    /// Download the artifact in memory
+    pub async fn to_vec(self) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        let _ = self.to_writer(&mut bytes).await?;
+        Ok(bytes)
+    }
+
+    /// Download the artifact to `writer`, returning the number of bytes written
+    pub async fn to_writer<W>(mut self, writer: &mut W) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let build_url = std::mem::take(&mut self.build_url);
+        let relative_path = std::mem::take(&mut self.relative_path);
+        let path = Path::Artifact {
+            build: Box::new(build_path(self.jenkins_client, &build_url)?),
+            relative_path: &relative_path,
+        };
+
+        let mut progress = DownloadProgress {
+            downloaded: 0,
+            total: None,
+        };
+        let mut retries = 0;
+        loop {
+            let before = progress.downloaded;
+            let error = match self.download_range(&path, &mut progress, writer).await {
+                Ok(true) => return Ok(progress.downloaded),
+                Ok(false) if progress.downloaded > before => {
+                    retries = 0;
+                    continue;
+                }
+                // an empty partial response before the end makes no progress
+                Ok(false) => client::Error::DownloadStalled {
+                    path: relative_path.clone(),
+                    downloaded: progress.downloaded,
+                }
+                .into(),
+                Err(error) if is_resumable(error.as_ref()) => error,
+                Err(error) => return Err(error),
+            };
+            if progress.downloaded > before {
+                retries = 0;
+            } else if retries < self.max_retries {
+                retries += 1;
+            } else {
+                return Err(error);
+            }
+            warn!(
+                "resuming download of {} at byte {}: {}",
+                relative_path, progress.downloaded, error
+            );
+        }
+    }
+
+    /// Download the next range of the artifact, returning `true` once it is complete
+    async fn download_range<W>(
+        &mut self,
+        path: &Path<'_>,
+        progress: &mut DownloadProgress,
+        writer: &mut W,
+    ) -> Result<bool>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let start = progress.downloaded;
+        if progress.total.is_some_and(|total| start >= total) {
+            return Ok(true);
+        }
+        let end = self.chunk_size.map(|chunk_size| {
+            let end = start + chunk_size - 1;
+            progress.total.map_or(end, |total| end.min(total - 1))
+        });
+        let mut response = self.jenkins_client.get_range(path, start, end).await?;
+
+        let mut to_skip = 0;
+        let full_response = match response.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                progress.total = total_from_content_range(&response).or(progress.total);
+                false
+            }
+            // past the end of the artifact, or an empty artifact answered with `bytes */0`
+            StatusCode::RANGE_NOT_SATISFIABLE
+                if start > 0 || total_from_content_range(&response) == Some(0) =>
+            {
+                return Ok(true)
+            }
+            _ => {
+                // the server ignores range requests, skip what was already downloaded
+                response = response.error_for_status()?;
+                progress.total = response.content_length();
+                to_skip = start;
+                true
+            }
+        };
+
+        let mut received = 0;
+        while let Some(chunk) = response.chunk().await? {
+            let mut chunk = &chunk[..];
+            if to_skip > 0 {
+                let skipped = to_skip.min(chunk.len() as u64);
+                to_skip -= skipped;
+                chunk = &chunk[skipped as usize..];
+            }
+            writer.write_all(chunk).await?;
+            received += chunk.len() as u64;
+            progress.downloaded += chunk.len() as u64;
+            if let Some(callback) = self.progress.as_mut() {
+                callback(*progress);
+            }
+        }
+
+        Ok(match progress.total {
+            Some(total) => progress.downloaded >= total,
+            None => full_response || received == 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::build::common::build_at;
+    use crate::client;
+
+    #[tokio::test]
+    async fn can_get_artifact_sizes() {
+        use crate::build::Build;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let build = build_at(&format!("{}/job/installer/1/", server.url()));
+
+        let artifacts = server
+            .mock("GET", "/job/installer/1/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "artifacts[displayPath,fileName,relativePath]".to_string(),
+            ))
+            .with_body(
+                serde_json::json!({
+                    "artifacts": [{
+                        "displayPath": "app installer.bin",
+                        "fileName": "app installer.bin",
+                        "relativePath": "dist/app installer.bin"
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+        let size = server
+            .mock("HEAD", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .with_header("content-length", "2048")
+            .with_header("last-modified", "Sun, 06 Nov 1994 08:49:37 GMT")
+            .create();
+
+        let sized = build.get_artifact_sizes(&jenkins_client).await.unwrap();
+
+        assert_eq!(sized.len(), 1);
+        assert_eq!(sized[0].artifact.file_name, "app installer.bin");
+        assert_eq!(sized[0].size, Some(2048));
+        assert_eq!(
+            sized[0].last_modified,
+            Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(784_111_777))
+        );
+        artifacts.assert();
+        size.assert();
+    }
+
+    #[tokio::test]
+    async fn can_download_artifact_in_chunks() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let build = build_at(&format!("{}/job/installer/1/", server.url()));
+
+        let mut mocks = vec![];
+        for (range, content_range, body) in [
+            ("bytes=0-3", "bytes 0-3/10", "0123"),
+            ("bytes=4-7", "bytes 4-7/10", "4567"),
+            ("bytes=8-9", "bytes 8-9/10", "89"),
+        ] {
+            mocks.push(
+                server
+                    .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+                    .match_header("range", range)
+                    .with_status(206)
+                    .with_header("content-range", content_range)
+                    .with_body(body)
+                    .create(),
+            );
+        }
+
+        let reported = Arc::new(Mutex::new(vec![]));
+        let reported_in_callback = reported.clone();
+        let bytes = build.artifacts[0]
+            .download_with(&jenkins_client, &build)
+            .with_chunk_size(4)
+            .with_progress(move |progress| {
+                reported_in_callback
+                    .lock()
+                    .unwrap()
+                    .push(progress.downloaded)
+            })
+            .to_vec()
+            .await
+            .unwrap();
+
+        assert_eq!(bytes, b"0123456789");
+        assert_eq!(*reported.lock().unwrap(), vec![4, 8, 10]);
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn can_resume_truncated_download() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let build = build_at(&format!("{}/job/installer/1/", server.url()));
+
+        let truncated = server
+            .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .match_header("range", "bytes=0-")
+            .with_status(206)
+            .with_header("content-range", "bytes 0-9/10")
+            .with_body("012345")
+            .create();
+        let resumed = server
+            .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .match_header("range", "bytes=6-")
+            .with_status(206)
+            .with_header("content-range", "bytes 6-9/10")
+            .with_body("6789")
+            .create();
+
+        let bytes = build.artifacts[0]
+            .download_with(&jenkins_client, &build)
+            .to_vec()
+            .await
+            .unwrap();
+
+        assert_eq!(bytes, b"0123456789");
+        truncated.assert();
+        resumed.assert();
+    }
+
+    #[tokio::test]
+    async fn can_give_up_on_empty_partial_responses() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let build = build_at(&format!("{}/job/installer/1/", server.url()));
+
+        let first = server
+            .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .match_header("range", "bytes=0-")
+            .with_status(206)
+            .with_header("content-range", "bytes 0-9/10")
+            .with_body("0123")
+            .create();
+        let empty = server
+            .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .match_header("range", "bytes=4-")
+            .with_status(206)
+            .with_header("content-range", "bytes 4-9/10")
+            .expect(3)
+            .create();
+
+        let error = build.artifacts[0]
+            .download_with(&jenkins_client, &build)
+            .with_max_retries(2)
+            .to_vec()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<client::Error>(),
+            Some(client::Error::DownloadStalled { downloaded: 4, .. })
+        ));
+        first.assert();
+        empty.assert();
+    }
+
+    #[tokio::test]
+    async fn can_download_empty_artifact() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let build = build_at(&format!("{}/job/installer/1/", server.url()));
+
+        let empty = server
+            .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .match_header("range", "bytes=0-3")
+            .with_status(416)
+            .with_header("content-range", "bytes */0")
+            .expect(1)
+            .create();
+
+        let bytes = build.artifacts[0]
+            .download_with(&jenkins_client, &build)
+            .with_chunk_size(4)
+            .to_vec()
+            .await
+            .unwrap();
+
+        assert!(bytes.is_empty());
+        empty.assert();
+    }
+
+    #[test]
+    fn can_match_glob() {
+        assert!(super::matches_glob("*.jar", "app-1.2.jar"));
+        assert!(super::matches_glob("app-?.*.jar", "app-1.2.jar"));
+        assert!(super::matches_glob("*", ""));
+        assert!(!super::matches_glob("*.jar", "app.jar.sha1"));
+        assert!(!super::matches_glob("app?.jar", "app.jar"));
+    }
+
+    #[tokio::test]
+    async fn can_download_artifact_by_name() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let build = build_at(&format!("{}/job/installer/1/", server.url()));
+
+        let mock = server
+            .mock("GET", "/job/installer/1/artifact/dist/app%20installer.bin")
+            .with_body("0123456789")
+            .expect(2)
+            .create();
+
+        assert!(build.get_artifact_by_name("*.jar").is_none());
+        let artifact = build.get_artifact_by_name("app *.bin").unwrap();
+        let bytes = artifact.download(&jenkins_client, &build).await.unwrap();
+        assert_eq!(bytes, b"0123456789");
+
+        let path =
+            std::env::temp_dir().join(format!("jenkins-api-artifact-{}", std::process::id()));
+        let written = artifact
+            .download_to(&jenkins_client, &build, &path)
+            .await
+            .unwrap();
+        assert_eq!(written, 10);
+        assert_eq!(std::fs::read(&path).unwrap(), b"0123456789");
+        std::fs::remove_file(&path).unwrap();
+        mock.assert();
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -10,7 +10,7 @@ use crate::user::ShortUser;
 
 build_with_common_fields_and_impl!(
     /// A `Build` from a BuildFlowJob
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct BuildFlowRun<ParentJob = BuildFlowJob> {
         /// Change set for this build
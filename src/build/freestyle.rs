@@ -1,16 +1,16 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
-use super::{Artifact, Build, BuildStatus};
-use crate::action::CommonAction;
+use super::{Artifact, Build, BuildStatus, ShortBuild};
+use crate::action::{CommonAction, GitBuildData};
 use crate::changeset;
 use crate::job::FreeStyleProject;
 use crate::user::ShortUser;
 
 build_with_common_fields_and_impl!(
     /// A `Build` from a FreeStyleProject
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct FreeStyleBuild<ParentJob = FreeStyleProject> {
         /// Which slave was it build on
@@ -19,8 +19,143 @@ build_with_common_fields_and_impl!(
         pub change_set: changeset::CommonChangeSetList,
         /// List of user ids who made a change since the last non-broken build
         pub culprits: Vec<ShortUser>,
+        /// Previous build
+        pub previous_build: Option<ShortBuild>,
+        /// Next build
+        pub next_build: Option<ShortBuild>,
     }
 );
 register_class!("hudson.model.FreeStyleBuild" => FreeStyleBuild);
 
-impl FreeStyleBuild {}
+impl FreeStyleBuild {
+    /// Name of the agent the build ran on, `None` when it ran on the built-in node
+    pub fn built_on_node(&self) -> Option<&str> {
+        Some(self.built_on.as_str()).filter(|node| !node.is_empty())
+    }
+
+    /// Changes in this build
+    pub fn changes(&self) -> impl Iterator<Item = &changeset::CommonChangeSet> {
+        self.change_set.items.iter()
+    }
+
+    /// Git revision built and remote URLs, when the job checks out a git repository
+    pub fn git_build_data(&self) -> Option<GitBuildData> {
+        self.actions
+            .iter()
+            .find_map(|action| action.as_variant().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_freestyle_build() {
+        let build: FreeStyleBuild = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.model.FreeStyleBuild",
+            "actions": [
+                {
+                    "_class": "hudson.model.CauseAction",
+                    "causes": [{
+                        "_class": "hudson.triggers.SCMTrigger$SCMTriggerCause",
+                        "shortDescription": "Started by an SCM change"
+                    }]
+                },
+                {},
+                {
+                    "_class": "hudson.plugins.git.util.BuildData",
+                    "buildsByBranchName": {
+                        "refs/remotes/origin/main": {
+                            "_class": "hudson.plugins.git.util.Build",
+                            "buildNumber": 8,
+                            "buildResult": null,
+                            "marked": {
+                                "SHA1": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                                "branch": [{
+                                    "SHA1": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                                    "name": "refs/remotes/origin/main"
+                                }]
+                            },
+                            "revision": {
+                                "SHA1": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                                "branch": [{
+                                    "SHA1": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                                    "name": "refs/remotes/origin/main"
+                                }]
+                            }
+                        }
+                    },
+                    "lastBuiltRevision": {
+                        "SHA1": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                        "branch": [{
+                            "SHA1": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                            "name": "refs/remotes/origin/main"
+                        }]
+                    },
+                    "remoteUrls": ["https://git.example.com/team/freestyle.git"],
+                    "scmName": ""
+                },
+                {"_class": "hudson.plugins.git.GitTagAction"},
+                {},
+                {"_class": "org.jenkinsci.plugins.displayurlapi.actions.RunDisplayAction"}
+            ],
+            "artifacts": [],
+            "building": false,
+            "description": null,
+            "displayName": "#8",
+            "duration": 1543,
+            "estimatedDuration": 1620,
+            "executor": null,
+            "fullDisplayName": "freestyle #8",
+            "id": "8",
+            "keepLog": false,
+            "number": 8,
+            "queueId": 52,
+            "result": "SUCCESS",
+            "timestamp": 1_700_000_000_000u64,
+            "url": "http://jenkins/job/freestyle/8/",
+            "builtOn": "agent-1",
+            "changeSet": {
+                "_class": "hudson.plugins.git.GitChangeSetList",
+                "items": [{
+                    "_class": "hudson.plugins.git.GitChangeSet",
+                    "affectedPaths": ["src/main.rs"],
+                    "commitId": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                    "timestamp": 1_699_999_000_000u64,
+                    "author": {"absoluteUrl": "http://jenkins/user/jane", "fullName": "Jane Doe"},
+                    "authorEmail": "jane@example.com",
+                    "comment": "Fix the build\n",
+                    "date": "2023-11-14 21:56:40 +0000",
+                    "id": "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b",
+                    "msg": "Fix the build",
+                    "paths": [{"editType": "edit", "file": "src/main.rs"}]
+                }],
+                "kind": "git"
+            },
+            "culprits": [{"absoluteUrl": "http://jenkins/user/jane", "fullName": "Jane Doe"}],
+            "nextBuild": null,
+            "previousBuild": {
+                "_class": "hudson.model.FreeStyleBuild",
+                "number": 7,
+                "url": "http://jenkins/job/freestyle/7/"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(build.built_on_node(), Some("agent-1"));
+        let change: changeset::GitChangeSet = build.changes().next().unwrap().as_variant().unwrap();
+        assert_eq!(change.author.full_name, "Jane Doe");
+        let git = build.git_build_data().unwrap();
+        assert_eq!(
+            git.remote_urls,
+            vec!["https://git.example.com/team/freestyle.git"]
+        );
+        assert_eq!(
+            git.last_built_revision.branch[0].name,
+            "refs/remotes/origin/main"
+        );
+        assert_eq!(build.previous_build.unwrap().number, 7);
+        assert!(build.next_build.is_none());
+    }
+}
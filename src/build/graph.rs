@@ -0,0 +1,527 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Deserialize;
+
+use crate::action::causes::{CommonCause, UpstreamCause};
+use crate::action::{CauseAction, CommonAction};
+use crate::build::BuildStatus;
+use crate::client::{self, Result, TreeBuilder};
+use crate::client_internals::path::Path;
+use crate::client_internals::{AdvancedQuery, InternalAdvancedQueryParams, ResponseExt};
+use crate::job::ShortJob;
+use crate::Jenkins;
+
+/// A `Build` in a `BuildGraph`
+#[derive(Debug, Clone)]
+pub struct BuildGraphNode {
+    /// Full name of the job of this build
+    pub job_name: String,
+    /// Number of the build
+    pub number: u32,
+    /// URL of the build
+    pub url: String,
+    /// Result of the build, `None` while it is running
+    pub result: Option<BuildStatus>,
+}
+
+/// A trigger relation between two nodes of a `BuildGraph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildGraphEdge {
+    /// Index in `BuildGraph::nodes` of the build that triggered `downstream`
+    pub upstream: usize,
+    /// Index in `BuildGraph::nodes` of the build triggered by `upstream`
+    pub downstream: usize,
+}
+
+/// Graph of the builds triggered downstream of a `Build`
+#[derive(Debug, Clone)]
+pub struct BuildGraph {
+    /// Builds of the graph, the first one is the build the graph was requested from
+    pub nodes: Vec<BuildGraphNode>,
+    /// Trigger relations between the builds
+    pub edges: Vec<BuildGraphEdge>,
+}
+
+impl BuildGraph {
+    /// Build the graph was requested from
+    pub fn root(&self) -> &BuildGraphNode {
+        &self.nodes[0]
+    }
+
+    /// Builds directly triggered by the node at `index`
+    pub fn downstream_of(&self, index: usize) -> impl Iterator<Item = &BuildGraphNode> {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.upstream == index)
+            .map(move |edge| &self.nodes[edge.downstream])
+    }
+}
+
+/// Cause that started a chain of builds triggering each other
+#[derive(Debug, Clone)]
+pub struct RootCause {
+    /// The cause, usually a user, a timer or a SCM change
+    pub cause: CommonCause,
+    /// URL of the build with this cause
+    pub build_url: String,
+    /// Number of upstream builds followed to find the cause
+    pub depth: u32,
+}
+
+/// Maximum number of upstream builds followed to find a root cause
+const MAX_UPSTREAM_DEPTH: u32 = 32;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct BuildSummary {
+    number: u32,
+    url: String,
+    result: Option<BuildStatus>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct BuildWithCauses {
+    number: u32,
+    url: String,
+    result: Option<BuildStatus>,
+    #[serde(default)]
+    actions: Vec<CommonAction>,
+}
+
+impl BuildWithCauses {
+    fn is_triggered_by(&self, job_name: &str, number: u32) -> bool {
+        self.actions
+            .iter()
+            .filter_map(|action| action.as_variant::<CauseAction>().ok())
+            .flat_map(|action| action.causes)
+            .filter_map(|cause| cause.as_variant::<UpstreamCause>().ok())
+            .any(|cause| cause.upstream_project == job_name && cause.upstream_build == number)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct JobWithBuilds {
+    full_name: String,
+    #[serde(default)]
+    downstream_projects: Vec<ShortJob>,
+    #[serde(default)]
+    builds: Vec<BuildWithCauses>,
+}
+
+/// Path of the job that ran the build at `build_path`, in the same folders
+fn job_of_build_path(build_path: Path<'_>) -> Option<Path<'_>> {
+    match build_path {
+        Path::Build {
+            job_name,
+            configuration,
+            ..
+        } => Some(Path::Job {
+            name: job_name,
+            configuration,
+        }),
+        Path::InFolder { folder_name, path } => {
+            job_of_build_path(*path).map(|path| Path::InFolder {
+                folder_name,
+                path: Box::new(path),
+            })
+        }
+        _ => None,
+    }
+}
+
+async fn get_job_with_builds(jenkins_client: &Jenkins, path: &Path<'_>) -> Result<JobWithBuilds> {
+    let tree = TreeBuilder::new()
+        .with_field("fullName")
+        .with_field(
+            TreeBuilder::object("downstreamProjects")
+                .with_subfield("name")
+                .with_subfield("url"),
+        )
+        .with_field(
+            TreeBuilder::object("builds")
+                .with_subfield("number")
+                .with_subfield("url")
+                .with_subfield("result")
+                .with_subfield(
+                    TreeBuilder::object("actions").with_subfield(
+                        TreeBuilder::object("causes")
+                            .with_subfield("shortDescription")
+                            .with_subfield("upstreamProject")
+                            .with_subfield("upstreamBuild")
+                            .with_subfield("upstreamUrl"),
+                    ),
+                ),
+        )
+        .build();
+    let response = jenkins_client
+        .get_with_params(
+            path,
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(tree)),
+        )
+        .await?
+        .parse_json()
+        .await?;
+    Ok(response)
+}
+
+pub(crate) async fn get_downstream_builds(
+    jenkins_client: &Jenkins,
+    build_url: &str,
+) -> Result<BuildGraph> {
+    let invalid_url = || client::Error::InvalidUrl {
+        url: build_url.to_string(),
+        expected: client::error::ExpectedType::Build,
+    };
+    let build_path = jenkins_client.url_to_path(build_url);
+    let root_job_path = job_of_build_path(build_path.clone()).ok_or_else(invalid_url)?;
+
+    let root: BuildSummary = jenkins_client
+        .get_with_params(
+            &build_path,
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                TreeBuilder::new()
+                    .with_field("number")
+                    .with_field("url")
+                    .with_field("result")
+                    .build(),
+            )),
+        )
+        .await?
+        .parse_json()
+        .await?;
+    let root_job = get_job_with_builds(jenkins_client, &root_job_path).await?;
+
+    let mut graph = BuildGraph {
+        nodes: vec![BuildGraphNode {
+            job_name: root_job.full_name.clone(),
+            number: root.number,
+            url: root.url,
+            result: root.result,
+        }],
+        edges: vec![],
+    };
+    let mut jobs: HashMap<String, JobWithBuilds> = HashMap::new();
+    let mut jobs_of_nodes = vec![root_job_path.to_string()];
+    let _ = jobs.insert(root_job_path.to_string(), root_job);
+
+    let mut to_visit = VecDeque::from(vec![0]);
+    while let Some(upstream) = to_visit.pop_front() {
+        let downstream_job_urls = jobs[&jobs_of_nodes[upstream]]
+            .downstream_projects
+            .iter()
+            .map(|job| job.url.clone())
+            .collect::<Vec<_>>();
+        for job_url in downstream_job_urls {
+            let path = jenkins_client.url_to_path(&job_url);
+            let key = path.to_string();
+            if !jobs.contains_key(&key) {
+                let job = get_job_with_builds(jenkins_client, &path).await?;
+                let _ = jobs.insert(key.clone(), job);
+            }
+            let job = &jobs[&key];
+            let node = &graph.nodes[upstream];
+            let triggered = job
+                .builds
+                .iter()
+                .filter(|build| build.is_triggered_by(&node.job_name, node.number))
+                .collect::<Vec<_>>();
+            for build in triggered {
+                let downstream = match graph.nodes.iter().position(|node| node.url == build.url) {
+                    Some(index) => index,
+                    None => {
+                        graph.nodes.push(BuildGraphNode {
+                            job_name: job.full_name.clone(),
+                            number: build.number,
+                            url: build.url.clone(),
+                            result: build.result,
+                        });
+                        jobs_of_nodes.push(key.clone());
+                        to_visit.push_back(graph.nodes.len() - 1);
+                        graph.nodes.len() - 1
+                    }
+                };
+                graph.edges.push(BuildGraphEdge {
+                    upstream,
+                    downstream,
+                });
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+pub(crate) async fn get_root_cause(
+    jenkins_client: &Jenkins,
+    build_url: &str,
+) -> Result<Option<RootCause>> {
+    #[derive(Deserialize)]
+    struct BuildCauses {
+        url: String,
+        #[serde(default)]
+        actions: Vec<CommonAction>,
+    }
+
+    let tree = || {
+        TreeBuilder::new()
+            .with_field("url")
+            .with_field(
+                TreeBuilder::object("actions")
+                    .with_subfield(TreeBuilder::object("causes").with_subfield("*")),
+            )
+            .build()
+    };
+    let mut path = build_url.to_string();
+    let mut visited = HashSet::new();
+    let mut depth = 0;
+    loop {
+        let build: BuildCauses = jenkins_client
+            .get_with_params(
+                &jenkins_client.url_to_path(&path),
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(tree())),
+            )
+            .await?
+            .parse_json()
+            .await?;
+        let cause = match build
+            .actions
+            .iter()
+            .filter_map(|action| action.as_variant::<CauseAction>().ok())
+            .flat_map(|action| action.causes)
+            .next()
+        {
+            Some(cause) => cause,
+            None => return Ok(None),
+        };
+        let _ = visited.insert(jenkins_client.url_to_path(&build.url).to_string());
+        let upstream = match cause.as_variant::<UpstreamCause>() {
+            Ok(upstream) if depth < MAX_UPSTREAM_DEPTH => upstream,
+            _ => {
+                return Ok(Some(RootCause {
+                    cause,
+                    build_url: build.url,
+                    depth,
+                }))
+            }
+        };
+        path = format!(
+            "/{}/{}/",
+            upstream.upstream_url.trim_matches('/'),
+            upstream.upstream_build
+        );
+        if visited.contains(&jenkins_client.url_to_path(&path).to_string()) {
+            return Ok(Some(RootCause {
+                cause,
+                build_url: build.url,
+                depth,
+            }));
+        }
+        depth += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::{Build, CommonBuild};
+
+    fn triggered_by(server_url: &str, job: &str, number: u32, upstream: u32) -> serde_json::Value {
+        serde_json::json!({
+            "number": number,
+            "url": format!("{}/job/{}/{}/", server_url, job, number),
+            "result": "SUCCESS",
+            "actions": [{
+                "_class": "hudson.model.CauseAction",
+                "causes": [{
+                    "_class": "hudson.model.Cause$UpstreamCause",
+                    "shortDescription": "Started by upstream project",
+                    "upstreamBuild": upstream,
+                    "upstreamProject": "compile",
+                    "upstreamUrl": "job/compile/"
+                }]
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn can_get_downstream_builds() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let jenkins_client = crate::JenkinsBuilder::new(&url).build().unwrap();
+
+        let _build = server
+            .mock("GET", "/job/compile/2/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "number": 2,
+                    "url": format!("{}/job/compile/2/", url),
+                    "result": "SUCCESS"
+                })
+                .to_string(),
+            )
+            .create();
+        let _compile = server
+            .mock("GET", "/job/compile/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "fullName": "compile",
+                    "downstreamProjects": [
+                        {"name": "test", "url": format!("{}/job/test/", url)},
+                        {"name": "deploy", "url": format!("{}/job/deploy/", url)}
+                    ],
+                    "builds": []
+                })
+                .to_string(),
+            )
+            .create();
+        let _test = server
+            .mock("GET", "/job/test/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "fullName": "test",
+                    "downstreamProjects": [],
+                    "builds": [
+                        triggered_by(&url, "test", 8, 2),
+                        triggered_by(&url, "test", 7, 1),
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+        let _deploy = server
+            .mock("GET", "/job/deploy/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "fullName": "deploy",
+                    "downstreamProjects": [],
+                    "builds": [triggered_by(&url, "deploy", 4, 1)]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let build: CommonBuild = serde_json::from_value(serde_json::json!({
+            "url": format!("{}/job/compile/2/", url),
+            "number": 2,
+            "duration": 10,
+            "estimatedDuration": 10,
+            "timestamp": 1_500_000_000_000u64,
+            "keepLog": false,
+            "result": "SUCCESS",
+            "displayName": "#2",
+            "building": false,
+            "id": "2",
+            "queueId": 3,
+            "actions": [],
+            "artifacts": []
+        }))
+        .unwrap();
+        let graph = build.get_downstream_builds(&jenkins_client).await.unwrap();
+
+        assert_eq!(graph.root().job_name, "compile");
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(
+            graph.edges,
+            vec![BuildGraphEdge {
+                upstream: 0,
+                downstream: 1
+            }]
+        );
+        let downstream = graph.downstream_of(0).collect::<Vec<_>>();
+        assert_eq!(downstream[0].job_name, "test");
+        assert_eq!(downstream[0].number, 8);
+    }
+
+    #[tokio::test]
+    async fn can_get_root_cause() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let jenkins_client = crate::JenkinsBuilder::new(&url).build().unwrap();
+
+        let _deploy = server
+            .mock("GET", "/job/deploy/4/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(triggered_by(&url, "deploy", 4, 2).to_string())
+            .create();
+        let _compile = server
+            .mock("GET", "/job/compile/2/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "url,actions[causes[*]]".to_string(),
+            ))
+            .with_body(
+                serde_json::json!({
+                    "url": format!("{}/job/compile/2/", url),
+                    "actions": [{}, {
+                        "_class": "hudson.model.CauseAction",
+                        "causes": [{
+                            "_class": "hudson.model.Cause$UserIdCause",
+                            "shortDescription": "Started by user Alice",
+                            "userId": "alice",
+                            "userName": "Alice"
+                        }]
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let root = get_root_cause(&jenkins_client, &format!("{}/job/deploy/4/", url))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(root.depth, 1);
+        assert_eq!(root.build_url, format!("{}/job/compile/2/", url));
+        let user: crate::action::causes::UserIdCause = root.cause.as_variant().unwrap();
+        assert_eq!(user.user_id, "alice");
+    }
+
+    #[tokio::test]
+    async fn can_get_root_cause_across_folders() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let jenkins_client = crate::JenkinsBuilder::new(&url).build().unwrap();
+
+        let in_folder = server
+            .mock("GET", "/job/team/job/compile/2/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(triggered_by(&url, "team/job/compile", 2, 2).to_string())
+            .create();
+        let compile = server
+            .mock("GET", "/job/compile/2/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "url": format!("{}/job/compile/2/", url),
+                    "actions": [{
+                        "_class": "hudson.model.CauseAction",
+                        "causes": [{
+                            "_class": "hudson.model.Cause$UserIdCause",
+                            "shortDescription": "Started by user Alice",
+                            "userId": "alice",
+                            "userName": "Alice"
+                        }]
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let root = get_root_cause(&jenkins_client, &format!("{}/job/team/job/compile/2/", url))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(root.depth, 1);
+        assert_eq!(root.build_url, format!("{}/job/compile/2/", url));
+        in_folder.assert();
+        compile.assert();
+    }
+}
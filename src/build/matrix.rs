@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -10,7 +10,7 @@ use crate::user::ShortUser;
 
 build_with_common_fields_and_impl!(
     /// A `Build` from a MatrixProject
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MatrixBuild<ParentJob = MatrixProject> {
         /// Change set for this build
@@ -29,7 +29,7 @@ impl MatrixBuild {}
 
 build_with_common_fields_and_impl!(
     /// A `Build` from a MatrixConfiguration
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MatrixRun<ParentJob = MatrixConfiguration> {
         /// Change set for this build
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -12,7 +12,7 @@ use crate::user::ShortUser;
 
 build_with_common_fields_and_impl!(
     /// A `Build` of a MavenModuleSet
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MavenModuleSetBuild<ParentJob = MavenModuleSet> {
         /// Change set for this build
@@ -33,7 +33,7 @@ impl MavenModuleSetBuild {}
 
 build_with_common_fields_and_impl!(
     /// A `Build` of a MavenModule
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MavenBuild<ParentJob = MavenModule> {
         /// Change set for this build
@@ -1,42 +1,100 @@
 //! Jenkins Builds
 
-use crate::client::Result;
-use crate::client_internals::path::{Name, Path};
+use crate::client::{self, Result};
+use crate::client_internals::path::Path;
+use crate::client_internals::ResponseExt;
 use crate::job::JobName;
 use crate::Jenkins;
 
 #[macro_use]
 mod common;
-pub use self::common::{Artifact, Build, BuildNumber, BuildStatus, CommonBuild, ShortBuild};
+pub use self::common::{
+    Artifact, Build, BuildNumber, BuildState, BuildStatus, CommonBuild, DisplayUrls, ShortBuild,
+};
+mod console;
+pub use self::console::ConsoleChunk;
+mod delete;
+pub use self::delete::BuildRange;
+mod download;
+pub(crate) use self::download::get_artifact_size;
+pub use self::download::{ArtifactDownload, ArtifactSize, DownloadProgress};
 mod flow;
 pub use self::flow::BuildFlowRun;
+mod graph;
+pub use self::graph::{BuildGraph, BuildGraphEdge, BuildGraphNode, RootCause};
 mod freestyle;
 pub use self::freestyle::FreeStyleBuild;
 mod pipeline;
-pub use self::pipeline::WorkflowRun;
+mod progress;
+pub use self::pipeline::{FlowGraph, WorkflowRun};
+mod stage_log;
+pub use self::stage_log::StageLogChunk;
+mod stop;
+mod summary;
+pub use self::summary::{BuildSummary, TestCounts};
+mod timestamps;
+pub use self::timestamps::{Timestamp, TimestampedLine, TimestampsQuery};
+mod warnings;
+pub use self::warnings::{AnalysisResult, AnalysisTool, Issue, IssueSet, Severity};
 mod matrix;
 pub use self::matrix::{MatrixBuild, MatrixRun};
 mod maven;
 pub use self::maven::{MavenBuild, MavenModuleSetBuild};
 mod multijob;
-pub use self::multijob::MultiJobBuild;
+pub use self::multijob::{MultiJobBuild, MultiJobPhase, MultiJobSubBuild};
+
+/// Get the path of the build at `url`, checking that it is a build
+pub(crate) fn build_path<'a>(jenkins_client: &Jenkins, url: &'a str) -> Result<Path<'a>> {
+    let build = jenkins_client.url_to_path(url);
+    match build {
+        Path::Build { .. } => Ok(build),
+        Path::InFolder { ref path, .. } if matches!(path.as_ref(), Path::Build { .. }) => Ok(build),
+        _ => Err(client::Error::InvalidUrl {
+            url: url.to_string(),
+            expected: client::error::ExpectedType::Build,
+        }
+        .into()),
+    }
+}
 
 impl Jenkins {
-    /// Get a build from a `job_name` and `build_number`
+    /// Get a build from a `job_name`, or its full name or `FolderPath` in nested folders, and
+    /// `build_number`
     pub async fn get_build<'a, J, B>(&self, job_name: J, build_number: B) -> Result<CommonBuild>
     where
         J: Into<JobName<'a>>,
         B: Into<BuildNumber>,
     {
+        let number = build_number.into();
         let response = self
-            .get(&Path::Build {
-                job_name: Name::Name(job_name.into().0),
-                number: build_number.into(),
+            .get(&job_name.into().to_path_with(|job_name| Path::Build {
+                job_name,
+                number,
                 configuration: None,
-            })
+            })?)
             .await?
-            .json()
+            .parse_json()
             .await?;
         Ok(response)
     }
+
+    /// Get the console output of a build from a `job_name`, or its full name or `FolderPath`
+    /// in nested folders, and `build_number`
+    pub async fn get_console<'a, J, B>(&self, job_name: J, build_number: B) -> Result<String>
+    where
+        J: Into<JobName<'a>>,
+        B: Into<BuildNumber>,
+    {
+        let number = build_number.into();
+        Ok(self
+            .get_endpoint(&job_name.into().to_path_with(|job_name| Path::ConsoleText {
+                job_name,
+                number,
+                configuration: None,
+                folder_name: None,
+            })?)
+            .await?
+            .text()
+            .await?)
+    }
 }
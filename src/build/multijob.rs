@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -11,7 +11,7 @@ use crate::user::ShortUser;
 
 build_with_common_fields_and_impl!(
     /// A `Build` from a MultiJobProject
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MultiJobBuild<ParentJob = MultiJobProject> {
         /// Change set for this build
@@ -26,9 +26,51 @@ build_with_common_fields_and_impl!(
 );
 register_class!("com.tikal.jenkins.plugins.multijob.MultiJobBuild" => MultiJobBuild);
 
-impl MultiJobBuild {}
+impl MultiJobBuild {
+    /// Group the sub-builds of this build by phase, in the order the phases were run
+    pub fn phases(&self) -> Vec<MultiJobPhase<'_>> {
+        let mut phases: Vec<MultiJobPhase<'_>> = vec![];
+        for sub_build in &self.sub_builds {
+            match phases
+                .iter_mut()
+                .find(|phase| phase.name == sub_build.phase_name)
+            {
+                Some(phase) => phase.sub_builds.push(sub_build),
+                None => phases.push(MultiJobPhase {
+                    name: &sub_build.phase_name,
+                    sub_builds: vec![sub_build],
+                }),
+            }
+        }
+        phases
+    }
+}
+
+/// A phase of a multi job build, with the sub-builds it triggered
+#[derive(Debug, Clone)]
+pub struct MultiJobPhase<'a> {
+    /// Name of the phase
+    pub name: &'a str,
+    /// Sub-builds triggered during this phase
+    pub sub_builds: Vec<&'a MultiJobSubBuild>,
+}
 
-#[derive(Deserialize, Debug)]
+impl MultiJobPhase<'_> {
+    /// Worst result of the sub-builds of this phase, or `None` if one of them is still running
+    pub fn result(&self) -> Option<BuildStatus> {
+        let mut worst = BuildStatus::Success;
+        for sub_build in &self.sub_builds {
+            match sub_build.result {
+                None => return None,
+                Some(result) if result.severity() > worst.severity() => worst = result,
+                Some(_) => (),
+            }
+        }
+        Some(worst)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 /// A sub-build from a multi job project
 pub struct MultiJobSubBuild {
@@ -55,3 +97,71 @@ pub struct MultiJobSubBuild {
     /// Url of build
     pub url: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sub_build(phase_name: &str, job_name: &str, result: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "abort": false,
+            "buildNumber": 1,
+            "duration": "1 sec",
+            "icon": "blue.png",
+            "jobName": job_name,
+            "parentBuildNumber": 1,
+            "parentJobName": "orchestrator",
+            "phaseName": phase_name,
+            "result": result,
+            "retry": false,
+            "url": format!("job/{}/1/", job_name)
+        })
+    }
+
+    #[test]
+    fn can_group_sub_builds_by_phase() {
+        let build: MultiJobBuild = serde_json::from_value(serde_json::json!({
+            "url": "http://none:8080/job/orchestrator/1/",
+            "number": 1,
+            "duration": 10,
+            "estimatedDuration": 10,
+            "timestamp": 1_500_000_000_000u64,
+            "keepLog": false,
+            "result": null,
+            "displayName": "#1",
+            "building": true,
+            "id": "1",
+            "queueId": 2,
+            "actions": [],
+            "artifacts": [],
+            "changeSet": {"items": []},
+            "builtOn": "",
+            "culprits": [],
+            "subBuilds": [
+                sub_build("build", "compile", Some("SUCCESS")),
+                sub_build("test", "unit", Some("UNSTABLE")),
+                sub_build("test", "integration", Some("SUCCESS")),
+                sub_build("deploy", "staging", None),
+            ]
+        }))
+        .unwrap();
+
+        let phases = build.phases();
+
+        assert_eq!(
+            phases.iter().map(|phase| phase.name).collect::<Vec<_>>(),
+            vec!["build", "test", "deploy"]
+        );
+        assert_eq!(
+            phases[1]
+                .sub_builds
+                .iter()
+                .map(|sub_build| sub_build.job_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["unit", "integration"]
+        );
+        assert_eq!(phases[0].result(), Some(BuildStatus::Success));
+        assert_eq!(phases[1].result(), Some(BuildStatus::Unstable));
+        assert_eq!(phases[2].result(), None);
+    }
+}
@@ -1,23 +1,393 @@
-use serde::Deserialize;
+use std::time::Duration;
+
+use futures_util::stream::Stream;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
+use super::build_path;
+use super::stage_log::{stream_stage_log, StageLogChunk};
 use super::{Artifact, Build, BuildStatus, ShortBuild};
-use crate::action::CommonAction;
+use crate::action::pipeline::CommonPipelineNode;
+use crate::action::{CommonAction, GitBuildData};
 use crate::changeset;
+use crate::client::{Result, TreeBuilder};
+use crate::client_internals::{AdvancedQuery, InternalAdvancedQueryParams, Path, ResponseExt};
 use crate::job::WorkflowJob;
+use crate::user::ShortUser;
+use crate::Jenkins;
 
 build_with_common_fields_and_impl!(
     /// A `Build` from a WorkflowJob
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct WorkflowRun<ParentJob = WorkflowJob> {
         /// Change set for this build
         pub change_sets: Vec<changeset::CommonChangeSetList>,
         /// Previous build
         pub previous_build: Option<ShortBuild>,
+        /// Next build
+        pub next_build: Option<ShortBuild>,
+        /// List of user ids who made a change since the last non-broken build, not provided by
+        /// older versions of the Pipeline plugin
+        #[serde(default)]
+        pub culprits: Vec<ShortUser>,
     }
 );
 register_class!("org.jenkinsci.plugins.workflow.job.WorkflowRun" => WorkflowRun);
 
-impl WorkflowRun {}
+/// Line logged by the Pipeline plugin when a run resumes after a restart of Jenkins
+const RESUMED_LOG_LINE: &str = "Resuming build at ";
+
+impl WorkflowRun {
+    /// Changes in this build, from all the repositories checked out by the pipeline
+    pub fn changes(&self) -> impl Iterator<Item = &changeset::CommonChangeSet> {
+        self.change_sets
+            .iter()
+            .flat_map(|change_set| change_set.items.iter())
+    }
+
+    /// Git revisions built and remote URLs, one for each repository checked out by the pipeline
+    pub fn git_build_data(&self) -> Vec<GitBuildData> {
+        self.actions
+            .iter()
+            .filter_map(|action| action.as_variant().ok())
+            .collect()
+    }
+
+    /// Was this run resumed after a restart of Jenkins, as logged in its console
+    ///
+    /// The console output is read line by line, and the download stops at the first line
+    /// logged on resume.
+    pub async fn was_resumed(&self, jenkins_client: &Jenkins) -> Result<bool> {
+        let mut response = jenkins_client
+            .get_endpoint_with_params(
+                &Path::ProgressiveText {
+                    build: Box::new(build_path(jenkins_client, &self.url)?),
+                },
+                [("start", 0)],
+            )
+            .await?;
+        let mut line = vec![];
+        while let Some(chunk) = response.chunk().await? {
+            for byte in chunk {
+                if byte != b'\n' {
+                    line.push(byte);
+                    continue;
+                }
+                if line.starts_with(RESUMED_LOG_LINE.as_bytes()) {
+                    return Ok(true);
+                }
+                line.clear();
+            }
+        }
+        Ok(line.starts_with(RESUMED_LOG_LINE.as_bytes()))
+    }
+
+    /// Stream the output of the steps of the stage named `stage`, checking for new output every
+    /// `interval` until the stage ends. The stream waits for the stage to start, and fails if the
+    /// run ended without it
+    pub fn stream_stage_log<'a>(
+        &'a self,
+        jenkins_client: &'a Jenkins,
+        stage: &'a str,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<StageLogChunk>> + 'a {
+        stream_stage_log(jenkins_client, &self.url, stage, interval)
+    }
+
+    /// Get the graph of the nodes executed by this pipeline
+    pub async fn get_flow_graph(&self, jenkins_client: &Jenkins) -> Result<FlowGraph> {
+        get_flow_graph(jenkins_client, &self.url).await
+    }
+}
+
+/// Graph of the nodes executed by a pipeline, each node pointing to the nodes executed just
+/// before it
+#[derive(Debug, Clone)]
+pub struct FlowGraph {
+    /// Nodes of the pipeline
+    pub nodes: Vec<CommonPipelineNode>,
+}
+
+impl FlowGraph {
+    /// Get the node with the ID `id`
+    pub fn node(&self, id: &str) -> Option<&CommonPipelineNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Nodes executed just before the node `id`
+    pub fn parents_of<'a>(&'a self, id: &str) -> impl Iterator<Item = &'a CommonPipelineNode> {
+        self.node(id)
+            .map(|node| node.parents.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(move |parent| self.node(parent))
+    }
+
+    /// Nodes executed just after the node `id`
+    pub fn children_of<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> impl Iterator<Item = &'a CommonPipelineNode> + 'a {
+        self.nodes
+            .iter()
+            .filter(move |node| node.parents.iter().any(|parent| parent == id))
+    }
+
+    /// Nodes without parents, usually the start of the flow
+    pub fn roots(&self) -> impl Iterator<Item = &CommonPipelineNode> {
+        self.nodes.iter().filter(|node| node.parents.is_empty())
+    }
+}
+
+pub(crate) async fn get_flow_graph(jenkins_client: &Jenkins, url: &str) -> Result<FlowGraph> {
+    #[derive(Deserialize)]
+    struct RunActions {
+        #[serde(default)]
+        actions: Vec<CommonAction>,
+    }
+
+    let tree = TreeBuilder::new()
+        .with_field(
+            TreeBuilder::object("actions").with_subfield(
+                TreeBuilder::object("nodes")
+                    .with_subfield("_class")
+                    .with_subfield("id")
+                    .with_subfield("displayName")
+                    .with_subfield("displayFunctionName")
+                    .with_subfield("parents"),
+            ),
+        )
+        .build();
+    let run: RunActions = jenkins_client
+        .get_with_params(
+            &build_path(jenkins_client, url)?,
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(tree)),
+        )
+        .await?
+        .parse_json()
+        .await?;
+    Ok(FlowGraph {
+        nodes: run
+            .actions
+            .iter()
+            .filter_map(|action| action.as_variant::<crate::action::FlowGraphAction>().ok())
+            .flat_map(|action| action.nodes)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_pipeline_run() {
+        let build_data = |sha1: &str, remote_url: &str| {
+            let revision = serde_json::json!({
+                "SHA1": sha1,
+                "branch": [{"SHA1": sha1, "name": "main"}]
+            });
+            serde_json::json!({
+                "_class": "hudson.plugins.git.util.BuildData",
+                "buildsByBranchName": {
+                    "main": {
+                        "_class": "hudson.plugins.git.util.Build",
+                        "buildNumber": 12,
+                        "buildResult": null,
+                        "marked": revision,
+                        "revision": revision
+                    }
+                },
+                "lastBuiltRevision": revision,
+                "remoteUrls": [remote_url],
+                "scmName": ""
+            })
+        };
+        let run: WorkflowRun = serde_json::from_value(serde_json::json!({
+            "_class": "org.jenkinsci.plugins.workflow.job.WorkflowRun",
+            "actions": [
+                {
+                    "_class": "hudson.model.CauseAction",
+                    "causes": [{
+                        "_class": "hudson.model.Cause$UserIdCause",
+                        "shortDescription": "Started by user Jane Doe",
+                        "userId": "jane",
+                        "userName": "Jane Doe"
+                    }]
+                },
+                {"_class": "jenkins.metrics.impl.TimeInQueueAction", "blockedDurationMillis": 0, "blockedTimeMillis": 0, "buildableDurationMillis": 4, "buildableTimeMillis": 4, "buildingDurationMillis": 65_112, "executingTimeMillis": 65_002, "executorUtilization": 1.0, "queuingDurationMillis": 4, "subTaskCount": 1, "totalDurationMillis": 65_116, "waitingDurationMillis": 0, "waitingTimeMillis": 0},
+                build_data("9c0e1d2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d", "https://git.example.com/team/app.git"),
+                build_data("1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b", "https://git.example.com/team/pipeline-library.git"),
+                {},
+                {"_class": "org.jenkinsci.plugins.workflow.job.views.FlowGraphAction"},
+                {},
+                {},
+                {"_class": "org.jenkinsci.plugins.displayurlapi.actions.RunDisplayAction"}
+            ],
+            "artifacts": [],
+            "building": false,
+            "description": null,
+            "displayName": "#12",
+            "duration": 65_112,
+            "estimatedDuration": 61_840,
+            "executor": null,
+            "fullDisplayName": "team » app #12",
+            "id": "12",
+            "keepLog": false,
+            "number": 12,
+            "queueId": 381,
+            "result": "SUCCESS",
+            "timestamp": 1_700_000_000_000u64,
+            "url": "http://jenkins/job/team/job/app/12/",
+            "changeSets": [{
+                "_class": "hudson.plugins.git.GitChangeSetList",
+                "items": [{
+                    "_class": "hudson.plugins.git.GitChangeSet",
+                    "affectedPaths": ["Jenkinsfile"],
+                    "commitId": "9c0e1d2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d",
+                    "timestamp": 1_699_999_000_000u64,
+                    "author": {"absoluteUrl": "http://jenkins/user/jane", "fullName": "Jane Doe"},
+                    "authorEmail": "jane@example.com",
+                    "comment": "Run the tests in parallel\n",
+                    "date": "2023-11-14 21:56:40 +0000",
+                    "id": "9c0e1d2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d",
+                    "msg": "Run the tests in parallel",
+                    "paths": [{"editType": "edit", "file": "Jenkinsfile"}]
+                }],
+                "kind": "git"
+            }],
+            "culprits": [{"absoluteUrl": "http://jenkins/user/jane", "fullName": "Jane Doe"}],
+            "inProgress": false,
+            "nextBuild": null,
+            "previousBuild": {"number": 11, "url": "http://jenkins/job/team/job/app/11/"}
+        }))
+        .unwrap();
+
+        assert_eq!(run.changes().count(), 1);
+        assert_eq!(run.culprits[0].full_name, "Jane Doe");
+        let git = run.git_build_data();
+        assert_eq!(git.len(), 2);
+        assert_eq!(
+            git[1].remote_urls,
+            vec!["https://git.example.com/team/pipeline-library.git"]
+        );
+        assert_eq!(run.previous_build.unwrap().number, 11);
+    }
+
+    #[tokio::test]
+    async fn can_detect_resumed_run() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let run: WorkflowRun = serde_json::from_value(serde_json::json!({
+            "_class": "org.jenkinsci.plugins.workflow.job.WorkflowRun",
+            "actions": [],
+            "artifacts": [],
+            "building": false,
+            "displayName": "#3",
+            "duration": 1000,
+            "estimatedDuration": 1000,
+            "id": "3",
+            "keepLog": false,
+            "number": 3,
+            "queueId": 7,
+            "result": "SUCCESS",
+            "timestamp": 1000,
+            "url": format!("{}/job/app/3/", server.url()),
+            "changeSets": [
+                {"_class": "hudson.plugins.git.GitChangeSetList", "items": [{"commitId": "a1"}], "kind": "git"},
+                {"_class": "hudson.plugins.git.GitChangeSetList", "items": [{"commitId": "b2"}], "kind": "git"}
+            ],
+            "culprits": [{"absoluteUrl": "http://jenkins/user/jane", "fullName": "Jane Doe"}],
+            "previousBuild": {"number": 2, "url": "http://jenkins/job/app/2/"},
+            "nextBuild": null
+        }))
+        .unwrap();
+
+        let mock = server
+            .mock("GET", "/job/app/3/logText/progressiveText")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "start".to_string(),
+                "0".to_string(),
+            ))
+            .with_body(
+                "Started by user admin\n[Pipeline] sh\nResuming build at Mon Oct 12 10:00:00 UTC 2026 after Jenkins restart\nFinished: SUCCESS\n",
+            )
+            .create();
+
+        assert_eq!(run.changes().count(), 2);
+        assert_eq!(run.culprits[0].full_name, "Jane Doe");
+        assert!(run.was_resumed(&jenkins_client).await.unwrap());
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_get_flow_graph() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let node = |class: &str, id: &str, name: &str, function: &str, parents: &[&str]| {
+            serde_json::json!({
+                "_class": format!("org.jenkinsci.plugins.workflow.{}", class),
+                "id": id,
+                "displayName": name,
+                "displayFunctionName": function,
+                "parents": parents
+            })
+        };
+        let mock = server
+            .mock("GET", "/job/app/3/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "actions[nodes[_class,id,displayName,displayFunctionName,parents]]".to_string(),
+            ))
+            .with_body(
+                serde_json::json!({
+                    "actions": [{}, {
+                        "_class": "org.jenkinsci.plugins.workflow.job.views.FlowGraphAction",
+                        "nodes": [
+                            node("graph.FlowStartNode", "2", "Start of Pipeline", "", &[]),
+                            node("cps.nodes.StepStartNode", "3", "Branch: unit", "parallel", &["2"]),
+                            node("cps.nodes.StepStartNode", "4", "Branch: lint", "parallel", &["2"]),
+                            node("cps.nodes.StepAtomNode", "5", "Shell Script", "sh", &["3"]),
+                            node("cps.nodes.StepEndNode", "6", "Parallel", "parallel", &["5", "4"]),
+                            node("graph.FlowEndNode", "7", "End of Pipeline", "", &["6"])
+                        ]
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let graph = get_flow_graph(&jenkins_client, &format!("{}/job/app/3/", server.url()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            graph
+                .roots()
+                .map(|node| node.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2"]
+        );
+        assert_eq!(
+            graph
+                .children_of("2")
+                .map(|node| node.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["3", "4"]
+        );
+        assert_eq!(
+            graph
+                .parents_of("6")
+                .map(|node| node.display_function_name.as_deref())
+                .collect::<Vec<_>>(),
+            vec![Some("sh"), Some("parallel")]
+        );
+        assert!(graph.nodes[0]
+            .as_variant::<crate::action::pipeline::FlowStartNode>()
+            .is_ok());
+        mock.assert();
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+
+use crate::client::Result;
+use crate::client_internals::ResponseExt;
+use crate::Jenkins;
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Time until a build started at `timestamp` completes, if it is still building and its duration
+/// can be estimated
+pub(crate) fn eta(building: bool, timestamp: u64, estimated_duration: i64) -> Option<Duration> {
+    if !building || estimated_duration < 0 {
+        return None;
+    }
+    let end = timestamp + estimated_duration as u64;
+    Some(Duration::from_millis(end.saturating_sub(now_millis())))
+}
+
+/// Progress in percent of a build started at `timestamp`, capped at 99% while it is building
+pub(crate) fn progress_percent(
+    building: bool,
+    timestamp: u64,
+    estimated_duration: i64,
+) -> Option<u32> {
+    if !building {
+        return Some(100);
+    }
+    if estimated_duration <= 0 {
+        return None;
+    }
+    let elapsed = now_millis().saturating_sub(timestamp);
+    Some((elapsed * 100 / estimated_duration as u64).min(99) as u32)
+}
+
+async fn get_build<B>(jenkins_client: &Jenkins, url: &str) -> Result<B>
+where
+    for<'de> B: Deserialize<'de>,
+{
+    let path = super::build_path(jenkins_client, url)?;
+    jenkins_client.get(&path).await?.parse_json().await
+}
+
+/// Refresh the build at `url` every `interval`, until it is not building anymore or an error
+/// occurs
+pub(crate) fn watch<'a, B>(
+    jenkins_client: &'a Jenkins,
+    url: String,
+    interval: Duration,
+    is_building: fn(&B) -> bool,
+) -> impl Stream<Item = Result<B>> + 'a
+where
+    for<'de> B: Deserialize<'de> + 'a,
+{
+    stream::unfold(Some(true), move |state| {
+        let url = url.clone();
+        async move {
+            let first = state?;
+            if !first {
+                tokio::time::sleep(interval).await;
+            }
+            let build = get_build::<B>(jenkins_client, &url).await;
+            let next = match build {
+                Ok(ref build) if is_building(build) => Some(false),
+                _ => None,
+            };
+            Some((build, next))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::build::CommonBuild;
+
+    fn build(url: &str, building: bool, timestamp: u64, estimated_duration: i64) -> String {
+        serde_json::json!({
+            "url": url,
+            "number": 3,
+            "duration": 0,
+            "estimatedDuration": estimated_duration,
+            "timestamp": timestamp,
+            "keepLog": false,
+            "result": if building { None } else { Some("SUCCESS") },
+            "displayName": "#3",
+            "building": building,
+            "id": "3",
+            "queueId": 9,
+            "actions": [],
+            "artifacts": []
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn can_estimate_progress() {
+        let started = now_millis() - 30_000;
+
+        assert_eq!(progress_percent(true, started, 60_000), Some(50));
+        assert_eq!(progress_percent(true, started, 10_000), Some(99));
+        assert_eq!(progress_percent(true, started, -1), None);
+        assert_eq!(progress_percent(false, started, 60_000), Some(100));
+
+        let eta = eta(true, started, 60_000).unwrap();
+        assert!(eta <= Duration::from_secs(30) && eta > Duration::from_secs(25));
+        assert_eq!(super::eta(true, started, 10_000), Some(Duration::ZERO));
+        assert_eq!(super::eta(false, started, 60_000), None);
+    }
+
+    #[tokio::test]
+    async fn can_watch_build_until_completion() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/slow/3/", server.url());
+        let started = now_millis();
+
+        let building = server
+            .mock("GET", "/job/slow/3/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(build(&url, true, started, 60_000))
+            .expect(2)
+            .create();
+        let watched: CommonBuild =
+            serde_json::from_str(&build(&url, true, started, 60_000)).unwrap();
+
+        let mut updates = Box::pin(watched.watch(&jenkins_client, Duration::from_millis(10)));
+        for _ in 0..2 {
+            let update = updates.next().await.unwrap().unwrap();
+            assert!(update.building);
+            assert!(update.progress_percent().unwrap() < 99);
+        }
+        building.assert();
+        building.remove();
+
+        let _completed = server
+            .mock("GET", "/job/slow/3/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(build(&url, false, started, 60_000))
+            .create();
+        let update = updates.next().await.unwrap().unwrap();
+        assert!(!update.building);
+        assert_eq!(update.eta(), None);
+        assert!(updates.next().await.is_none());
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+
+use super::build_path;
+use super::console::get_progressive_text;
+use crate::client::{self, Result};
+use crate::client_internals::path::Path;
+use crate::client_internals::ResponseExt;
+use crate::Jenkins;
+
+/// Output logged by a step of a pipeline stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageLogChunk {
+    /// ID of the flow node of the step
+    pub node_id: String,
+    /// Name of the step, like `Shell Script`
+    pub step: String,
+    /// Text logged by the step since its previous chunk
+    pub text: String,
+}
+
+/// A run, a stage or a step, as described by the Pipeline Stage View plugin
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WfapiNode {
+    id: String,
+    name: String,
+    status: String,
+    #[serde(default)]
+    stages: Vec<WfapiNode>,
+    #[serde(default)]
+    stage_flow_nodes: Vec<WfapiNode>,
+}
+
+/// Can a node with this status still log something
+fn is_live(status: &str) -> bool {
+    matches!(
+        status,
+        "IN_PROGRESS" | "PAUSED_PENDING_INPUT" | "NOT_EXECUTED"
+    )
+}
+
+#[derive(Default)]
+struct StageLogState {
+    polled: bool,
+    offsets: HashMap<String, u64>,
+    finished: HashSet<String>,
+}
+
+async fn describe(
+    jenkins_client: &Jenkins,
+    build: &Path<'_>,
+    node: Option<&str>,
+) -> Result<WfapiNode> {
+    jenkins_client
+        .get_endpoint(&Path::Wfapi {
+            build: Box::new(build.clone()),
+            node,
+        })
+        .await?
+        .parse_json()
+        .await
+}
+
+/// Read what the steps of `stage` logged since the previous poll, and whether they can log more
+async fn poll_stage_log(
+    jenkins_client: &Jenkins,
+    url: &str,
+    stage: &str,
+    state: &mut StageLogState,
+) -> Result<(Vec<StageLogChunk>, bool)> {
+    let build = build_path(jenkins_client, url)?;
+    let run = describe(jenkins_client, &build, None).await?;
+    let run_live = is_live(&run.status);
+    let stage = match run.stages.into_iter().find(|found| found.name == stage) {
+        Some(stage) => stage,
+        // the stage hasn't started yet
+        None if run_live => return Ok((vec![], true)),
+        None => {
+            return Err(client::Error::StageNotFound {
+                run: url.to_string(),
+                stage: stage.to_string(),
+            }
+            .into())
+        }
+    };
+
+    let stage = describe(jenkins_client, &build, Some(&stage.id)).await?;
+    let mut chunks = vec![];
+    for step in stage.stage_flow_nodes {
+        if state.finished.contains(&step.id) {
+            continue;
+        }
+        let start = state.offsets.get(&step.id).copied().unwrap_or(0);
+        let chunk = get_progressive_text(
+            jenkins_client,
+            Path::PipelineNodeLog {
+                build: Box::new(build.clone()),
+                node: &step.id,
+            },
+            start,
+        )
+        .await?;
+        if !chunk.more_data && !is_live(&step.status) {
+            let _ = state.finished.insert(step.id.clone());
+        }
+        let _ = state.offsets.insert(step.id.clone(), chunk.next_offset);
+        if !chunk.text.is_empty() {
+            chunks.push(StageLogChunk {
+                node_id: step.id,
+                step: step.name,
+                text: chunk.text,
+            });
+        }
+    }
+    Ok((chunks, run_live && is_live(&stage.status)))
+}
+
+/// Poll the log of the steps of `stage` in the run at `url` every `interval`, until the stage
+/// ends or an error occurs
+pub(crate) fn stream_stage_log<'a>(
+    jenkins_client: &'a Jenkins,
+    url: &'a str,
+    stage: &'a str,
+    interval: Duration,
+) -> impl Stream<Item = Result<StageLogChunk>> + 'a {
+    stream::unfold(Some(StageLogState::default()), move |state| async move {
+        let mut state = state?;
+        if state.polled {
+            tokio::time::sleep(interval).await;
+        }
+        state.polled = true;
+        let (items, next): (Vec<Result<StageLogChunk>>, _) =
+            match poll_stage_log(jenkins_client, url, stage, &mut state).await {
+                Ok((chunks, live)) => (
+                    chunks.into_iter().map(Ok).collect(),
+                    if live { Some(state) } else { None },
+                ),
+                Err(error) => (vec![Err(error)], None),
+            };
+        Some((stream::iter(items), next))
+    })
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(status: &str, stages: serde_json::Value) -> String {
+        serde_json::json!({"id": "12", "name": "#12", "status": status, "stages": stages})
+            .to_string()
+    }
+
+    fn stage(status: &str, steps: serde_json::Value) -> String {
+        serde_json::json!({"id": "9", "name": "Deploy", "status": status, "stageFlowNodes": steps})
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn can_stream_stage_log() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/app/12/", server.url());
+
+        let queued_run = server
+            .mock("GET", "/job/app/12/wfapi/describe")
+            .with_body(run("IN_PROGRESS", serde_json::json!([])))
+            .expect(1)
+            .create();
+        let running_run = server
+            .mock("GET", "/job/app/12/wfapi/describe")
+            .with_body(run(
+                "IN_PROGRESS",
+                serde_json::json!([
+                    {"id": "6", "name": "Build", "status": "SUCCESS"},
+                    {"id": "9", "name": "Deploy", "status": "IN_PROGRESS"}
+                ]),
+            ))
+            .expect(1)
+            .create();
+        let finished_run = server
+            .mock("GET", "/job/app/12/wfapi/describe")
+            .with_body(run(
+                "SUCCESS",
+                serde_json::json!([
+                    {"id": "6", "name": "Build", "status": "SUCCESS"},
+                    {"id": "9", "name": "Deploy", "status": "SUCCESS"}
+                ]),
+            ))
+            .expect_at_least(1)
+            .create();
+        let running_stage = server
+            .mock("GET", "/job/app/12/execution/node/9/wfapi/describe")
+            .with_body(stage(
+                "IN_PROGRESS",
+                serde_json::json!([{"id": "10", "name": "Shell Script", "status": "IN_PROGRESS"}]),
+            ))
+            .expect(1)
+            .create();
+        let finished_stage = server
+            .mock("GET", "/job/app/12/execution/node/9/wfapi/describe")
+            .with_body(stage(
+                "SUCCESS",
+                serde_json::json!([{"id": "10", "name": "Shell Script", "status": "SUCCESS"}]),
+            ))
+            .expect_at_least(1)
+            .create();
+        let first_log = server
+            .mock(
+                "GET",
+                "/job/app/12/execution/node/10/log/logText/progressiveText",
+            )
+            .match_query(mockito::Matcher::UrlEncoded(
+                "start".to_string(),
+                "0".to_string(),
+            ))
+            .with_header("X-Text-Size", "10")
+            .with_header("X-More-Data", "true")
+            .with_body("deploying\n")
+            .create();
+        let last_log = server
+            .mock(
+                "GET",
+                "/job/app/12/execution/node/10/log/logText/progressiveText",
+            )
+            .match_query(mockito::Matcher::UrlEncoded(
+                "start".to_string(),
+                "10".to_string(),
+            ))
+            .with_header("X-Text-Size", "19")
+            .with_body("deployed\n")
+            .create();
+
+        let chunks = stream_stage_log(&jenkins_client, &url, "Deploy", Duration::from_millis(10))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["deploying\n", "deployed\n"]
+        );
+        assert_eq!(chunks[0].step, "Shell Script");
+
+        let missing = stream_stage_log(&jenkins_client, &url, "Test", Duration::from_millis(10))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].is_err());
+        queued_run.assert();
+        running_run.assert();
+        finished_run.assert();
+        running_stage.assert();
+        finished_stage.assert();
+        first_log.assert();
+        last_log.assert();
+    }
+}
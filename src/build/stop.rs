@@ -0,0 +1,95 @@
+use super::build_path;
+use crate::build::BuildNumber;
+use crate::client::Result;
+use crate::client_internals::path::Path;
+use crate::job::FolderPath;
+use crate::Jenkins;
+
+/// Abort the build, letting a pipeline run its `post` sections
+pub(crate) const STOP: &str = "stop";
+/// Terminate a pipeline without running its `post` sections
+pub(crate) const TERM: &str = "term";
+/// Kill a pipeline immediately
+pub(crate) const KILL: &str = "kill";
+
+/// Send `signal` to the build at `url`
+pub(crate) async fn stop_build(jenkins_client: &Jenkins, url: &str, signal: &str) -> Result<()> {
+    let _ = jenkins_client
+        .post(&Path::BuildStop {
+            build: Box::new(build_path(jenkins_client, url)?),
+            signal,
+        })
+        .await?;
+    Ok(())
+}
+
+impl Jenkins {
+    /// Abort the build `build_number` of the `Job` at `path`, in nested folders if `path` has
+    /// some
+    pub async fn stop_build<P, B>(&self, path: P, build_number: B) -> Result<()>
+    where
+        P: Into<FolderPath>,
+        B: Into<BuildNumber>,
+    {
+        let path = path.into();
+        let number = build_number.into();
+        let _ = self
+            .post(&Path::BuildStop {
+                build: Box::new(path.to_path_with(|job_name| Path::Build {
+                    job_name,
+                    number,
+                    configuration: None,
+                })?),
+                signal: STOP,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::build::{Build, CommonBuild};
+
+    #[tokio::test]
+    async fn can_stop_builds() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let stop = server
+            .mock("POST", "/job/team/job/app/12/stop")
+            .expect(2)
+            .create();
+        let term = server.mock("POST", "/job/team/job/app/12/term").create();
+        let kill = server.mock("POST", "/job/team/job/app/12/kill").create();
+
+        let build: CommonBuild = serde_json::from_value(serde_json::json!({
+            "url": format!("{}/job/team/job/app/12/", server.url()),
+            "number": 12,
+            "duration": 0,
+            "estimatedDuration": 0,
+            "timestamp": 0,
+            "keepLog": false,
+            "result": null,
+            "displayName": "#12",
+            "fullDisplayName": "team » app #12",
+            "building": true,
+            "id": "12",
+            "queueId": 3,
+            "actions": [],
+            "artifacts": []
+        }))
+        .unwrap();
+        build.stop(&jenkins_client).await.unwrap();
+        build.term(&jenkins_client).await.unwrap();
+        build.kill(&jenkins_client).await.unwrap();
+        jenkins_client.stop_build("team/app", 12).await.unwrap();
+
+        stop.assert();
+        term.assert();
+        kill.assert();
+    }
+}
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::build_path;
+use crate::build::BuildStatus;
+use crate::client::{AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, ResponseExt};
+use crate::Jenkins;
+
+/// Number of tests run by a `Build`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestCounts {
+    /// Number of tests
+    pub total: u32,
+    /// Number of tests failed
+    pub failed: u32,
+    /// Number of tests skipped
+    pub skipped: u32,
+}
+
+impl TestCounts {
+    /// Number of tests passed
+    pub fn passed(&self) -> u32 {
+        self.total.saturating_sub(self.failed + self.skipped)
+    }
+}
+
+/// Compact summary of a `Build`, as used in notifications
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildSummary {
+    /// Number of the build
+    pub number: u32,
+    /// Full display name of the build, like `folder » job #12`
+    pub full_display_name: String,
+    /// URL of the build
+    pub url: String,
+    /// Link to the build provided by the Display URL plugin
+    pub display_url: String,
+    /// Result of the build, `None` while it is running
+    pub result: Option<BuildStatus>,
+    /// Is the build still running
+    pub building: bool,
+    /// Duration of the build, zero while it is running
+    pub duration: Duration,
+    /// Full names of the users who may have broken the build
+    pub culprits: Vec<String>,
+    /// Number of changes in the build
+    pub change_count: usize,
+    /// Number of tests, if test results were recorded
+    pub tests: Option<TestCounts>,
+    /// Number of artifacts archived
+    pub artifact_count: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Culprit {
+    full_name: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ChangeItems {
+    #[serde(default)]
+    items: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TestAction {
+    total_count: Option<u32>,
+    fail_count: Option<u32>,
+    skip_count: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RawSummary {
+    number: u32,
+    full_display_name: Option<String>,
+    url: String,
+    result: Option<BuildStatus>,
+    building: bool,
+    duration: u64,
+    #[serde(default)]
+    culprits: Vec<Culprit>,
+    // freestyle builds have one change set, pipelines one per checkout
+    change_set: Option<ChangeItems>,
+    #[serde(default)]
+    change_sets: Vec<ChangeItems>,
+    #[serde(default)]
+    actions: Vec<Option<TestAction>>,
+    #[serde(default)]
+    artifacts: Vec<serde_json::Value>,
+}
+
+pub(crate) async fn summarize(jenkins_client: &Jenkins, url: &str) -> Result<BuildSummary> {
+    let tree = TreeBuilder::new()
+        .with_field("number")
+        .with_field("fullDisplayName")
+        .with_field("url")
+        .with_field("result")
+        .with_field("building")
+        .with_field("duration")
+        .with_field(TreeBuilder::object("culprits").with_subfield("fullName"))
+        .with_field(
+            TreeBuilder::object("changeSet")
+                .with_subfield(TreeBuilder::object("items").with_subfield("commitId")),
+        )
+        .with_field(
+            TreeBuilder::object("changeSets")
+                .with_subfield(TreeBuilder::object("items").with_subfield("commitId")),
+        )
+        .with_field(
+            TreeBuilder::object("actions")
+                .with_subfield("totalCount")
+                .with_subfield("failCount")
+                .with_subfield("skipCount"),
+        )
+        .with_field(TreeBuilder::object("artifacts").with_subfield("relativePath"))
+        .build();
+    let raw: RawSummary = jenkins_client
+        .get_with_params(
+            &build_path(jenkins_client, url)?,
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(tree)),
+        )
+        .await?
+        .parse_json()
+        .await?;
+
+    let tests = raw.actions.iter().flatten().find_map(|action| {
+        Some(TestCounts {
+            total: action.total_count?,
+            failed: action.fail_count.unwrap_or(0),
+            skipped: action.skip_count.unwrap_or(0),
+        })
+    });
+    let display_url = format!("{}/display/redirect", raw.url.trim_end_matches('/'));
+    let number = raw.number;
+    Ok(BuildSummary {
+        number,
+        full_display_name: raw
+            .full_display_name
+            .unwrap_or_else(|| format!("#{}", number)),
+        display_url,
+        url: raw.url,
+        result: raw.result,
+        building: raw.building,
+        duration: Duration::from_millis(raw.duration),
+        culprits: raw
+            .culprits
+            .into_iter()
+            .map(|culprit| culprit.full_name)
+            .collect(),
+        change_count: raw
+            .change_set
+            .iter()
+            .chain(raw.change_sets.iter())
+            .map(|change_set| change_set.items.len())
+            .sum(),
+        tests,
+        artifact_count: raw.artifacts.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_summarize_build() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/app/12/", server.url());
+
+        let mock = server
+            .mock("GET", "/job/app/12/api/json")
+            .match_query(mockito::Matcher::Regex("tree=.*culprits".to_string()))
+            .with_body(
+                serde_json::json!({
+                    "_class": "org.jenkinsci.plugins.workflow.job.WorkflowRun",
+                    "number": 12,
+                    "fullDisplayName": "app #12",
+                    "url": url,
+                    "result": "UNSTABLE",
+                    "building": false,
+                    "duration": 90000,
+                    "culprits": [{"fullName": "Jane Doe"}],
+                    "changeSets": [
+                        {"items": [{"commitId": "a1"}, {"commitId": "b2"}]},
+                        {"items": [{"commitId": "c3"}]}
+                    ],
+                    "actions": [
+                        {},
+                        null,
+                        {"_class": "hudson.tasks.junit.TestResultAction", "failCount": 2, "skipCount": 1, "totalCount": 40}
+                    ],
+                    "artifacts": [{"relativePath": "app.jar"}]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let summary = summarize(&jenkins_client, &url).await.unwrap();
+
+        assert_eq!(summary.result, Some(BuildStatus::Unstable));
+        assert_eq!(summary.duration, Duration::from_secs(90));
+        assert_eq!(summary.culprits, vec!["Jane Doe"]);
+        assert_eq!(summary.change_count, 3);
+        assert_eq!(summary.tests.unwrap().passed(), 37);
+        assert_eq!(summary.artifact_count, 1);
+        assert_eq!(
+            summary.display_url,
+            format!("{}/job/app/12/display/redirect", server.url())
+        );
+        mock.assert();
+    }
+}
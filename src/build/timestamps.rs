@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use crate::client::Result;
+use crate::client_internals::path::Path;
+use crate::Jenkins;
+
+/// Options of the `/timestamps` endpoint of the Timestamper plugin
+///
+/// By default, the elapsed time since the start of the build is returned in seconds, with three
+/// decimals, without the lines of the console.
+///
+/// ```rust
+/// # use jenkins_api::build::TimestampsQuery;
+/// let query = TimestampsQuery::new()
+///     .with_time("HH:mm:ss")
+///     .with_time_zone("UTC")
+///     .append_log();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimestampsQuery {
+    time: Option<String>,
+    elapsed: Option<String>,
+    precision: Option<String>,
+    time_zone: Option<String>,
+    append_log: bool,
+}
+
+impl TimestampsQuery {
+    /// Elapsed time in seconds, without the lines of the console
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Display the time of each line with a `SimpleDateFormat` pattern, like `HH:mm:ss`
+    pub fn with_time(mut self, format: &str) -> Self {
+        self.time = Some(format.to_string());
+        self
+    }
+
+    /// Display the time elapsed since the start of the build with a pattern, like `HH:mm:ss.S`
+    pub fn with_elapsed(mut self, format: &str) -> Self {
+        self.elapsed = Some(format.to_string());
+        self
+    }
+
+    /// Precision of the elapsed time in seconds, like `3`, `seconds` or `milliseconds`
+    pub fn with_precision(mut self, precision: &str) -> Self {
+        self.precision = Some(precision.to_string());
+        self
+    }
+
+    /// Time zone used to display the time, like `UTC` or `Europe/Paris`
+    pub fn with_time_zone(mut self, time_zone: &str) -> Self {
+        self.time_zone = Some(time_zone.to_string());
+        self
+    }
+
+    /// Add the line of the console after each timestamp
+    pub fn append_log(mut self) -> Self {
+        self.append_log = true;
+        self
+    }
+
+    fn to_query(&self) -> Vec<(&'static str, &str)> {
+        let mut query = vec![];
+        let options = [
+            ("time", &self.time),
+            ("elapsed", &self.elapsed),
+            ("precision", &self.precision),
+            ("timeZone", &self.time_zone),
+        ];
+        for (name, value) in options.iter() {
+            if let Some(value) = value {
+                query.push((*name, value.as_str()));
+            }
+        }
+        if self.append_log {
+            query.push(("appendLog", ""));
+        }
+        query
+    }
+}
+
+/// Timestamp of a line of the console, as formatted by the Timestamper plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Timestamp {
+    /// Time elapsed since the start of the build, in seconds or with an elapsed time pattern
+    /// made of hours, minutes and seconds
+    Elapsed(Duration),
+    /// Timestamp formatted with a pattern that can not be read back as an elapsed time, like the
+    /// time of the line set with `TimestampsQuery::with_time`
+    Formatted(String),
+}
+
+impl Timestamp {
+    fn parse(timestamp: &str, query: &TimestampsQuery) -> Option<Self> {
+        if timestamp.is_empty() {
+            return None;
+        }
+        let elapsed = if query.time.is_some() {
+            None
+        } else {
+            parse_seconds(timestamp).or_else(|| parse_clock(timestamp))
+        };
+        Some(elapsed.map_or_else(
+            || Timestamp::Formatted(timestamp.to_string()),
+            Timestamp::Elapsed,
+        ))
+    }
+
+    /// Time elapsed since the start of the build, if the timestamp is an elapsed time
+    pub fn elapsed(&self) -> Option<Duration> {
+        match self {
+            Timestamp::Elapsed(elapsed) => Some(*elapsed),
+            Timestamp::Formatted(_) => None,
+        }
+    }
+}
+
+fn parse_seconds(timestamp: &str) -> Option<Duration> {
+    timestamp
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// Parse an elapsed time like `01:02:03` or `01:02:03.456`
+fn parse_clock(timestamp: &str) -> Option<Duration> {
+    let mut parts = timestamp.split(':');
+    let (hours, minutes, seconds) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let hours: u64 = hours.parse().ok()?;
+    let minutes: u64 = minutes.parse().ok()?;
+    let seconds = parse_seconds(seconds)?;
+    Some(Duration::from_secs(hours * 3600 + minutes * 60) + seconds)
+}
+
+/// A line of the console of a build with its timestamp, as formatted by the Timestamper plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampedLine {
+    /// Timestamp of the line, if the line has one
+    pub timestamp: Option<Timestamp>,
+    /// Line of the console, if it was requested with `TimestampsQuery::append_log`
+    pub line: Option<String>,
+}
+
+impl TimestampedLine {
+    fn parse(line: &str, query: &TimestampsQuery) -> Self {
+        if query.append_log {
+            let (timestamp, line) = line.split_once("  ").unwrap_or(("", line));
+            TimestampedLine {
+                timestamp: Timestamp::parse(timestamp, query),
+                line: Some(line.to_string()),
+            }
+        } else {
+            TimestampedLine {
+                timestamp: Timestamp::parse(line, query),
+                line: None,
+            }
+        }
+    }
+}
+
+/// Get the timestamps of the console of the build at `url`
+pub(crate) async fn get_timestamps(
+    jenkins_client: &Jenkins,
+    url: &str,
+    query: &TimestampsQuery,
+) -> Result<Vec<TimestampedLine>> {
+    let build = super::build_path(jenkins_client, url)?;
+    let text = jenkins_client
+        .get_endpoint_with_params(
+            &Path::Timestamps {
+                build: Box::new(build),
+            },
+            query.to_query(),
+        )
+        .await?
+        .text()
+        .await?;
+    Ok(text
+        .lines()
+        .map(|line| TimestampedLine::parse(line, query))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_timestamps() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/app/12/", server.url());
+
+        let mock = server
+            .mock("GET", "/job/app/12/timestamps/")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("elapsed".to_string(), "HH:mm:ss".to_string()),
+                mockito::Matcher::UrlEncoded("appendLog".to_string(), "".to_string()),
+            ]))
+            .with_body("00:00:00  Started by me\n00:00:02  Finished:  SUCCESS\n")
+            .create();
+
+        let lines = get_timestamps(
+            &jenkins_client,
+            &url,
+            &TimestampsQuery::new().with_elapsed("HH:mm:ss").append_log(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            lines,
+            vec![
+                TimestampedLine {
+                    timestamp: Some(Timestamp::Elapsed(Duration::from_secs(0))),
+                    line: Some("Started by me".to_string()),
+                },
+                TimestampedLine {
+                    timestamp: Some(Timestamp::Elapsed(Duration::from_secs(2))),
+                    line: Some("Finished:  SUCCESS".to_string()),
+                },
+            ]
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn can_parse_timestamps() {
+        let query = TimestampsQuery::new();
+        let line = TimestampedLine::parse("1.250", &query);
+        assert_eq!(line.line, None);
+        assert_eq!(
+            line.timestamp,
+            Some(Timestamp::Elapsed(Duration::from_millis(1250)))
+        );
+        assert_eq!(TimestampedLine::parse("", &query).timestamp, None);
+
+        let line = TimestampedLine::parse("01:02:03.5", &query.clone().with_elapsed("HH:mm:ss.S"));
+        assert_eq!(
+            line.timestamp.and_then(|timestamp| timestamp.elapsed()),
+            Some(Duration::from_millis(3_723_500))
+        );
+
+        let line = TimestampedLine::parse("12:30:00", &query.with_time("HH:mm:ss"));
+        assert_eq!(
+            line.timestamp,
+            Some(Timestamp::Formatted("12:30:00".to_string()))
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use serde::{Deserialize, Serialize};
+
+use super::build_path;
+use crate::client::Result;
+use crate::client_internals::path::Path;
+use crate::client_internals::ResponseExt;
+use crate::Jenkins;
+
+/// A static analysis tool whose results were recorded by the Warnings Next Generation plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisTool {
+    /// ID of the tool, used in the URL of its results like `checkstyle`
+    pub id: String,
+    /// Name of the tool
+    pub name: String,
+    /// Number of issues found by the tool
+    pub size: u32,
+    /// URL to the results of the tool in the latest build
+    pub latest_url: Option<String>,
+}
+
+/// Summary of the issues found by a static analysis tool in a build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisResult {
+    /// Number of issues
+    pub total_size: u32,
+    /// Number of issues that were not in the reference build
+    pub new_size: u32,
+    /// Number of issues of the reference build that are fixed
+    pub fixed_size: u32,
+    /// Status of the quality gates, like `PASSED`, `WARNING` or `FAILED`
+    pub quality_gate_status: Option<String>,
+    /// Errors that happened while analysing
+    #[serde(default)]
+    pub error_messages: Vec<String>,
+    /// Messages logged while analysing
+    #[serde(default)]
+    pub info_messages: Vec<String>,
+}
+
+impl AnalysisResult {
+    /// Number of issues that were already in the reference build
+    pub fn outstanding_size(&self) -> u32 {
+        self.total_size.saturating_sub(self.new_size)
+    }
+}
+
+/// Issues of an analysis to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSet {
+    /// All the issues of the build
+    All,
+    /// Issues that were not in the reference build
+    New,
+    /// Issues of the reference build that are fixed
+    Fixed,
+    /// Issues that were already in the reference build
+    Outstanding,
+}
+
+impl IssueSet {
+    fn as_str(self) -> &'static str {
+        match self {
+            IssueSet::All => "all",
+            IssueSet::New => "new",
+            IssueSet::Fixed => "fixed",
+            IssueSet::Outstanding => "outstanding",
+        }
+    }
+}
+
+/// Severity of an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Severity {
+    /// An error
+    Error,
+    /// A warning with high priority
+    High,
+    /// A warning with normal priority
+    Normal,
+    /// A warning with low priority
+    Low,
+    /// Unknown severity
+    #[serde(other)]
+    Unknown,
+}
+
+/// An issue found by a static analysis tool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    /// Path of the file with the issue
+    pub file_name: String,
+    /// Name of the file with the issue, without its path
+    pub base_name: Option<String>,
+    /// First line of the issue
+    pub line_start: u32,
+    /// Last line of the issue
+    pub line_end: u32,
+    /// First column of the issue, 0 if unknown
+    #[serde(default)]
+    pub column_start: u32,
+    /// Last column of the issue, 0 if unknown
+    #[serde(default)]
+    pub column_end: u32,
+    /// Message of the issue
+    pub message: String,
+    /// Severity of the issue
+    pub severity: Severity,
+    /// Category of the issue
+    pub category: Option<String>,
+    /// Type of the issue, like the rule that found it
+    #[serde(rename = "type")]
+    pub issue_type: Option<String>,
+    /// Package or namespace of the file
+    pub package_name: Option<String>,
+    /// Module of the file
+    pub module_name: Option<String>,
+    /// Fingerprint used to track the issue across builds
+    pub fingerprint: Option<String>,
+}
+
+/// Get the tools whose results were recorded on the build at `url`
+pub(crate) async fn get_analysis_tools(
+    jenkins_client: &Jenkins,
+    url: &str,
+) -> Result<Vec<AnalysisTool>> {
+    #[derive(Deserialize)]
+    struct Tools {
+        tools: Vec<AnalysisTool>,
+    }
+
+    let tools: Tools = jenkins_client
+        .get(&Path::Analysis {
+            build: Box::new(build_path(jenkins_client, url)?),
+            tool: "warnings-ng",
+            issues: None,
+        })
+        .await?
+        .parse_json()
+        .await?;
+    Ok(tools.tools)
+}
+
+/// Get the summary of the issues found by `tool` on the build at `url`
+pub(crate) async fn get_analysis_result(
+    jenkins_client: &Jenkins,
+    url: &str,
+    tool: &str,
+) -> Result<AnalysisResult> {
+    jenkins_client
+        .get(&Path::Analysis {
+            build: Box::new(build_path(jenkins_client, url)?),
+            tool,
+            issues: None,
+        })
+        .await?
+        .parse_json()
+        .await
+}
+
+/// Get the issues in `issues` found by `tool` on the build at `url`
+pub(crate) async fn get_issues(
+    jenkins_client: &Jenkins,
+    url: &str,
+    tool: &str,
+    issues: IssueSet,
+) -> Result<Vec<Issue>> {
+    #[derive(Deserialize)]
+    struct Report {
+        issues: Vec<Issue>,
+    }
+
+    let report: Report = jenkins_client
+        .get(&Path::Analysis {
+            build: Box::new(build_path(jenkins_client, url)?),
+            tool,
+            issues: Some(issues.as_str()),
+        })
+        .await?
+        .parse_json()
+        .await?;
+    Ok(report.issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_analysis() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let url = format!("{}/job/app/12/", server.url());
+
+        let tools = server
+            .mock("GET", "/job/app/12/warnings-ng/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "io.jenkins.plugins.analysis.core.restapi.AggregationApi",
+                    "tools": [{
+                        "id": "checkstyle",
+                        "latestUrl": format!("{}/job/app/12/checkstyle", server.url()),
+                        "name": "CheckStyle",
+                        "size": 3
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+        let result = server
+            .mock("GET", "/job/app/12/checkstyle/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "io.jenkins.plugins.analysis.core.restapi.AnalysisResultApi",
+                    "errorMessages": [],
+                    "fixedSize": 1,
+                    "infoMessages": ["Successfully parsed file checkstyle-result.xml"],
+                    "newSize": 1,
+                    "qualityGateStatus": "WARNING",
+                    "totalSize": 3
+                })
+                .to_string(),
+            )
+            .create();
+        let new_issues = server
+            .mock("GET", "/job/app/12/checkstyle/new/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "io.jenkins.plugins.analysis.core.restapi.ReportApi",
+                    "issues": [{
+                        "baseName": "Parser.java",
+                        "category": "Imports",
+                        "columnEnd": 0,
+                        "columnStart": 8,
+                        "description": "",
+                        "fileName": "/src/main/java/com/example/Parser.java",
+                        "fingerprint": "FD3A",
+                        "lineEnd": 12,
+                        "lineStart": 12,
+                        "message": "Unused import - java.util.List.",
+                        "moduleName": "",
+                        "origin": "checkstyle",
+                        "packageName": "com.example",
+                        "reference": "12",
+                        "severity": "NORMAL",
+                        "toolName": "CheckStyle",
+                        "type": "UnusedImportsCheck"
+                    }],
+                    "size": 1
+                })
+                .to_string(),
+            )
+            .create();
+
+        let found_tools = get_analysis_tools(&jenkins_client, &url).await.unwrap();
+        assert_eq!(found_tools[0].id, "checkstyle");
+        let summary = get_analysis_result(&jenkins_client, &url, &found_tools[0].id)
+            .await
+            .unwrap();
+        assert_eq!(summary.outstanding_size(), 2);
+        assert_eq!(summary.quality_gate_status.as_deref(), Some("WARNING"));
+        let issues = get_issues(&jenkins_client, &url, "checkstyle", IssueSet::New)
+            .await
+            .unwrap();
+        assert_eq!(issues[0].line_start, 12);
+        assert_eq!(issues[0].severity, Severity::Normal);
+        assert_eq!(issues[0].issue_type.as_deref(), Some("UnusedImportsCheck"));
+
+        tools.assert();
+        result.assert();
+        new_issues.assert();
+    }
+}
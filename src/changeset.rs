@@ -119,7 +119,7 @@ specialize!(CommonChangeSet => ChangeSet);
 impl ChangeSet for CommonChangeSet {}
 
 /// Changes found from git
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GitChangeSet {
     /// Comment
@@ -147,7 +147,7 @@ register_class!("hudson.plugins.git.GitChangeSet" => GitChangeSet);
 impl ChangeSet for GitChangeSet {}
 
 /// Changes found from a repo
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ChangeLogEntry {
     /// ID of the commit
@@ -165,7 +165,7 @@ register_class!("hudson.plugins.repo.ChangeLogEntry" => ChangeLogEntry);
 impl ChangeSet for ChangeLogEntry {}
 
 /// Edit type on a file
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum EditType {
     /// Adding a new file
@@ -177,7 +177,7 @@ pub enum EditType {
 }
 
 /// A file that was changed
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PathChange {
     /// File that was changed
@@ -3,11 +3,16 @@
 use serde::{self, Deserialize};
 
 use crate::client_internals::path::{Name, Path as PrivatePath};
-use crate::client_internals::InternalAdvancedQueryParams;
+use crate::client_internals::{InternalAdvancedQueryParams, ResponseExt};
 
 // pub use client_internals::path::Name;
+#[cfg(feature = "recorder")]
+pub use crate::client_internals::recorder::{Interaction, RecordedResponse};
 pub use crate::client_internals::AdvancedQuery;
 pub use crate::client_internals::{error, Error, Result};
+pub use crate::client_internals::{Cursor, Page};
+pub use crate::client_internals::{HttpTransport, TransportFuture};
+pub use crate::client_internals::{RetryPolicy, RetryablePost};
 pub use crate::client_internals::{TreeBuilder, TreeQueryParam};
 
 use crate::build;
@@ -112,7 +117,8 @@ impl<'a> From<Path<'a>> for PrivatePath<'a> {
 }
 
 impl super::Jenkins {
-    /// Get a `Path` from Jenkins, specifying the depth or tree parameters
+    /// Get a `Path` from Jenkins, specifying the depth or tree parameters. Without parameters,
+    /// the default tree set on the `JenkinsBuilder` is used, if any
     ///
     /// # Example
     ///
@@ -139,7 +145,7 @@ impl super::Jenkins {
     /// }
     ///
     /// # #[tokio::main(flavor = "current_thread")]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     /// #    let jenkins = JenkinsBuilder::new("http://localhost:8080")
     /// #        .with_user("user", Some("password"))
     /// #        .build()?;
@@ -168,14 +174,23 @@ impl super::Jenkins {
         Q: Into<Option<AdvancedQuery>>,
         for<'de> T: Deserialize<'de>,
     {
-        let response = self
-            .get_with_params(
-                &object.into(),
-                parameters.into().map(InternalAdvancedQueryParams::from),
-            )
-            .await?
-            .json()
-            .await?;
-        Ok(response)
+        let path = object.into();
+        let response = match (parameters.into(), self.default_tree.as_deref()) {
+            (None, Some(tree)) => self.get_with_params(&path, [("tree", tree)]).await?,
+            (parameters, _) => {
+                self.get_with_params(&path, parameters.map(InternalAdvancedQueryParams::from))
+                    .await?
+            }
+        };
+        response.parse_json().await
+    }
+
+    /// Post to a `Path` of Jenkins answering with JSON, like some endpoints of plugins, and
+    /// deserialize the response
+    pub async fn post_object_as<T>(&self, object: Path<'_>) -> Result<T>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        self.post_json(&object.into()).await
     }
 }
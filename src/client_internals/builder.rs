@@ -1,8 +1,13 @@
+#[cfg(feature = "recorder")]
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
 
-use reqwest::{self, Client, Url};
+use reqwest::{self, redirect, Client, Url};
 
-use super::{Jenkins, User};
+use super::{HttpTransport, Jenkins, RetryPolicy, TreeQueryParam, User};
 use crate::client::Result;
 
 /// Builder for Jenkins client
@@ -24,7 +29,19 @@ pub struct JenkinsBuilder {
     url: String,
     user: Option<User>,
     csrf_enabled: bool,
+    crumb_fallback: bool,
+    check_not_found: bool,
+    debug_body_len: Option<usize>,
     depth: u8,
+    default_tree: Option<TreeQueryParam>,
+    retry_policy: RetryPolicy,
+    timeout: Option<Duration>,
+    redirect_policy: Option<redirect::Policy>,
+    transport: Option<Box<dyn HttpTransport>>,
+    #[cfg(feature = "recorder")]
+    recorder: Option<PathBuf>,
+    #[cfg(feature = "recorder")]
+    replay: Option<PathBuf>,
 }
 
 impl JenkinsBuilder {
@@ -40,7 +57,19 @@ impl JenkinsBuilder {
             },
             user: None,
             csrf_enabled: true,
+            crumb_fallback: false,
+            check_not_found: false,
+            debug_body_len: None,
             depth: 1,
+            default_tree: None,
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+            redirect_policy: None,
+            transport: None,
+            #[cfg(feature = "recorder")]
+            recorder: None,
+            #[cfg(feature = "recorder")]
+            replay: None,
         }
     }
 
@@ -54,12 +83,42 @@ impl JenkinsBuilder {
             return Err(url::ParseError::EmptyHost.into());
         }
 
+        let mut client_builder = Client::builder();
+        if let Some(policy) = self.redirect_policy {
+            client_builder = client_builder.redirect(policy);
+        }
+        let client = client_builder.build()?;
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Box::new(client.clone()),
+        };
+        #[cfg(feature = "recorder")]
+        let transport: Box<dyn HttpTransport> = match (self.replay, self.recorder) {
+            (Some(cassette), _) => {
+                Box::new(super::recorder::ReplayTransport::from_file(&cassette)?)
+            }
+            (None, Some(cassette)) => Box::new(super::recorder::RecordingTransport::new(
+                transport, cassette,
+            )),
+            (None, None) => transport,
+        };
+
         Ok(Jenkins {
-            url: self.url,
-            client: Client::builder().build()?,
-            user: self.user,
+            url: Arc::from(self.url),
+            client,
+            transport: Arc::from(transport),
+            user: self.user.map(Arc::new),
             csrf_enabled: self.csrf_enabled,
+            crumb_fallback: self.crumb_fallback,
+            crumb_issuer_missing: Arc::new(AtomicBool::new(false)),
+            check_not_found: self.check_not_found,
+            debug_body_len: self.debug_body_len,
+            retry_policy: Arc::new(self.retry_policy),
+            timeout: self.timeout,
             depth: self.depth,
+            default_tree: self
+                .default_tree
+                .map(|tree| Arc::from(tree.to_string().as_str())),
         })
     }
 
@@ -78,12 +137,97 @@ impl JenkinsBuilder {
         self
     }
 
+    /// Send requests without a crumb when the crumb issuer is not found
+    ///
+    /// By default, requests that need a crumb fail when the crumb issuer of Jenkins can't be
+    /// found. With this, the client remembers that the crumb issuer is disabled and sends all
+    /// later requests without a crumb, which works when authenticating with an API token.
+    pub fn with_crumb_fallback(mut self) -> Self {
+        self.crumb_fallback = true;
+        self
+    }
+
+    /// When an object is not found, check who requests are made as to hint if it may be hidden
+    /// by permissions. Not found errors are then returned as
+    /// [`Error::NotFound`](../client/enum.Error.html#variant.NotFound)
+    pub fn with_not_found_check(mut self) -> Self {
+        self.check_not_found = true;
+        self
+    }
+
+    /// Keep the first `max_len` bytes of the body of responses that can't be deserialized in
+    /// [`Error::DeserializationError`](../client/enum.Error.html#variant.DeserializationError),
+    /// to see what Jenkins returned
+    pub fn with_debug_body(mut self, max_len: usize) -> Self {
+        self.debug_body_len = Some(max_len);
+        self
+    }
+
     /// Change the default depth parameters of requests made to Jenkins. It
     /// controls the amount of data in responses
     pub fn with_depth(mut self, depth: u8) -> Self {
         self.depth = depth;
         self
     }
+
+    /// Request only the fields of `tree` instead of using the depth, when no query is given for
+    /// a call. Fields that are missing from `tree` but needed to parse a response take their
+    /// default value, or make the call fail if they have none
+    pub fn with_default_tree(mut self, tree: TreeQueryParam) -> Self {
+        self.default_tree = Some(tree);
+        self
+    }
+
+    /// Time out requests after `timeout`. It can be changed for some calls with
+    /// [`Jenkins::with_timeout`](struct.Jenkins.html#method.with_timeout)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Follow redirections with `policy` instead of following up to 10 redirections
+    ///
+    /// With [`redirect::Policy::none()`](https://docs.rs/reqwest/0.12/reqwest/redirect/struct.Policy.html#method.none),
+    /// a proxy redirecting to a login page makes requests to the JSON API fail with
+    /// [`Error::UnexpectedRedirect`](../client/enum.Error.html#variant.UnexpectedRedirect)
+    /// instead of getting the HTML of the login page. The policy is not used with a custom
+    /// transport.
+    pub fn with_redirect_policy(mut self, policy: redirect::Policy) -> Self {
+        self.redirect_policy = Some(policy);
+        self
+    }
+
+    /// Retry requests that failed because Jenkins could not be reached or was unavailable,
+    /// following `policy`
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Use a custom `HttpTransport` to send requests instead of the default `reqwest::Client`
+    pub fn with_transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    /// Record all requests made to Jenkins and their responses in the file `cassette`, one JSON
+    /// object per line, to be replayed later with [`with_replay`](#method.with_replay)
+    #[cfg(feature = "recorder")]
+    pub fn with_recorder<P: Into<PathBuf>>(mut self, cassette: P) -> Self {
+        self.recorder = Some(cassette.into());
+        self
+    }
+
+    /// Answer all requests from the file `cassette` previously recorded with
+    /// [`with_recorder`](#method.with_recorder), without contacting Jenkins
+    ///
+    /// Each recorded interaction is replayed once, in the order they were recorded for
+    /// identical requests.
+    #[cfg(feature = "recorder")]
+    pub fn with_replay<P: Into<PathBuf>>(mut self, cassette: P) -> Self {
+        self.replay = Some(cassette.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -94,7 +238,7 @@ mod tests {
     fn create_builder() {
         let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL);
 
-        assert_eq!(jenkins_client.url, JENKINS_URL);
+        assert_eq!(&*jenkins_client.url, JENKINS_URL);
         assert_eq!(jenkins_client.user, None);
         assert!(jenkins_client.csrf_enabled);
     }
@@ -103,7 +247,7 @@ mod tests {
     fn create_builder_with_trailing_slash() {
         let jenkins_client = crate::JenkinsBuilder::new(&format!("{}/", JENKINS_URL));
 
-        assert_eq!(jenkins_client.url, JENKINS_URL);
+        assert_eq!(&*jenkins_client.url, JENKINS_URL);
         assert_eq!(jenkins_client.user, None);
         assert!(jenkins_client.csrf_enabled);
     }
@@ -112,7 +256,7 @@ mod tests {
     fn disable_csrf() {
         let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).disable_csrf();
 
-        assert_eq!(jenkins_client.url, JENKINS_URL);
+        assert_eq!(&*jenkins_client.url, JENKINS_URL);
         assert_eq!(jenkins_client.user, None);
         assert!(!jenkins_client.csrf_enabled);
     }
@@ -1,7 +1,11 @@
+use std::sync::atomic::Ordering;
+
+use log::{debug, warn};
 use reqwest::{header::HeaderName, header::HeaderValue, RequestBuilder};
 use serde::Deserialize;
 
-use super::{path::Path, Jenkins};
+use super::errors::is_not_found;
+use super::{path::Path, Jenkins, ResponseExt};
 use crate::client::Result;
 
 #[derive(Debug, Deserialize, Clone)]
@@ -16,19 +20,113 @@ impl Jenkins {
         &self,
         request_builder: RequestBuilder,
     ) -> Result<RequestBuilder> {
-        if self.csrf_enabled {
-            let crumb = self.get_csrf().await?;
-            Ok(request_builder.header(
+        if !self.csrf_enabled || self.crumb_issuer_missing.load(Ordering::Relaxed) {
+            return Ok(request_builder);
+        }
+        match self.get_csrf().await {
+            Ok(crumb) => Ok(request_builder.header(
                 HeaderName::from_lowercase(crumb.crumb_request_field.to_lowercase().as_bytes())?,
                 HeaderValue::from_str(&crumb.crumb)?,
-            ))
-        } else {
-            Ok(request_builder)
+            )),
+            Err(error) if self.crumb_fallback && is_not_found(error.as_ref()) => {
+                warn!("crumb issuer not found, sending requests without crumb");
+                self.crumb_issuer_missing.store(true, Ordering::Relaxed);
+                Ok(request_builder)
+            }
+            Err(error) => Err(error),
         }
     }
 
     pub(crate) async fn get_csrf(&self) -> Result<Crumb> {
-        let crumb: Crumb = self.get(&Path::CrumbIssuer).await?.json().await?;
+        debug!("fetching a crumb");
+        let crumb: Crumb = self
+            .get_with_depth(&Path::CrumbIssuer)
+            .await?
+            .parse_json()
+            .await?;
         Ok(crumb)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_post_without_crumb_issuer() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .with_crumb_fallback()
+            .build()
+            .unwrap();
+
+        let crumb_issuer = server
+            .mock("GET", "/crumbIssuer/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .expect(1)
+            .create();
+        let post = server
+            .mock("POST", "/mypath")
+            .match_header("jenkins-crumb", mockito::Matcher::Missing)
+            .expect(2)
+            .create();
+
+        for _ in 0..2 {
+            let _ = jenkins_client
+                .post(&super::Path::Raw { path: "/mypath" })
+                .await
+                .unwrap();
+        }
+
+        crumb_issuer.assert();
+        post.assert();
+    }
+
+    #[tokio::test]
+    async fn can_require_crumb_issuer() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let _crumb_issuer = server
+            .mock("GET", "/crumbIssuer/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .create();
+        let post = server.mock("POST", "/mypath").expect(0).create();
+
+        assert!(jenkins_client
+            .post(&super::Path::Raw { path: "/mypath" })
+            .await
+            .is_err());
+        post.assert();
+    }
+
+    #[tokio::test]
+    async fn can_post_with_crumb_and_default_tree() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .with_default_tree(crate::client::TreeBuilder::new().with_field("name").build())
+            .build()
+            .unwrap();
+
+        let crumb_issuer = server
+            .mock("GET", "/crumbIssuer/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "depth".to_string(),
+                "1".to_string(),
+            ))
+            .with_body(r#"{"crumb": "abc", "crumbRequestField": "Jenkins-Crumb"}"#)
+            .create();
+        let post = server
+            .mock("POST", "/mypath")
+            .match_header("jenkins-crumb", "abc")
+            .create();
+
+        let _ = jenkins_client
+            .post(&super::Path::Raw { path: "/mypath" })
+            .await
+            .unwrap();
+
+        crumb_issuer.assert();
+        post.assert();
+    }
+}
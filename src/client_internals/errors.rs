@@ -52,6 +52,150 @@ pub enum Error {
         /// Action
         action: Action,
     },
+
+    #[error(
+        "failed to deserialize response of {path} at {}: {source}{}",
+        .json_pointer.as_deref().unwrap_or("unknown location"),
+        .body.as_ref().map(|body| format!(", body: {}", body)).unwrap_or_default()
+    )]
+    ///  Error when the response of Jenkins doesn't match the expected type
+    DeserializationError {
+        /// Path of the request
+        path: String,
+        /// JSON pointer to the value that failed to deserialize, available with the
+        /// `path-to-error` feature
+        json_pointer: Option<String>,
+        /// Deserialization error
+        source: serde_json::Error,
+        /// Body of the response, truncated, when enabled with
+        /// [`JenkinsBuilder::with_debug_body`](../struct.JenkinsBuilder.html#method.with_debug_body)
+        body: Option<String>,
+    },
+
+    #[error("{path} not found ({hint})")]
+    ///  Error when an object is not found, with a hint on why when checking not found errors
+    ///  is enabled on the `JenkinsBuilder`
+    NotFound {
+        /// Path of the object
+        path: String,
+        /// Hint on why the object was not found
+        hint: NotFoundHint,
+    },
+
+    #[error("{path} returned an HTML page with status {status} instead of JSON: {snippet}")]
+    ///  Error when a request to the JSON API gets an HTML page, like a login or an error page
+    UnexpectedHtmlResponse {
+        /// Path of the request
+        path: String,
+        /// Status of the response
+        status: u16,
+        /// Beginning of the text of the page
+        snippet: String,
+    },
+
+    #[error(
+        "{path} redirected with status {status} to {}",
+        .location.as_deref().unwrap_or("an unknown location")
+    )]
+    ///  Error when a request to the JSON API is redirected and redirections are not followed, or
+    ///  when a change is redirected to another page, like a login page
+    UnexpectedRedirect {
+        /// Path of the request
+        path: String,
+        /// Status of the response
+        status: u16,
+        /// Location the request was redirected to
+        location: Option<String>,
+    },
+
+    #[error("invalid build range '{range}', expected numbers or ranges like '1-3,5'")]
+    ///  Error when parsing a range of builds with an invalid syntax
+    InvalidBuildRange {
+        /// Range that was parsed
+        range: String,
+    },
+
+    #[error("can't change the jobs of view {view}: {snippet}")]
+    ///  Error when adding or removing a job of a view that can't list jobs, like the `all` view.
+    ///  Jenkins answers with an error page instead of failing the request
+    ViewNotModifiable {
+        /// Name of the view
+        view: String,
+        /// Beginning of the text of the error page
+        snippet: String,
+    },
+
+    #[error("failed to delete build {build}, after deleting builds {deleted:?}: {source}")]
+    ///  Error when deleting a range of builds stopped part way, like on a build kept forever
+    BuildDeletionFailed {
+        /// Number of the build that couldn't be deleted
+        build: u32,
+        /// Numbers of the builds deleted before the failure
+        deleted: Vec<u32>,
+        /// Error deleting the build
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("stage {stage} not found in {run}")]
+    ///  Error when streaming the log of a stage that a finished pipeline run didn't have
+    StageNotFound {
+        /// URL of the run
+        run: String,
+        /// Name of the stage
+        stage: String,
+    },
+
+    #[error("failed to move {job} to {destination}: {reason}")]
+    ///  Error when a job was not found as expected after moving it
+    JobMigrationFailed {
+        /// Full name of the job moved
+        job: String,
+        /// Folder the job was moved to
+        destination: String,
+        /// What was not as expected
+        reason: String,
+    },
+
+    #[error(
+        "unexpected _class '{}', expected '{expected}'",
+        .found.as_deref().unwrap_or("none")
+    )]
+    ///  Error when decoding an object as a type with a different `_class`
+    UnexpectedClass {
+        /// `_class` of the type
+        expected: String,
+        /// `_class` of the object
+        found: Option<String>,
+    },
+
+    #[error("timed out after {timeout:?} while polling")]
+    ///  Error when a poller didn't get a result before its timeout
+    PollTimeout {
+        /// Timeout of the poller
+        timeout: std::time::Duration,
+    },
+
+    #[error("polling was cancelled")]
+    ///  Error when a poller was cancelled before getting a result
+    PollCancelled,
+
+    #[error("download of {path} stopped receiving data at byte {downloaded}")]
+    ///  Error when the server keeps answering a download with no data before its end
+    DownloadStalled {
+        /// Path of the downloaded file
+        path: String,
+        /// Number of bytes downloaded
+        downloaded: u64,
+    },
+}
+
+/// Is `error` a not found error, from the status of the response or from the not found check
+pub(crate) fn is_not_found(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+        error.status() == Some(reqwest::StatusCode::NOT_FOUND)
+    } else {
+        matches!(error.downcast_ref::<Error>(), Some(Error::NotFound { .. }))
+    }
 }
 
 /// Possible type of URL expected in links between items
@@ -84,6 +228,28 @@ impl fmt::Display for ExpectedType {
     }
 }
 
+/// Hint on why Jenkins answered that an object was not found
+///
+/// Jenkins answers "not found" for objects a user is not allowed to see, so this can't be
+/// certain.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NotFoundHint {
+    /// The request was anonymous or its credentials were refused, the object may exist but be
+    /// hidden to this user
+    PossiblyForbidden,
+    /// The request was authenticated, the object is probably missing
+    ProbablyMissing,
+}
+
+impl fmt::Display for NotFoundHint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NotFoundHint::PossiblyForbidden => write!(f, "possibly forbidden"),
+            NotFoundHint::ProbablyMissing => write!(f, "probably missing"),
+        }
+    }
+}
+
 /// Possible action done on an object
 #[derive(Debug, Copy, Clone)]
 pub enum Action {
@@ -1,44 +1,74 @@
 //! Jenkins Client
 
 use std::fmt::Debug;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
 
 use log::{debug, warn};
 use regex::Regex;
 use reqwest::{
-    header::HeaderValue, header::CONTENT_TYPE, Body, Client, RequestBuilder, Response, StatusCode,
+    header::HeaderValue, header::CONTENT_TYPE, header::LOCATION, header::RANGE, multipart::Form,
+    Body, Client, RequestBuilder, Response, StatusCode,
 };
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 mod errors;
+pub(crate) use self::errors::is_not_found;
+use self::errors::NotFoundHint;
 pub use self::errors::{Error, Result};
 mod builder;
 pub mod path;
 pub use self::builder::JenkinsBuilder;
 pub use self::path::{Name, Path};
 mod csrf;
+mod page;
+pub use self::page::{Cursor, Page};
 mod tree;
 pub use self::tree::{TreeBuilder, TreeQueryParam};
+mod response;
+pub(crate) use self::response::ResponseExt;
+mod transport;
+pub use self::transport::{HttpTransport, TransportFuture};
+mod retry;
+pub use self::retry::{RetryPolicy, RetryablePost};
+#[cfg(feature = "recorder")]
+pub(crate) mod recorder;
 
 /// Helper type for error management
 pub mod error {
     pub use super::errors::Action;
     pub use super::errors::ExpectedType;
+    pub use super::errors::NotFoundHint;
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 struct User {
     username: String,
     password: Option<String>,
 }
 
 /// Client struct with the methods to query Jenkins
-#[derive(Debug)]
+///
+/// `Jenkins` is `Send + Sync`, and cloning it is cheap: clones share the configuration and the
+/// connections, so it can be moved into tasks or shared as the state of a web service without
+/// wrapping it in an `Arc`.
+#[derive(Debug, Clone)]
 pub struct Jenkins {
-    url: String,
+    url: Arc<str>,
     client: Client,
-    user: Option<User>,
+    transport: Arc<dyn HttpTransport>,
+    user: Option<Arc<User>>,
     csrf_enabled: bool,
+    crumb_fallback: bool,
+    crumb_issuer_missing: Arc<AtomicBool>,
+    check_not_found: bool,
+    debug_body_len: Option<usize>,
+    retry_policy: Arc<RetryPolicy>,
+    timeout: Option<Duration>,
     pub(crate) depth: u8,
+    pub(crate) default_tree: Option<Arc<str>>,
 }
 
 /// Advanced query parameters supported by Jenkins to control the amount of data retrieved
@@ -74,6 +104,43 @@ impl From<AdvancedQuery> for InternalAdvancedQueryParams {
 }
 
 impl Jenkins {
+    /// Get a client sharing the configuration and connections of this one, whose requests time
+    /// out after `timeout`
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # async fn example(jenkins: &jenkins_api::Jenkins) -> jenkins_api::client::Result<()> {
+    /// let job = jenkins
+    ///     .with_timeout(Duration::from_secs(2))
+    ///     .get_job("health check")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Jenkins {
+        Jenkins {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Get a client sharing the configuration and connections of this one, whose requests use
+    /// the depth `depth` instead of the one set on the `JenkinsBuilder`, or of its default tree
+    ///
+    /// ```rust
+    /// # async fn example(jenkins: &jenkins_api::Jenkins) -> jenkins_api::client::Result<()> {
+    /// let job = jenkins.clone_with_depth(0).get_job("deploy").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clone_with_depth(&self, depth: u8) -> Jenkins {
+        Jenkins {
+            depth,
+            default_tree: None,
+            ..self.clone()
+        }
+    }
+
     pub(crate) fn url_api_json(&self, endpoint: &str) -> String {
         format!("{}{}/api/json", self.url, endpoint)
     }
@@ -87,10 +154,38 @@ impl Jenkins {
             request_builder =
                 request_builder.basic_auth(user.username.clone(), user.password.clone());
         }
-        let query = request_builder.build()?;
+        if let Some(timeout) = self.timeout {
+            request_builder = request_builder.timeout(timeout);
+        }
+        let mut query = request_builder.build()?;
         debug!("sending {} {}", query.method(), query.url());
 
-        let response = self.client.execute(query).await?;
+        let mut attempt = 0;
+        let mut response = loop {
+            let retry = self.retry_policy.retry_of(&query, attempt);
+            let result = self.transport.execute(query).await;
+            match retry {
+                Some(retry) if RetryPolicy::should_retry(&result) => {
+                    attempt += 1;
+                    let wait = self.retry_policy.backoff(attempt);
+                    warn!(
+                        "retrying {} {} in {:?} (attempt {})",
+                        retry.method(),
+                        retry.url(),
+                        wait,
+                        attempt
+                    );
+                    tokio::time::sleep(wait).await;
+                    query = retry;
+                }
+                _ => break result?,
+            }
+        };
+        if let Some(max_len) = self.debug_body_len {
+            let _ = response
+                .extensions_mut()
+                .insert(response::DebugBodyLen(max_len));
+        }
         Ok(response)
     }
 
@@ -103,6 +198,15 @@ impl Jenkins {
     }
 
     pub(crate) async fn get(&self, path: &Path<'_>) -> Result<Response> {
+        match self.default_tree {
+            Some(ref tree) => self.get_with_params(path, [("tree", &**tree)]).await,
+            None => self.get_with_depth(path).await,
+        }
+    }
+
+    /// Get `path` with the depth, ignoring the default tree, for requests made by the client
+    /// itself whose response must keep all its fields
+    pub(crate) async fn get_with_depth(&self, path: &Path<'_>) -> Result<Response> {
         self.get_with_params(path, [("depth", &self.depth.to_string())])
             .await
     }
@@ -117,9 +221,97 @@ impl Jenkins {
             .get(self.url_api_json(&path.to_string()))
             .query(&qps);
         let resp = self.send(query).await?;
+        if self.check_not_found && resp.status() == StatusCode::NOT_FOUND {
+            return Err(self.not_found_error(path).await);
+        }
+        if resp.status().is_redirection() {
+            debug!("{} was redirected: {}", path, resp.status());
+            return Err(Error::UnexpectedRedirect {
+                path: path.to_string(),
+                status: resp.status().as_u16(),
+                location: resp
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .map(String::from),
+            }
+            .into());
+        }
+        Self::error_for_status(resp)
+    }
+
+    /// Check who the request was made as to guess why an object was not found
+    async fn not_found_error(&self, path: &Path<'_>) -> Box<dyn std::error::Error + Send + Sync> {
+        #[derive(Deserialize)]
+        struct WhoAmI {
+            anonymous: bool,
+        }
+
+        let probe = self
+            .client
+            .get(self.url_api_json(&Path::WhoAmI.to_string()));
+        let anonymous = match self.send(probe).await {
+            Ok(response) if response.status().is_success() => response
+                .json::<WhoAmI>()
+                .await
+                .map(|who_am_i| who_am_i.anonymous)
+                .unwrap_or(true),
+            _ => true,
+        };
+        Error::NotFound {
+            path: path.to_string(),
+            hint: if anonymous {
+                NotFoundHint::PossiblyForbidden
+            } else {
+                NotFoundHint::ProbablyMissing
+            },
+        }
+        .into()
+    }
+
+    /// Get an endpoint that is not part of the JSON API, so without appending `/api/json`
+    pub(crate) async fn get_endpoint(&self, path: &Path<'_>) -> Result<Response> {
+        let resp = self
+            .send(self.client.get(self.url(&path.to_string())))
+            .await?;
+        Self::error_for_status(resp)
+    }
+
+    /// Get an endpoint that is not part of the JSON API with the query parameters `qps`
+    pub(crate) async fn get_endpoint_with_params<T: Serialize>(
+        &self,
+        path: &Path<'_>,
+        qps: T,
+    ) -> Result<Response> {
+        let query = self.client.get(self.url(&path.to_string())).query(&qps);
+        let resp = self.send(query).await?;
         Self::error_for_status(resp)
     }
 
+    /// Get a range of bytes of a file, without checking the status of the response
+    pub(crate) async fn get_range(
+        &self,
+        path: &Path<'_>,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<Response> {
+        let range = match end {
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        };
+        let request_builder = self
+            .client
+            .get(self.url(&path.to_string()))
+            .header(RANGE, range);
+        self.send(request_builder).await
+    }
+
+    /// Send a `HEAD` request, to read the headers describing a file
+    pub(crate) async fn head(&self, path: &Path<'_>) -> Result<Response> {
+        let request_builder = self.client.head(self.url(&path.to_string()));
+        Self::error_for_status(self.send(request_builder).await?)
+    }
+
     pub(crate) async fn post(&self, path: &Path<'_>) -> Result<Response> {
         let mut request_builder = self.client.post(self.url(&path.to_string()));
 
@@ -129,24 +321,116 @@ impl Jenkins {
         Self::error_for_status(resp)
     }
 
+    /// Post to an endpoint answering with JSON, and deserialize the response
+    pub(crate) async fn post_json<T: DeserializeOwned>(&self, path: &Path<'_>) -> Result<T> {
+        self.post(path).await?.parse_json().await
+    }
+
     pub(crate) async fn post_with_body<T: Into<Body> + Debug>(
         &self,
         path: &Path<'_>,
         body: T,
         qps: &[(&str, &str)],
     ) -> Result<Response> {
-        let mut request_builder = self.client.post(self.url(&path.to_string()));
+        self.post_body(path, body, qps, true, "application/x-www-form-urlencoded")
+            .await
+    }
+
+    /// Post a body without a CSRF crumb, for requests authenticated by a token
+    pub(crate) async fn post_with_body_without_crumb<T: Into<Body> + Debug>(
+        &self,
+        path: &Path<'_>,
+        body: T,
+        qps: &[(&str, &str)],
+    ) -> Result<Response> {
+        self.post_body(path, body, qps, false, "application/x-www-form-urlencoded")
+            .await
+    }
 
+    /// Post a `multipart/form-data` body
+    pub(crate) async fn post_with_multipart_form(
+        &self,
+        path: &Path<'_>,
+        form: Form,
+        qps: &[(&str, &str)],
+    ) -> Result<Response> {
+        let mut request_builder = self.client.post(self.url(&path.to_string()));
         request_builder = self.add_csrf_to_request(request_builder).await?;
+        debug!("{:?}", form);
+        let response = self
+            .send(request_builder.query(qps).multipart(form))
+            .await?;
+        Self::error_for_post(response).await
+    }
 
-        request_builder = request_builder.header(
-            CONTENT_TYPE,
-            HeaderValue::from_static("application/x-www-form-urlencoded"),
-        );
+    /// Post an XML body, like a `config.xml`
+    pub(crate) async fn post_with_xml_body(
+        &self,
+        path: &Path<'_>,
+        body: String,
+        qps: &[(&str, &str)],
+    ) -> Result<Response> {
+        self.post_body(path, body, qps, true, "text/xml").await
+    }
+
+    /// Post a JSON body
+    pub(crate) async fn post_with_json_body<T: Serialize>(
+        &self,
+        path: &Path<'_>,
+        body: &T,
+    ) -> Result<Response> {
+        self.post_body(
+            path,
+            serde_json::to_string(body)?,
+            &[],
+            true,
+            "application/json",
+        )
+        .await
+    }
+
+    /// Call a method exposed to JavaScript by Stapler, with its arguments as a JSON array
+    pub(crate) async fn post_stapler_method<T: Serialize>(
+        &self,
+        path: &Path<'_>,
+        arguments: &T,
+    ) -> Result<Response> {
+        self.post_body(
+            path,
+            serde_json::to_string(arguments)?,
+            &[],
+            true,
+            "application/x-stapler-method-invocation;charset=UTF-8",
+        )
+        .await
+    }
+
+    async fn post_body<T: Into<Body> + Debug>(
+        &self,
+        path: &Path<'_>,
+        body: T,
+        qps: &[(&str, &str)],
+        with_crumb: bool,
+        content_type: &str,
+    ) -> Result<Response> {
+        let mut request_builder = self.client.post(self.url(&path.to_string()));
+
+        if with_crumb {
+            request_builder = self.add_csrf_to_request(request_builder).await?;
+        }
+
+        request_builder =
+            request_builder.header(CONTENT_TYPE, HeaderValue::from_str(content_type)?);
         debug!("{:?}", body);
         request_builder = request_builder.query(qps).body(body);
         let response = self.send(request_builder).await?;
 
+        Self::error_for_post(response).await
+    }
+
+    /// Check the status of the response to a post, reading Java exceptions from the body of
+    /// internal server errors
+    async fn error_for_post(response: Response) -> Result<Response> {
         if response.status() == StatusCode::INTERNAL_SERVER_ERROR {
             // get the error before reading the body. In this case it can't be OK
             let error = match response.error_for_status_ref() {
@@ -313,12 +597,164 @@ mod tests {
         assert_eq!(
             format!("{:?}", response),
             format!(
-                r#"Err(reqwest::Error {{ kind: Status(500), url: "{}/error-NewException" }})"#,
+                r#"Err(reqwest::Error {{ kind: Status(500, None), url: "{}/error-NewException" }})"#,
                 server.url()
             ),
         );
     }
 
+    async fn not_found_hint(who_am_i: &str) -> String {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .with_not_found_check()
+            .build()
+            .unwrap();
+
+        let _job = server
+            .mock("GET", "/job/hidden/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .create();
+        let _who_am_i = server
+            .mock("GET", "/whoAmI/api/json")
+            .with_body(who_am_i)
+            .create();
+
+        let response = jenkins_client.get_job("hidden").await;
+
+        format!("{:?}", response)
+    }
+
+    #[tokio::test]
+    async fn can_hint_not_found_is_possibly_forbidden() {
+        assert_eq!(
+            not_found_hint(r#"{"anonymous":true,"authenticated":true,"name":"anonymous"}"#).await,
+            r#"Err(NotFound { path: "/job/hidden", hint: PossiblyForbidden })"#
+        );
+    }
+
+    #[tokio::test]
+    async fn can_hint_not_found_is_probably_missing() {
+        assert_eq!(
+            not_found_hint(r#"{"anonymous":false,"authenticated":true,"name":"user"}"#).await,
+            r#"Err(NotFound { path: "/job/hidden", hint: ProbablyMissing })"#
+        );
+    }
+
+    #[tokio::test]
+    async fn can_override_timeout_for_a_call() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let _slow = server
+            .mock("GET", "/job/slow/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_chunked_body(|writer| {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                writer.write_all(br#"{"name": "slow"}"#)
+            })
+            .create();
+
+        let response: crate::client::Result<serde_json::Value> = jenkins_client
+            .with_timeout(std::time::Duration::from_millis(50))
+            .get_object_as(crate::client::Path::Raw { path: "/job/slow" }, None)
+            .await;
+        assert!(response.is_err());
+
+        let response: serde_json::Value = jenkins_client
+            .get_object_as(crate::client::Path::Raw { path: "/job/slow" }, None)
+            .await
+            .unwrap();
+        assert_eq!(response["name"], "slow");
+    }
+
+    #[tokio::test]
+    async fn can_share_clones_across_tasks() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        assert_shareable(&jenkins_client);
+
+        let shallow = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "depth".to_string(),
+                "0".to_string(),
+            ))
+            .with_body(r#"{"name": "app"}"#)
+            .create();
+
+        let clone = jenkins_client.clone_with_depth(0);
+        let response = tokio::spawn(async move {
+            let response = clone
+                .get(&super::path::Path::Raw { path: "/job/app" })
+                .await
+                .unwrap();
+            response.text().await.unwrap()
+        })
+        .await
+        .unwrap();
+        assert_eq!(response, r#"{"name": "app"}"#);
+        assert_eq!(jenkins_client.depth, 1);
+        shallow.assert();
+    }
+
+    #[tokio::test]
+    async fn can_default_to_tree() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .with_default_tree(
+                crate::client::TreeBuilder::new()
+                    .with_field("name")
+                    .with_field("url")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let tree = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "name,url".to_string(),
+            ))
+            .with_body(
+                serde_json::json!({
+                    "_class": "hudson.model.FreeStyleProject",
+                    "name": "app",
+                    "displayName": "app",
+                    "url": format!("{}/job/app/", server.url()),
+                    "buildable": true,
+                    "builds": [],
+                    "color": "blue",
+                    "inQueue": false,
+                    "keepDependencies": false,
+                    "nextBuildNumber": 1,
+                    "property": [],
+                    "actions": [],
+                    "healthReport": []
+                })
+                .to_string(),
+            )
+            .expect(2)
+            .create();
+
+        let _: serde_json::Value = jenkins_client
+            .get_object_as(
+                crate::client::Path::Job {
+                    name: "app",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let job = jenkins_client.get_job("app").await.unwrap();
+        assert_eq!(job.name, "app");
+        tree.assert();
+    }
+
     #[tokio::test]
     async fn can_post_with_query_params() {
         let mut server = mockito::Server::new_async().await;
@@ -337,4 +773,68 @@ mod tests {
         assert_eq!(response.unwrap().text().await.unwrap(), "ok");
         mock.assert()
     }
+
+    #[tokio::test]
+    async fn can_post_and_get_object() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Scheduled {
+            id: u32,
+        }
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server
+            .mock("POST", "/plugin/scheduler/schedule")
+            .with_body(r#"{"id":42}"#)
+            .create();
+
+        let scheduled: Scheduled = jenkins_client
+            .post_object_as(crate::client::Path::Raw {
+                path: "/plugin/scheduler/schedule",
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(scheduled.id, 42);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_stop_at_redirections() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .with_redirect_policy(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        let job = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(302)
+            .with_header("location", "https://sso.example.com/login")
+            .create();
+
+        let error = jenkins_client
+            .get(&super::Path::Job {
+                name: super::Name::Name("app"),
+                configuration: None,
+            })
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<crate::client::Error>() {
+            Some(crate::client::Error::UnexpectedRedirect {
+                status, location, ..
+            }) => {
+                assert_eq!(*status, 302);
+                assert_eq!(location.as_deref(), Some("https://sso.example.com/login"));
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+        job.assert();
+    }
 }
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// Position of a page in a list, as the range of elements to get with a tree query
+///
+/// ```rust
+/// # use jenkins_api::client::Cursor;
+/// let cursor = Cursor::first(50);
+/// assert_eq!(cursor.next(), Cursor::at(50, 50));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cursor {
+    offset: u32,
+    limit: u32,
+}
+
+impl Cursor {
+    /// Cursor to the first `limit` elements
+    pub fn first(limit: u32) -> Self {
+        Self::at(0, limit)
+    }
+
+    /// Cursor to `limit` elements, skipping the first `offset` ones
+    pub fn at(offset: u32, limit: u32) -> Self {
+        Cursor { offset, limit }
+    }
+
+    /// Number of elements skipped
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Maximum number of elements in the page
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// Index of the element after the page
+    pub fn end(&self) -> u32 {
+        self.offset.saturating_add(self.limit)
+    }
+
+    /// Cursor to the page following this one, with the same limit
+    pub fn next(&self) -> Self {
+        Self::at(self.end(), self.limit)
+    }
+}
+
+/// A page of elements of a list, with the `Cursor` used to get it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Elements of the page
+    pub items: Vec<T>,
+    /// Position of the page in the list
+    pub cursor: Cursor,
+}
+
+impl<T> Page<T> {
+    pub(crate) fn new(items: Vec<T>, cursor: Cursor) -> Self {
+        Page { items, cursor }
+    }
+
+    /// Is this the last page of the list. As Jenkins doesn't tell the length of lists, a full
+    /// page is assumed to have a page after it, which may be empty
+    pub fn is_last(&self) -> bool {
+        self.cursor.limit == 0 || self.items.len() < self.cursor.limit as usize
+    }
+
+    /// Cursor to the next page, if this isn't the last page
+    pub fn next_page(&self) -> Option<Cursor> {
+        if self.is_last() {
+            None
+        } else {
+            Some(self.cursor.next())
+        }
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
@@ -54,6 +54,21 @@ pub enum Path<'a> {
     JobDisable {
         name: Name<'a>,
     },
+    JobShelve {
+        name: Name<'a>,
+    },
+    JobDelete {
+        name: Name<'a>,
+    },
+    CreateItem,
+    JobLogRotate {
+        job: Box<Path<'a>>,
+    },
+    MoveJob {
+        job: Box<Path<'a>>,
+    },
+    ShelvedProjects,
+    ManageShelvedProject,
     Build {
         job_name: Name<'a>,
         number: build::BuildNumber,
@@ -70,6 +85,7 @@ pub enum Path<'a> {
         folder_name: Option<Name<'a>>,
     },
     Queue,
+    QueueCancelItem,
     QueueItem {
         id: i32,
     },
@@ -86,10 +102,84 @@ pub enum Path<'a> {
     Computer {
         name: Name<'a>,
     },
+    ComputerChangeOfflineCause {
+        name: Name<'a>,
+    },
+    ComputerToggleOffline {
+        name: Name<'a>,
+    },
+    ComputerDisconnect {
+        name: Name<'a>,
+    },
+    ComputerDelete {
+        name: Name<'a>,
+    },
+    ComputerCreateItem,
+    ComputerConfigXML {
+        name: Name<'a>,
+    },
     Raw {
         path: &'a str,
     },
     CrumbIssuer,
+    SearchSuggest,
+    WhoAmI,
+    ItemCategories,
+    CheckJobName,
+    UpdateCenter,
+    UpdateCenterInstallStatus,
+    PluginManagerInstallPlugins,
+    RoleStrategy {
+        method: &'a str,
+    },
+    FailureCauseManagement {
+        method: Option<&'a str>,
+    },
+    FailureCause {
+        id: Name<'a>,
+    },
+    Artifact {
+        build: Box<Path<'a>>,
+        relative_path: &'a str,
+    },
+    ProgressiveText {
+        build: Box<Path<'a>>,
+    },
+    PipelineNodeLog {
+        build: Box<Path<'a>>,
+        node: &'a str,
+    },
+    Wfapi {
+        build: Box<Path<'a>>,
+        node: Option<&'a str>,
+    },
+    BuildDelete {
+        build: Box<Path<'a>>,
+    },
+    BuildStop {
+        build: Box<Path<'a>>,
+        signal: &'a str,
+    },
+    Timestamps {
+        build: Box<Path<'a>>,
+    },
+    Coverage {
+        build: Box<Path<'a>>,
+    },
+    Analysis {
+        build: Box<Path<'a>>,
+        tool: &'a str,
+        issues: Option<&'a str>,
+    },
+    TestResultsAnalyzer {
+        job: Box<Path<'a>>,
+        method: &'a str,
+    },
+    #[cfg(feature = "feeds")]
+    Feed {
+        job_name: Option<Name<'a>>,
+        feed: &'a str,
+    },
 }
 impl<'a> Display for Path<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -119,6 +209,13 @@ impl<'a> Display for Path<'a> {
             Path::PollSCMJob { ref name } => write!(f, "/job/{}/polling", name),
             Path::JobEnable { ref name } => write!(f, "/job/{}/enable", name),
             Path::JobDisable { ref name } => write!(f, "/job/{}/disable", name),
+            Path::JobShelve { ref name } => write!(f, "/job/{}/shelve/shelveProject", name),
+            Path::JobDelete { ref name } => write!(f, "/job/{}/doDelete", name),
+            Path::CreateItem => write!(f, "/createItem"),
+            Path::JobLogRotate { ref job } => write!(f, "{}/logRotate", job),
+            Path::MoveJob { ref job } => write!(f, "{}/move/move", job),
+            Path::ShelvedProjects => write!(f, "/shelvedProjects"),
+            Path::ManageShelvedProject => write!(f, "/shelvedProjects/manageShelvedProject"),
             Path::Build {
                 ref job_name,
                 ref number,
@@ -174,6 +271,7 @@ impl<'a> Display for Path<'a> {
                 folder_name: Some(ref folder_name),
             } => write!(f, "/job/{}/job/{}/config.xml", folder_name, job_name,),
             Path::Queue => write!(f, "/queue"),
+            Path::QueueCancelItem => write!(f, "/queue/cancelItem"),
             Path::QueueItem { ref id } => write!(f, "/queue/item/{}", id),
             Path::MavenArtifactRecord {
                 ref job_name,
@@ -193,17 +291,95 @@ impl<'a> Display for Path<'a> {
                 ref folder_name,
                 ref path,
             } => write!(f, "/job/{}{}", folder_name, path),
-            Path::Computers => write!(f, "/computer/api/json"),
-            Path::Computer { ref name } => write!(f, "/computer/{}/api/json", name),
+            Path::Computers => write!(f, "/computer"),
+            Path::Computer { ref name } => write!(f, "/computer/{}", name),
+            Path::ComputerChangeOfflineCause { ref name } => {
+                write!(f, "/computer/{}/changeOfflineCause", name)
+            }
+            Path::ComputerToggleOffline { ref name } => {
+                write!(f, "/computer/{}/toggleOffline", name)
+            }
+            Path::ComputerDisconnect { ref name } => write!(f, "/computer/{}/doDisconnect", name),
+            Path::ComputerDelete { ref name } => write!(f, "/computer/{}/doDelete", name),
+            Path::ComputerCreateItem => write!(f, "/computer/doCreateItem"),
+            Path::ComputerConfigXML { ref name } => write!(f, "/computer/{}/config.xml", name),
             Path::Raw { path } => write!(f, "{}", path),
             Path::CrumbIssuer => write!(f, "/crumbIssuer"),
+            Path::SearchSuggest => write!(f, "/search/suggest"),
+            Path::WhoAmI => write!(f, "/whoAmI"),
+            Path::ItemCategories => write!(f, "/view/all/itemCategories"),
+            Path::CheckJobName => write!(f, "/checkJobName"),
+            Path::UpdateCenter => write!(f, "/updateCenter"),
+            Path::UpdateCenterInstallStatus => write!(f, "/updateCenter/installStatus"),
+            Path::PluginManagerInstallPlugins => write!(f, "/pluginManager/installPlugins"),
+            Path::RoleStrategy { method } => write!(f, "/role-strategy/strategy/{}", method),
+            Path::FailureCauseManagement { method: None } => write!(f, "/failure-cause-management"),
+            Path::FailureCauseManagement {
+                method: Some(method),
+            } => write!(f, "/failure-cause-management/{}", method),
+            Path::FailureCause { ref id } => write!(f, "/failure-cause-management/{}", id),
+            Path::Artifact {
+                ref build,
+                relative_path,
+            } => write!(
+                f,
+                "{}/artifact/{}",
+                build,
+                relative_path
+                    .split('/')
+                    .map(urlencoding::encode)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            ),
+            Path::ProgressiveText { ref build } => write!(f, "{}/logText/progressiveText", build),
+            Path::PipelineNodeLog { ref build, node } => {
+                write!(f, "{}/execution/node/{}/log", build, node)
+            }
+            Path::Wfapi {
+                ref build,
+                node: None,
+            } => write!(f, "{}/wfapi/describe", build),
+            Path::Wfapi {
+                ref build,
+                node: Some(node),
+            } => write!(f, "{}/execution/node/{}/wfapi/describe", build, node),
+            Path::BuildDelete { ref build } => write!(f, "{}/doDelete", build),
+            Path::BuildStop {
+                ref build,
+                ref signal,
+            } => write!(f, "{}/{}", build, signal),
+            Path::Timestamps { ref build } => write!(f, "{}/timestamps/", build),
+            Path::Coverage { ref build } => write!(f, "{}/coverage", build),
+            Path::Analysis {
+                ref build,
+                tool,
+                issues: None,
+            } => write!(f, "{}/{}", build, tool),
+            Path::Analysis {
+                ref build,
+                tool,
+                issues: Some(issues),
+            } => write!(f, "{}/{}/{}", build, tool, issues),
+            Path::TestResultsAnalyzer { ref job, method } => {
+                write!(f, "{}/test_results_analyzer/{}", job, method)
+            }
+            #[cfg(feature = "feeds")]
+            Path::Feed {
+                job_name: None,
+                feed,
+            } => write!(f, "/{}", feed),
+            #[cfg(feature = "feeds")]
+            Path::Feed {
+                job_name: Some(ref job_name),
+                feed,
+            } => write!(f, "/job/{}/{}", job_name, feed),
         }
     }
 }
 
 impl Jenkins {
     pub(crate) fn url_to_path<'a>(&self, url: &'a str) -> Path<'a> {
-        let path = if url.starts_with(&self.url) {
+        let path = if url.starts_with(&*self.url) {
             &url[self.url.len()..]
         } else {
             url
@@ -278,6 +454,16 @@ impl Jenkins {
                     }
                 }
             }
+            ("/job", 7)
+                if &path[slashes[2]..slashes[3]] == "/job"
+                    && &path[slashes[4]..slashes[5]] != "/job" =>
+            {
+                // build of a configuration of a job in a folder, like a matrix run
+                Path::InFolder {
+                    folder_name: Name::UrlEncodedName(&path[5..slashes[2]]),
+                    path: Box::new(self.url_to_path(&path[slashes[2]..])),
+                }
+            }
             ("/queue", 4) => Path::QueueItem {
                 id: path[(slashes[2] + 1)..(path.len() - 1)].parse().unwrap(),
             },
@@ -410,4 +596,50 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn can_parse_build_with_config_in_folder_path() {
+        let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
+
+        let path = jenkins_client.url_to_path("/job/myfolder/job/myjob/config/1/");
+        assert_eq!(
+            path,
+            Path::InFolder {
+                folder_name: Name::UrlEncodedName("myfolder"),
+                path: Box::new(Path::Build {
+                    job_name: Name::UrlEncodedName("myjob"),
+                    number: build::BuildNumber::Number(1),
+                    configuration: Some(Name::UrlEncodedName("config"))
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn can_parse_job_in_nested_folders_path() {
+        let jenkins_client = crate::JenkinsBuilder::new(JENKINS_URL).build().unwrap();
+
+        let path = jenkins_client.url_to_path("/job/myfolder/job/mysubfolder/job/myjob/");
+        assert_eq!(
+            path,
+            Path::Job {
+                name: Name::UrlEncodedName("myfolder/job/mysubfolder/job/myjob"),
+                configuration: None
+            }
+        );
+    }
+
+    #[test]
+    fn can_display_console_of_build_with_config_in_folder() {
+        let path = Path::ConsoleText {
+            job_name: Name::Name("my job"),
+            number: build::BuildNumber::Number(1),
+            configuration: Some(Name::UrlEncodedName("label=linux")),
+            folder_name: Some(Name::Name("my folder")),
+        };
+        assert_eq!(
+            path.to_string(),
+            "/job/my%20folder/job/my%20job/label=linux/1/consoleText"
+        );
+    }
 }
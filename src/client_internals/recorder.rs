@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use log::debug;
+use reqwest::{Request, Response, ResponseBuilderExt};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::io::AsyncWriteExt;
+
+use super::{HttpTransport, TransportFuture};
+use crate::client::Result;
+
+/// An HTTP request sent to Jenkins and the response it got, as stored in a cassette
+///
+/// Request headers are not recorded, so cassettes don't contain credentials.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Interaction {
+    /// HTTP method of the request
+    pub method: String,
+    /// Path and query of the request, relative to the Jenkins host
+    pub path: String,
+    /// Body of the request, if any
+    pub body: Option<String>,
+    /// Response received
+    pub response: RecordedResponse,
+}
+
+/// A response stored in a cassette
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordedResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    pub headers: BTreeMap<String, String>,
+    /// Response body, stored encoded in base64
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    pub body: Vec<u8>,
+}
+
+fn to_base64<S: Serializer>(bytes: &[u8], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&STANDARD.encode(bytes))
+}
+
+fn from_base64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<u8>, D::Error> {
+    STANDARD
+        .decode(String::deserialize(deserializer)?)
+        .map_err(serde::de::Error::custom)
+}
+
+fn request_path(request: &Request) -> String {
+    match request.url().query() {
+        Some(query) => format!("{}?{}", request.url().path(), query),
+        None => request.url().path().to_string(),
+    }
+}
+
+fn request_body(request: &Request) -> Option<String> {
+    request
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .filter(|body| !body.is_empty())
+        .map(|body| String::from_utf8_lossy(body).into_owned())
+}
+
+fn to_response(request: &Request, recorded: &RecordedResponse) -> Result<Response> {
+    let mut builder = http::Response::builder()
+        .status(recorded.status)
+        .url(request.url().clone());
+    for (name, value) in &recorded.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    Ok(Response::from(builder.body(recorded.body.clone())?))
+}
+
+/// Transport recording every interaction into a cassette file, one JSON object per line
+#[derive(Debug)]
+pub(crate) struct RecordingTransport {
+    inner: Box<dyn HttpTransport>,
+    cassette: PathBuf,
+    /// Cassette file, created on the first request
+    file: tokio::sync::Mutex<Option<tokio::fs::File>>,
+}
+
+impl RecordingTransport {
+    pub(crate) fn new(inner: Box<dyn HttpTransport>, cassette: PathBuf) -> Self {
+        RecordingTransport {
+            inner,
+            cassette,
+            file: tokio::sync::Mutex::new(None),
+        }
+    }
+}
+
+impl HttpTransport for RecordingTransport {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let method = request.method().to_string();
+            let path = request_path(&request);
+            let body = request_body(&request);
+            let replayed_request = request.try_clone();
+
+            let response = self.inner.execute(request).await?;
+            let recorded = RecordedResponse {
+                status: response.status().as_u16(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        value
+                            .to_str()
+                            .ok()
+                            .map(|value| (name.to_string(), value.to_string()))
+                    })
+                    .collect(),
+                body: response.bytes().await?.to_vec(),
+            };
+            let response = match replayed_request {
+                Some(ref request) => to_response(request, &recorded)?,
+                None => Response::from(
+                    http::Response::builder()
+                        .status(recorded.status)
+                        .body(recorded.body.clone())?,
+                ),
+            };
+
+            debug!("recording {} {} to {:?}", method, path, self.cassette);
+            let mut line = serde_json::to_vec(&Interaction {
+                method,
+                path,
+                body,
+                response: recorded,
+            })?;
+            line.push(b'\n');
+            let mut file = self.file.lock().await;
+            if file.is_none() {
+                *file = Some(tokio::fs::File::create(&self.cassette).await?);
+            }
+            if let Some(file) = file.as_mut() {
+                file.write_all(&line).await?;
+                file.flush().await?;
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Transport answering requests from a cassette file
+#[derive(Debug)]
+pub(crate) struct ReplayTransport {
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl ReplayTransport {
+    pub(crate) fn from_file(cassette: &Path) -> Result<Self> {
+        let interactions = fs::read_to_string(cassette)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<serde_json::Result<_>>()?;
+        Ok(ReplayTransport {
+            interactions: Mutex::new(interactions),
+        })
+    }
+}
+
+impl HttpTransport for ReplayTransport {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let method = request.method().to_string();
+            let path = request_path(&request);
+            let body = request_body(&request);
+
+            let mut interactions = self
+                .interactions
+                .lock()
+                .map_err(|_| "replay lock poisoned")?;
+            let position = interactions.iter().position(|interaction| {
+                interaction.method == method && interaction.path == path && interaction.body == body
+            });
+            match position {
+                Some(position) => {
+                    debug!("replaying {} {}", method, path);
+                    let interaction = interactions.remove(position);
+                    to_response(&request, &interaction.response)
+                }
+                None => Err(format!("no recorded interaction for {} {}", method, path).into()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::Matcher;
+
+    use crate::client::Path;
+
+    #[tokio::test]
+    async fn can_record_and_replay() {
+        let cassette =
+            std::env::temp_dir().join(format!("jenkins-api-cassette-{}.json", std::process::id()));
+
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/job/recorded/api/json")
+            .match_query(Matcher::Any)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"name": "recorded"}"#)
+            .create();
+        let recording = crate::JenkinsBuilder::new(&server.url())
+            .with_recorder(&cassette)
+            .build()
+            .unwrap();
+        let recorded: serde_json::Value = recording
+            .get_object_as(
+                Path::Job {
+                    name: "recorded",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        drop(server);
+
+        let replaying = crate::JenkinsBuilder::new("http://none:8080")
+            .with_replay(&cassette)
+            .build()
+            .unwrap();
+        let replayed: serde_json::Value = replaying
+            .get_object_as(
+                Path::Job {
+                    name: "recorded",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(recorded, replayed);
+
+        let unrecorded: crate::client::Result<serde_json::Value> = replaying
+            .get_object_as(
+                Path::Job {
+                    name: "recorded",
+                    configuration: None,
+                },
+                None,
+            )
+            .await;
+        assert_eq!(
+            unrecorded.unwrap_err().to_string(),
+            "no recorded interaction for GET /job/recorded/api/json"
+        );
+
+        std::fs::remove_file(cassette).unwrap();
+    }
+
+    #[test]
+    fn can_store_binary_bodies() {
+        let recorded = super::RecordedResponse {
+            status: 200,
+            headers: Default::default(),
+            body: vec![0xff, 0x00, b'a'],
+        };
+
+        let json = serde_json::to_value(&recorded).unwrap();
+        assert_eq!(json["body"], "/wBh");
+        let parsed: super::RecordedResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.body, recorded.body);
+    }
+}
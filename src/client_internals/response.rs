@@ -0,0 +1,265 @@
+use std::future::Future;
+
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+
+use super::{Error, Result};
+
+/// Maximum length of the body kept in deserialization errors, set on responses when enabled with
+/// `JenkinsBuilder::with_debug_body`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DebugBodyLen(pub(crate) usize);
+
+/// Length of the snippet of HTML pages kept in `Error::UnexpectedHtmlResponse`
+const HTML_SNIPPET_LEN: usize = 200;
+
+/// Helpers to read the responses of Jenkins
+pub(crate) trait ResponseExt {
+    /// Deserialize the JSON body of the response, reporting the request path and the location
+    /// of the offending value on errors
+    fn parse_json<T: DeserializeOwned>(self) -> impl Future<Output = Result<T>> + Send;
+
+    /// Get the beginning of the text of the response if it is an HTML page, for endpoints
+    /// answering with an empty body on success and with an error page otherwise
+    fn html_page(self) -> impl Future<Output = Result<Option<String>>> + Send;
+}
+
+impl ResponseExt for Response {
+    async fn parse_json<T: DeserializeOwned>(self) -> Result<T> {
+        let path = self.url().path().to_string();
+        let debug_body_len = self.extensions().get::<DebugBodyLen>().copied();
+        let status = self.status().as_u16();
+        let is_html = is_html(&self);
+        let body = self.bytes().await?;
+        if is_html {
+            return Err(Error::UnexpectedHtmlResponse {
+                path,
+                status,
+                snippet: html_snippet(&body),
+            }
+            .into());
+        }
+        Ok(deserialize(path, &body).map_err(|mut error| {
+            if let (
+                Error::DeserializationError {
+                    body: ref mut debug_body,
+                    ..
+                },
+                Some(DebugBodyLen(max_len)),
+            ) = (&mut error, debug_body_len)
+            {
+                *debug_body = Some(truncate(&body, max_len));
+            }
+            error
+        })?)
+    }
+
+    async fn html_page(self) -> Result<Option<String>> {
+        if !is_html(&self) {
+            return Ok(None);
+        }
+        let body = self.bytes().await?;
+        let snippet = html_snippet(&body);
+        Ok(Some(snippet).filter(|snippet| !snippet.is_empty()))
+    }
+}
+
+/// Is the response an HTML page
+fn is_html(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(|content_type| content_type.trim_start().starts_with("text/html"))
+        .unwrap_or(false)
+}
+
+/// Beginning of the text of an HTML page, on one line
+fn html_snippet(body: &[u8]) -> String {
+    let page = String::from_utf8_lossy(body);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    let snippet = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match snippet.char_indices().nth(HTML_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &snippet[..end]),
+        None => snippet,
+    }
+}
+
+fn truncate(body: &[u8], max_len: usize) -> String {
+    if body.len() > max_len {
+        format!("{}...", String::from_utf8_lossy(&body[..max_len]))
+    } else {
+        String::from_utf8_lossy(body).into_owned()
+    }
+}
+
+#[cfg(not(feature = "path-to-error"))]
+fn deserialize<T: DeserializeOwned>(path: String, body: &[u8]) -> std::result::Result<T, Error> {
+    serde_json::from_slice(body).map_err(|source| Error::DeserializationError {
+        path,
+        json_pointer: None,
+        source,
+        body: None,
+    })
+}
+
+#[cfg(feature = "path-to-error")]
+fn deserialize<T: DeserializeOwned>(path: String, body: &[u8]) -> std::result::Result<T, Error> {
+    use serde_path_to_error::Segment;
+
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|error| {
+        let json_pointer = error
+            .path()
+            .iter()
+            .map(|segment| match segment {
+                Segment::Seq { index } => format!("/{}", index),
+                Segment::Map { key } => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+                Segment::Enum { variant } => format!("/{}", variant),
+                Segment::Unknown => "/?".to_string(),
+            })
+            .collect();
+        Error::DeserializationError {
+            path,
+            json_pointer: Some(json_pointer),
+            source: error.into_inner(),
+            body: None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Path;
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Job {
+        name: String,
+        builds: Vec<Build>,
+    }
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Build {
+        number: u32,
+    }
+
+    #[tokio::test]
+    async fn deserialization_errors_have_context() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let _mock = server
+            .mock("GET", "/job/drifted/api/json")
+            .with_body(r#"{"name":"drifted","builds":[{"number":1},{"number":"two"}]}"#)
+            .create();
+
+        let error = jenkins_client
+            .get_object_as::<_, Job>(
+                Path::Job {
+                    name: "drifted",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<crate::client::Error>() {
+            Some(crate::client::Error::DeserializationError {
+                path, json_pointer, ..
+            }) => {
+                assert_eq!(path, "/job/drifted/api/json");
+                if cfg!(feature = "path-to-error") {
+                    assert_eq!(json_pointer.as_deref(), Some("/builds/1/number"));
+                } else {
+                    assert_eq!(json_pointer, &None);
+                }
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[tokio::test]
+    async fn can_keep_body_of_deserialization_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .with_debug_body(16)
+            .build()
+            .unwrap();
+
+        let _mock = server
+            .mock("GET", "/job/proxied/api/json")
+            .with_body("<html><body>Please sign in</body></html>")
+            .create();
+
+        let error = jenkins_client
+            .get_object_as::<_, Job>(
+                Path::Job {
+                    name: "proxied",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<crate::client::Error>() {
+            Some(crate::client::Error::DeserializationError { body, .. }) => {
+                assert_eq!(body.as_deref(), Some("<html><body>Plea..."));
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[tokio::test]
+    async fn html_pages_are_reported() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let _mock = server
+            .mock("GET", "/job/proxied/api/json")
+            .with_header("content-type", "text/html;charset=utf-8")
+            .with_body(
+                "<html><head><title>Sign in</title></head>\n<body><p>Please sign in</p></body></html>",
+            )
+            .create();
+
+        let error = jenkins_client
+            .get_object_as::<_, Job>(
+                Path::Job {
+                    name: "proxied",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<crate::client::Error>() {
+            Some(crate::client::Error::UnexpectedHtmlResponse {
+                path,
+                status,
+                snippet,
+            }) => {
+                assert_eq!(path, "/job/proxied/api/json");
+                assert_eq!(*status, 200);
+                assert_eq!(snippet, "Sign in Please sign in");
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+    }
+}
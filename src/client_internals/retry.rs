@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use reqwest::{Method, Request, Response, StatusCode};
+
+use crate::client::Result;
+
+/// A `POST` endpoint that can safely be sent again when it failed
+///
+/// Requests that change the state of Jenkins are not retried by default, as sending them twice
+/// can have side effects, like triggering a build twice. Endpoints listed here have the same
+/// effect when sent several times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryablePost {
+    /// Enabling a job
+    EnableJob,
+    /// Disabling a job
+    DisableJob,
+    /// Preparing Jenkins for shutdown
+    QuietDown,
+    /// Cancelling the preparation for shutdown
+    CancelQuietDown,
+    /// Any endpoint whose path ends with this suffix, like `/toggleOffline`
+    Endpoint(String),
+}
+
+impl RetryablePost {
+    fn matches(&self, path: &str) -> bool {
+        let path = path.trim_end_matches('/');
+        match self {
+            RetryablePost::EnableJob => path.contains("/job/") && path.ends_with("/enable"),
+            RetryablePost::DisableJob => path.contains("/job/") && path.ends_with("/disable"),
+            RetryablePost::QuietDown => path.ends_with("/quietDown"),
+            RetryablePost::CancelQuietDown => path.ends_with("/cancelQuietDown"),
+            RetryablePost::Endpoint(suffix) => path.ends_with(suffix.trim_end_matches('/')),
+        }
+    }
+}
+
+/// How requests that failed because Jenkins could not be reached or was unavailable are retried
+///
+/// `GET` and `HEAD` requests are safe to retry, `POST` requests are only retried when their
+/// endpoint is allowed with [`allow_post_retry`](#method.allow_post_retry). The default policy
+/// doesn't retry.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use jenkins_api::client::{RetryPolicy, RetryablePost};
+/// let jenkins = jenkins_api::JenkinsBuilder::new("http://localhost:8080")
+///     .with_retry_policy(
+///         RetryPolicy::new(3)
+///             .with_backoff(Duration::from_millis(200), Duration::from_secs(5))
+///             .allow_post_retry(RetryablePost::EnableJob)
+///             .allow_post_retry(RetryablePost::DisableJob),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retryable_posts: Vec<RetryablePost>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(0)
+    }
+}
+
+impl RetryPolicy {
+    /// Retry safe requests up to `max_retries` times, waiting 500ms before the first retry and
+    /// doubling the wait each time, up to 30s
+    pub fn new(max_retries: u32) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            retryable_posts: vec![],
+        }
+    }
+
+    /// Wait `initial` before the first retry, doubling the wait for each retry up to `max`
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Also retry `POST` requests to `endpoint`, that must have the same effect when sent twice
+    pub fn allow_post_retry(mut self, endpoint: RetryablePost) -> Self {
+        self.retryable_posts.push(endpoint);
+        self
+    }
+
+    /// Can a request with this `method` to this `path` be retried
+    pub fn is_retryable(&self, method: &Method, path: &str) -> bool {
+        match *method {
+            Method::GET | Method::HEAD => true,
+            Method::POST => self
+                .retryable_posts
+                .iter()
+                .any(|endpoint| endpoint.matches(path)),
+            _ => false,
+        }
+    }
+
+    /// Copy of `request` to send if it fails, when it can be retried `attempt` more times
+    pub(crate) fn retry_of(&self, request: &Request, attempt: u32) -> Option<Request> {
+        if attempt < self.max_retries && self.is_retryable(request.method(), request.url().path()) {
+            request.try_clone()
+        } else {
+            None
+        }
+    }
+
+    /// Should a request be retried after getting `result`
+    pub(crate) fn should_retry(result: &Result<Response>) -> bool {
+        match result {
+            Ok(response) => matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            Err(_) => true,
+        }
+    }
+
+    /// How long to wait before the retry number `attempt`, starting at 1
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new(2).with_backoff(Duration::from_millis(1), Duration::from_millis(3))
+    }
+
+    #[test]
+    fn only_retries_safe_requests() {
+        let policy = policy().allow_post_retry(RetryablePost::DisableJob);
+
+        assert!(policy.is_retryable(&Method::GET, "/job/app/api/json"));
+        assert!(policy.is_retryable(&Method::POST, "/job/folder/job/app/disable"));
+        assert!(!policy.is_retryable(&Method::POST, "/job/app/enable"));
+        assert!(!policy.is_retryable(&Method::POST, "/job/app/build"));
+        assert_eq!(policy.backoff(1), Duration::from_millis(1));
+        assert_eq!(policy.backoff(2), Duration::from_millis(2));
+        assert_eq!(policy.backoff(40), Duration::from_millis(3));
+    }
+
+    #[tokio::test]
+    async fn can_retry_unavailable_jenkins() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .with_retry_policy(policy())
+            .build()
+            .unwrap();
+
+        let unavailable = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .expect(1)
+            .create();
+        let available = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"name": "app"}"#)
+            .expect(1)
+            .create();
+        let build = server
+            .mock("POST", "/job/app/build")
+            .with_status(503)
+            .expect(1)
+            .create();
+
+        let job: serde_json::Value = jenkins_client
+            .get_object_as(
+                crate::client::Path::Job {
+                    name: "app",
+                    configuration: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(job["name"], "app");
+        assert!(jenkins_client
+            .post(&crate::client_internals::Path::BuildJob {
+                name: crate::client_internals::Name::Name("app"),
+            })
+            .await
+            .is_err());
+
+        unavailable.assert();
+        available.assert();
+        build.assert();
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+use reqwest::{Client, Request, Response};
+
+use crate::client::Result;
+
+/// Future returned by an `HttpTransport` when executing a request
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response>> + Send + 'a>>;
+
+/// HTTP backend used by `Jenkins` to send its requests
+///
+/// Requests are built by the `Jenkins` client (url, query parameters, authentication, crumb)
+/// then handed to the transport. The default transport is a `reqwest::Client`; a custom one can
+/// be set with [`JenkinsBuilder::with_transport`](../struct.JenkinsBuilder.html#method.with_transport)
+/// to use another HTTP stack, or to answer requests without a Jenkins server. A `Response` can be
+/// created from any `http::Response` with `Response::from`.
+///
+/// ```rust
+/// # extern crate jenkins_api;
+/// # extern crate reqwest;
+/// #
+/// use jenkins_api::client::{HttpTransport, TransportFuture};
+///
+/// #[derive(Debug)]
+/// struct LoggingTransport(reqwest::Client);
+///
+/// impl HttpTransport for LoggingTransport {
+///     fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+///         println!("{} {}", request.method(), request.url());
+///         HttpTransport::execute(&self.0, request)
+///     }
+/// }
+///
+/// let jenkins = jenkins_api::JenkinsBuilder::new("http://localhost:8080")
+///     .with_transport(LoggingTransport(reqwest::Client::new()))
+///     .build()
+///     .unwrap();
+/// ```
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Send `request` and return the response from the server
+    fn execute(&self, request: Request) -> TransportFuture<'_>;
+}
+
+impl HttpTransport for Client {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(Client::execute(self, request).await?) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CannedTransport {
+        requested: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl HttpTransport for CannedTransport {
+        fn execute(&self, request: Request) -> TransportFuture<'_> {
+            self.requested
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", request.method(), request.url()));
+            Box::pin(async move {
+                Ok(Response::from(
+                    http::Response::builder()
+                        .status(200)
+                        .body(r#"{"name": "canned"}"#)?,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn can_use_custom_transport() {
+        let transport = CannedTransport::default();
+        let requested = transport.requested.clone();
+        let jenkins_client = crate::JenkinsBuilder::new("http://none:8080")
+            .with_transport(transport)
+            .build()
+            .unwrap();
+
+        let response: serde_json::Value = jenkins_client
+            .get_object_as(crate::client::Path::Raw { path: "/canned" }, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response["name"], "canned");
+        assert_eq!(
+            *requested.lock().unwrap(),
+            vec!["GET http://none:8080/canned/api/json"]
+        );
+    }
+}
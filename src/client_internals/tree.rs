@@ -2,6 +2,8 @@ use std::fmt::Display;
 
 use serde::{Serialize, Serializer};
 
+use super::Cursor;
+
 /// Jenkins tree query parameter
 #[derive(Debug)]
 pub struct TreeQueryParam {
@@ -9,6 +11,8 @@ pub struct TreeQueryParam {
     keyname: Option<String>,
     /// Children keys
     subkeys: Vec<TreeQueryParam>,
+    /// Range of elements to get, when the key is a list
+    range: Option<(u32, u32)>,
 }
 impl Serialize for TreeQueryParam {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -20,6 +24,15 @@ impl Serialize for TreeQueryParam {
 }
 impl Display for TreeQueryParam {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_fields(f)?;
+        match self.range {
+            Some((start, end)) => write!(f, "{{{},{}}}", start, end),
+            None => Ok(()),
+        }
+    }
+}
+impl TreeQueryParam {
+    fn fmt_fields(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match (self.keyname.as_ref(), self.subkeys.len()) {
             (Some(keyname), 0) => write!(f, "{}", keyname),
             (Some(keyname), _) => write!(
@@ -67,6 +80,7 @@ impl TreeBuilder {
             tree: TreeQueryParam {
                 keyname: None,
                 subkeys: vec![],
+                range: None,
             },
         }
     }
@@ -81,6 +95,7 @@ impl TreeBuilder {
             tree: TreeQueryParam {
                 keyname: Some(name.to_string()),
                 subkeys: vec![],
+                range: None,
             },
         }
     }
@@ -88,6 +103,15 @@ impl TreeBuilder {
     pub fn with_subfield<T: Into<TreeQueryParam>>(self, subfield: T) -> Self {
         self.with_field(subfield)
     }
+    /// Only get the elements from `start` (included) to `end` (excluded) of a list
+    pub fn with_range(mut self, start: u32, end: u32) -> Self {
+        self.tree.range = Some((start, end));
+        self
+    }
+    /// Only get the elements of a list in the page at `cursor`
+    pub fn with_cursor(self, cursor: Cursor) -> Self {
+        self.with_range(cursor.offset(), cursor.end())
+    }
     /// Build the `TreeQueryParam`
     pub fn build(self) -> TreeQueryParam {
         self.tree
@@ -103,6 +127,7 @@ impl<'a> From<&'a str> for TreeQueryParam {
         TreeQueryParam {
             keyname: Some(value.to_string()),
             subkeys: vec![],
+            range: None,
         }
     }
 }
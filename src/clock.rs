@@ -0,0 +1,99 @@
+//! Clock of the Jenkins controller
+//!
+//! The time of the controller is read from the `Date` header of its responses, which has a
+//! precision of one second. The clock of agents compared to the controller is reported by
+//! `CommonComputer::clock_difference`.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::DATE;
+
+use crate::client::Result;
+use crate::client_internals::path::Path;
+use crate::Jenkins;
+
+/// Time of the Jenkins controller, compared to the local clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerTime {
+    /// Time of the controller when it answered
+    pub server_time: SystemTime,
+    /// Local time at the middle of the request
+    pub local_time: SystemTime,
+    /// Duration of the request
+    pub round_trip: Duration,
+}
+
+impl ServerTime {
+    /// Difference between the clock of the controller and the local clock in milliseconds,
+    /// positive when the controller is ahead
+    pub fn skew_millis(&self) -> i64 {
+        millis_since_epoch(self.server_time) - millis_since_epoch(self.local_time)
+    }
+
+    /// Is the clock of the controller more than `tolerance` away from the local clock. As the
+    /// time of the controller is known to the second, `tolerance` should be at least a second
+    /// plus the round trip of the request
+    pub fn is_skewed(&self, tolerance: Duration) -> bool {
+        self.skew_millis().unsigned_abs() > tolerance.as_millis() as u64
+    }
+}
+
+fn millis_since_epoch(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_millis() as i64,
+        Err(error) => -(error.duration().as_millis() as i64),
+    }
+}
+
+impl Jenkins {
+    /// Get the time of the Jenkins controller, from the `Date` header of its response, if it
+    /// sends one
+    pub async fn get_server_time(&self) -> Result<Option<ServerTime>> {
+        let sent = SystemTime::now();
+        let response = self
+            .get_with_params(&Path::Home, [("tree", "mode")])
+            .await?;
+        let round_trip = sent.elapsed().unwrap_or_default();
+        Ok(response
+            .headers()
+            .get(DATE)
+            .and_then(|date| date.to_str().ok())
+            .and_then(|date| httpdate::parse_http_date(date).ok())
+            .map(|server_time| ServerTime {
+                server_time,
+                local_time: sent + round_trip / 2,
+                round_trip,
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_server_time() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "mode".to_string(),
+            ))
+            .with_header("date", "Sun, 06 Nov 1994 08:49:37 GMT")
+            .with_body(r#"{"mode": "NORMAL"}"#)
+            .create();
+
+        let time = jenkins_client.get_server_time().await.unwrap().unwrap();
+
+        assert_eq!(
+            time.server_time,
+            UNIX_EPOCH + Duration::from_secs(784_111_777)
+        );
+        assert!(time.skew_millis() < 0);
+        assert!(time.is_skewed(Duration::from_secs(60)));
+        mock.assert();
+    }
+}
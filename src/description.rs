@@ -0,0 +1,76 @@
+//! Helpers to display the description of jobs, builds, views and nodes
+//!
+//! Descriptions are rendered by Jenkins with the configured markup formatter. With the default
+//! plain text formatter they are escaped, but with an HTML formatter they may contain markup that
+//! should not be injected as is in another UI.
+
+/// Markup formatter configured in Jenkins, deciding how descriptions are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupFormatter {
+    /// Descriptions are plain text, escaped when rendered (Jenkins default)
+    PlainText,
+    /// Descriptions are HTML, like with the "Safe HTML" or "Raw HTML" formatters
+    Html,
+}
+
+/// Escape a description so that it can be inserted in an HTML page and be displayed as is
+pub fn escape_html(description: &str) -> String {
+    let mut escaped = String::with_capacity(description.len());
+    for c in description.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Get the text of a description, removing any markup if it is rendered as HTML
+#[cfg(feature = "html-sanitizer")]
+pub fn to_plain_text(description: &str, formatter: MarkupFormatter) -> String {
+    // wide enough to never wrap lines of a description
+    const WIDTH: usize = 10_000;
+    match formatter {
+        MarkupFormatter::PlainText => description.to_string(),
+        MarkupFormatter::Html => html2text::from_read_with_decorator(
+            description.as_bytes(),
+            WIDTH,
+            html2text::render::text_renderer::TrivialDecorator::new(),
+        )
+        .trim_end()
+        .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_escape_html() {
+        assert_eq!(
+            escape_html(r#"<script>alert("x & y")</script>"#),
+            "&lt;script&gt;alert(&quot;x &amp; y&quot;)&lt;/script&gt;"
+        );
+    }
+
+    #[cfg(feature = "html-sanitizer")]
+    #[test]
+    fn can_get_plain_text_of_html_description() {
+        let description =
+            r#"Deploys <b>prod</b><script>alert("x")</script> <a href="http://x">docs</a>"#;
+
+        assert_eq!(
+            to_plain_text(description, MarkupFormatter::PlainText),
+            description
+        );
+        assert_eq!(
+            to_plain_text(description, MarkupFormatter::Html),
+            "Deploys prod docs"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Disk space used by jobs, as computed by the Disk Usage plugin
+//!
+//! The plugin computes the sizes periodically on the controller, they are read from the
+//! actions it adds to each job.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::action::{CommonAction, ProjectDiskUsageAction};
+use crate::client::{Result, TreeBuilder};
+use crate::Jenkins;
+
+/// Disk space used by a job
+#[derive(Debug, Clone)]
+pub struct JobDiskUsage {
+    /// Full name of the job
+    pub full_name: String,
+    /// URL of the job
+    pub url: String,
+    /// Disk usage computed by the plugin
+    pub usage: ProjectDiskUsageAction,
+}
+
+/// Disk space used by all the jobs of a Jenkins instance
+#[derive(Debug, Clone, Default)]
+pub struct DiskUsageReport {
+    /// Jobs with their disk usage
+    pub jobs: Vec<JobDiskUsage>,
+    /// Full names of the jobs without disk usage, not computed yet by the plugin
+    pub jobs_without_usage: Vec<String>,
+}
+
+impl DiskUsageReport {
+    /// Total disk space used by the jobs, in bytes
+    pub fn total(&self) -> u64 {
+        self.jobs.iter().map(|job| job.usage.total()).sum()
+    }
+
+    /// Disk space used by the jobs in bytes, grouped by the first `depth` folders of their full
+    /// name. With a depth of 1, jobs are grouped by top-level folder, like by team
+    pub fn by_folder(&self, depth: usize) -> BTreeMap<String, u64> {
+        let mut folders = BTreeMap::new();
+        for job in &self.jobs {
+            let segments = job.full_name.split('/').collect::<Vec<_>>();
+            let folder = segments[..depth.min(segments.len())].join("/");
+            *folders.entry(folder).or_insert(0) += job.usage.total();
+        }
+        folders
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JobWithUsage {
+    url: String,
+    #[serde(default)]
+    actions: Vec<Option<CommonAction>>,
+}
+
+impl Jenkins {
+    /// Get the disk space used by all the jobs, in all folders, as computed by the Disk Usage
+    /// plugin
+    pub async fn get_disk_usage_report(&self) -> Result<DiskUsageReport> {
+        let folders = self
+            .walk_folders::<(), JobWithUsage>(
+                |tree| tree,
+                |jobs| {
+                    jobs.with_subfield("url").with_subfield(
+                        TreeBuilder::object("actions")
+                            .with_subfield("diskUsageWithoutBuilds")
+                            .with_subfield("allDiskUsageWithoutBuilds")
+                            .with_subfield("jobRootDirDiskUsage")
+                            .with_subfield("allSlaveWorkspaces")
+                            .with_subfield("allCustomOrNonSlaveWorkspaces")
+                            .with_subfield("buildsDiskUsage"),
+                    )
+                },
+            )
+            .await?;
+
+        let mut report = DiskUsageReport::default();
+        for job in folders.into_iter().flat_map(|folder| folder.jobs) {
+            if job.is_folder {
+                continue;
+            }
+            let full_name = job.path.full_name();
+            let usage = job
+                .fields
+                .actions
+                .iter()
+                .flatten()
+                .find_map(|action| action.as_variant::<ProjectDiskUsageAction>().ok());
+            match usage {
+                Some(usage) => report.jobs.push(JobDiskUsage {
+                    full_name,
+                    url: job.fields.url,
+                    usage,
+                }),
+                None => report.jobs_without_usage.push(full_name),
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_get_disk_usage_report() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let jenkins_client = crate::JenkinsBuilder::new(&url).build().unwrap();
+
+        let usage = |job: u64, workspaces: u64| {
+            serde_json::json!({
+                "_class": "hudson.plugins.disk_usage.ProjectDiskUsageAction",
+                "allCustomOrNonSlaveWorkspaces": 0,
+                "allSlaveWorkspaces": workspaces,
+                "buildsDiskUsage": {"all": job / 2, "locked": 0},
+                "diskUsageWithoutBuilds": job / 2,
+                "jobRootDirDiskUsage": job
+            })
+        };
+        let root = server
+            .mock("GET", "/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "jobs": [
+                        {
+                            "name": "payments",
+                            "url": format!("{}/job/payments/", url),
+                            "jobs": [{"name": "api"}],
+                            "actions": [{}]
+                        },
+                        {
+                            "name": "docs",
+                            "url": format!("{}/job/docs/", url),
+                            "actions": [{"_class": "hudson.model.ParametersDefinitionProperty"}, usage(100, 20)]
+                        },
+                        {
+                            "name": "new-job",
+                            "url": format!("{}/job/new-job/", url),
+                            "actions": [{}]
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+        let payments = server
+            .mock("GET", "/job/payments/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "jobs": [
+                        {
+                            "name": "api",
+                            "url": format!("{}/job/payments/job/api/", url),
+                            "actions": [usage(1000, 500)]
+                        },
+                        {
+                            "name": "web",
+                            "url": format!("{}/job/payments/job/web/", url),
+                            "actions": [usage(300, 0)]
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let report = jenkins_client.get_disk_usage_report().await.unwrap();
+
+        assert_eq!(report.total(), 1920);
+        assert_eq!(report.jobs_without_usage, vec!["new-job"]);
+        let by_team = report.by_folder(1);
+        assert_eq!(by_team.get("payments"), Some(&1800));
+        assert_eq!(by_team.get("docs"), Some(&120));
+        root.assert();
+        payments.assert();
+    }
+}
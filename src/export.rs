@@ -0,0 +1,293 @@
+//! Export of the configuration of Jenkins items, for backups
+//!
+//! The `config.xml` of every folder, job and view is written to a tar archive, following the
+//! layout of `JENKINS_HOME`: `jobs/folder/jobs/job/config.xml` for a job in a folder, and
+//! `views/view/config.xml` or `jobs/folder/views/view/config.xml` for views. A `manifest.json`
+//! listing the exported items is written at the end of the archive.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::client::{Result, TreeBuilder};
+use crate::client_internals::path::Path as PrivatePath;
+use crate::Jenkins;
+
+/// Kind of an exported item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportedItemKind {
+    /// A folder, or another item containing jobs like a multibranch project
+    Folder,
+    /// A job
+    Job,
+    /// A view
+    View,
+}
+
+/// An item whose configuration was exported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedConfig {
+    /// Kind of the item
+    pub kind: ExportedItemKind,
+    /// Full name of the item, like `folder/job`, or `folder/view` for a view of a folder
+    pub full_name: String,
+    /// Path of the `config.xml` in the archive
+    pub archive_path: String,
+    /// Size of the `config.xml`
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest<'a> {
+    exported_at: u64,
+    items: &'a [ExportedConfig],
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemName {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FolderViews {
+    #[serde(default)]
+    views: Vec<ItemName>,
+}
+
+/// Directory of the item at `path` in the archive, like `jobs/folder/jobs/job/`
+fn archive_dir<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    names.map(|name| format!("jobs/{}/", name)).collect()
+}
+
+/// Write a file to a tar archive
+async fn write_tar_entry<W>(
+    archive: &mut tokio_tar::Builder<W>,
+    name: &str,
+    content: &[u8],
+    mtime: u64,
+) -> Result<()>
+where
+    W: AsyncWrite + Unpin + Send,
+{
+    let mut header = tokio_tar::Header::new_gnu();
+    header.set_entry_type(tokio_tar::EntryType::Regular);
+    header.set_mode(0o644);
+    header.set_size(content.len() as u64);
+    header.set_mtime(mtime);
+    archive.append_data(&mut header, name, content).await?;
+    Ok(())
+}
+
+/// Join a parent full name and a name
+fn full_name(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+impl Jenkins {
+    async fn get_config_xml_at(&self, path: &str) -> Result<Vec<u8>> {
+        Ok(self
+            .get_endpoint(&PrivatePath::Raw {
+                path: &format!("{}/config.xml", path),
+            })
+            .await?
+            .bytes()
+            .await?
+            .to_vec())
+    }
+
+    /// Export the `config.xml` of all folders, jobs and views to `writer` as a tar archive,
+    /// returning the exported items
+    ///
+    /// Configurations are fetched and written one at a time, so the archive is never fully in
+    /// memory. The archive ends with a `manifest.json` listing the exported items.
+    pub async fn export_all_configs<W>(&self, writer: &mut W) -> Result<Vec<ExportedConfig>>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let mut archive = tokio_tar::Builder::new_non_terminated(&mut *writer);
+        let mtime = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let folders = self
+            .walk_folders::<FolderViews, ()>(
+                |tree| tree.with_field(TreeBuilder::object("views").with_subfield("name")),
+                |jobs| jobs,
+            )
+            .await?;
+
+        let mut exported = vec![];
+        for folder in folders {
+            let folder_path = folder.path.to_folder_path().to_string();
+            let folder_dir = archive_dir(folder.path.names());
+            for view in folder.fields.views {
+                let archive_path = format!("{}views/{}/config.xml", folder_dir, view.name);
+                let config = self
+                    .get_config_xml_at(&format!(
+                        "{}/view/{}",
+                        folder_path,
+                        urlencoding::encode(&view.name)
+                    ))
+                    .await?;
+                write_tar_entry(&mut archive, &archive_path, &config, mtime).await?;
+                exported.push(ExportedConfig {
+                    kind: ExportedItemKind::View,
+                    full_name: full_name(&folder.path.full_name(), &view.name),
+                    archive_path,
+                    size: config.len() as u64,
+                });
+            }
+
+            for job in folder.jobs {
+                let archive_path = format!("{}config.xml", archive_dir(job.path.names()));
+                let config = self
+                    .get_config_xml_at(&job.path.to_path()?.to_string())
+                    .await?;
+                write_tar_entry(&mut archive, &archive_path, &config, mtime).await?;
+                exported.push(ExportedConfig {
+                    kind: if job.is_folder {
+                        ExportedItemKind::Folder
+                    } else {
+                        ExportedItemKind::Job
+                    },
+                    full_name: job.path.full_name(),
+                    archive_path,
+                    size: config.len() as u64,
+                });
+            }
+        }
+
+        let manifest = serde_json::to_vec_pretty(&Manifest {
+            exported_at: mtime,
+            items: &exported,
+        })?;
+        write_tar_entry(&mut archive, "manifest.json", &manifest, mtime).await?;
+        archive.finish().await?;
+        drop(archive);
+        writer.flush().await?;
+        Ok(exported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    /// List the files of a tar archive with their content
+    async fn untar(archive: &[u8]) -> Vec<(String, String)> {
+        let mut files = vec![];
+        let mut entries = tokio_tar::Archive::new(archive).entries().unwrap();
+        while let Some(entry) = entries.next().await {
+            let mut entry = entry.unwrap();
+            let name = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut content = String::new();
+            let _ = entry.read_to_string(&mut content).await.unwrap();
+            files.push((name, content));
+        }
+        files
+    }
+
+    #[tokio::test]
+    async fn can_export_all_configs() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mut mocks = vec![
+            server
+                .mock("GET", "/api/json")
+                .match_query(mockito::Matcher::Any)
+                .with_body(
+                    r#"{"jobs":[{"name":"build"},{"name":"team","jobs":[]}],"views":[{"name":"all"}]}"#,
+                )
+                .create(),
+            server
+                .mock("GET", "/job/team/api/json")
+                .match_query(mockito::Matcher::Any)
+                .with_body(r#"{"jobs":[{"name":"deploy app"}],"views":[]}"#)
+                .create(),
+        ];
+        for (path, body) in [
+            ("/view/all/config.xml", "<hudson.model.AllView/>"),
+            ("/job/build/config.xml", "<project/>"),
+            (
+                "/job/team/config.xml",
+                "<com.cloudbees.hudson.plugins.folder.Folder/>",
+            ),
+            (
+                "/job/team/job/deploy%20app/config.xml",
+                "<flow-definition/>",
+            ),
+        ] {
+            mocks.push(server.mock("GET", path).with_body(body).create());
+        }
+
+        let mut archive = vec![];
+        let exported = jenkins_client
+            .export_all_configs(&mut archive)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            exported
+                .iter()
+                .map(|item| (item.kind, item.full_name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ExportedItemKind::View, "all"),
+                (ExportedItemKind::Job, "build"),
+                (ExportedItemKind::Folder, "team"),
+                (ExportedItemKind::Job, "team/deploy app"),
+            ]
+        );
+        let files = untar(&archive).await;
+        assert_eq!(
+            files
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "views/all/config.xml",
+                "jobs/build/config.xml",
+                "jobs/team/config.xml",
+                "jobs/team/jobs/deploy app/config.xml",
+                "manifest.json",
+            ]
+        );
+        assert_eq!(files[3].1, "<flow-definition/>");
+        let manifest: serde_json::Value = serde_json::from_str(&files[4].1).unwrap();
+        assert_eq!(
+            manifest["items"][3]["archivePath"],
+            "jobs/team/jobs/deploy app/config.xml"
+        );
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn can_write_long_names() {
+        let name = format!("jobs/{}/config.xml", "a".repeat(120));
+        let mut archive = tokio_tar::Builder::new_non_terminated(vec![]);
+
+        write_tar_entry(&mut archive, &name, b"<project/>", 0)
+            .await
+            .unwrap();
+        let archive = archive.into_inner().await.unwrap();
+
+        assert_eq!(
+            untar(&archive).await,
+            vec![(name, "<project/>".to_string())]
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Causes of build failures identified by the Build Failure Analyzer plugin
+//!
+//! Causes found on a `Build` are listed by its
+//! [`FailureCauseBuildAction`](../action/struct.FailureCauseBuildAction.html). The knowledge base
+//! of causes the plugin looks for is managed from `/failure-cause-management`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{self, Result, TreeBuilder};
+use crate::client_internals::{Name, Path, ResponseExt};
+use crate::Jenkins;
+
+/// A line of the log of a build matching an indication of a failure cause
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FoundIndication {
+    /// Pattern that matched
+    pub pattern: String,
+    /// File in which the pattern matched, usually the log of the build
+    pub matching_file: Option<String>,
+    /// Text that matched the pattern
+    pub matching_string: Option<String>,
+}
+
+/// A failure cause identified on a build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FoundFailureCause {
+    /// ID of the cause in the knowledge base
+    pub id: String,
+    /// Name of the cause
+    pub name: String,
+    /// Description of the cause
+    pub description: Option<String>,
+    /// Categories of the cause, to group similar causes
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Indications that matched
+    #[serde(default)]
+    pub found_indications: Vec<FoundIndication>,
+}
+
+impl FoundFailureCause {
+    /// Is this cause in the category `category`
+    pub fn has_category(&self, category: &str) -> bool {
+        self.categories.iter().any(|other| other == category)
+    }
+}
+
+/// An indication of a failure cause, matched against the log of failed builds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Indication {
+    /// _class provided by Jenkins, telling if the pattern is matched line by line or on the
+    /// whole log
+    #[serde(rename = "_class")]
+    pub class: Option<String>,
+    /// Regular expression of the indication
+    pub pattern: String,
+}
+
+/// A failure cause of the knowledge base of the plugin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureCause {
+    /// ID of the cause
+    pub id: String,
+    /// Name of the cause
+    pub name: String,
+    /// Description of the cause
+    pub description: Option<String>,
+    /// Comment on the cause
+    pub comment: Option<String>,
+    /// Categories of the cause
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Indications looked for in the log of failed builds
+    #[serde(default)]
+    pub indications: Vec<Indication>,
+}
+
+impl Jenkins {
+    /// Get the failure causes of the knowledge base of the Build Failure Analyzer plugin
+    pub async fn get_failure_causes(&self) -> Result<Vec<FailureCause>> {
+        #[derive(Deserialize)]
+        struct Causes {
+            causes: Vec<FailureCause>,
+        }
+
+        let causes: Causes = self
+            .get_object_as(
+                client::Path::Raw {
+                    path: &Path::FailureCauseManagement { method: None }.to_string(),
+                },
+                TreeBuilder::new()
+                    .with_field(
+                        TreeBuilder::object("causes")
+                            .with_subfield("id")
+                            .with_subfield("name")
+                            .with_subfield("description")
+                            .with_subfield("comment")
+                            .with_subfield("categories")
+                            .with_subfield(
+                                TreeBuilder::object("indications")
+                                    .with_subfield("_class")
+                                    .with_subfield("pattern"),
+                            ),
+                    )
+                    .build(),
+            )
+            .await?;
+        Ok(causes.causes)
+    }
+
+    /// Get the failure cause `id` of the knowledge base
+    pub async fn get_failure_cause(&self, id: &str) -> Result<FailureCause> {
+        self.get(&Path::FailureCause { id: Name::Name(id) })
+            .await?
+            .parse_json()
+            .await
+    }
+
+    /// Remove the failure cause `id` from the knowledge base
+    pub async fn remove_failure_cause(&self, id: &str) -> Result<()> {
+        let _ = self
+            .post_with_body(
+                &Path::FailureCauseManagement {
+                    method: Some("removeConfirm"),
+                },
+                url::form_urlencoded::Serializer::new(String::new())
+                    .append_pair("id", id)
+                    .finish(),
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{CommonAction, FailureCauseBuildAction};
+
+    #[test]
+    fn can_read_found_failure_causes() {
+        let action: CommonAction = serde_json::from_value(serde_json::json!({
+            "_class": "com.sonyericsson.jenkins.plugins.bfa.model.FailureCauseBuildAction",
+            "foundFailureCauses": [{
+                "categories": ["infrastructure"],
+                "description": "The agent was disconnected during the build",
+                "foundIndications": [{
+                    "_class": "com.sonyericsson.jenkins.plugins.bfa.model.FoundIndication",
+                    "matchingFile": "log",
+                    "matchingString": "hudson.remoting.ChannelClosedException",
+                    "pattern": ".*ChannelClosedException.*"
+                }],
+                "id": "b1c2",
+                "name": "Agent disconnected"
+            }]
+        }))
+        .unwrap();
+
+        let action: FailureCauseBuildAction = action.as_variant().unwrap();
+
+        let cause = &action.found_failure_causes[0];
+        assert_eq!(cause.name, "Agent disconnected");
+        assert!(cause.has_category("infrastructure"));
+        assert_eq!(
+            cause.found_indications[0].matching_string.as_deref(),
+            Some("hudson.remoting.ChannelClosedException")
+        );
+    }
+
+    #[tokio::test]
+    async fn can_manage_failure_causes() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let causes = server
+            .mock("GET", "/failure-cause-management/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "causes[id,name,description,comment,categories,indications[_class,pattern]]"
+                    .to_string(),
+            ))
+            .with_body(
+                serde_json::json!({
+                    "causes": [{
+                        "id": "b1c2",
+                        "name": "Agent disconnected",
+                        "description": "The agent was disconnected during the build",
+                        "comment": null,
+                        "categories": ["infrastructure"],
+                        "indications": [{
+                            "_class": "com.sonyericsson.jenkins.plugins.bfa.model.indication.BuildLogIndication",
+                            "pattern": ".*ChannelClosedException.*"
+                        }]
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+        let remove = server
+            .mock("POST", "/failure-cause-management/removeConfirm")
+            .match_body("id=b1c2")
+            .create();
+
+        let causes_found = jenkins_client.get_failure_causes().await.unwrap();
+        assert_eq!(
+            causes_found[0].indications[0].pattern,
+            ".*ChannelClosedException.*"
+        );
+        jenkins_client
+            .remove_failure_cause(&causes_found[0].id)
+            .await
+            .unwrap();
+
+        causes.assert();
+        remove.assert();
+    }
+
+    #[tokio::test]
+    async fn can_get_failure_cause() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/failure-cause-management/b1c2/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "id": "b1c2",
+                    "name": "Agent disconnected",
+                    "description": "The agent was disconnected during the build",
+                    "comment": "Check the network of the agent",
+                    "categories": ["infrastructure"],
+                    "indications": [{
+                        "_class": "com.sonyericsson.jenkins.plugins.bfa.model.indication.BuildLogIndication",
+                        "pattern": ".*ChannelClosedException.*"
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let cause = jenkins_client.get_failure_cause("b1c2").await.unwrap();
+
+        assert_eq!(cause.name, "Agent disconnected");
+        assert_eq!(
+            cause.comment.as_deref(),
+            Some("Check the network of the agent")
+        );
+        assert_eq!(cause.indications[0].pattern, ".*ChannelClosedException.*");
+        mock.assert();
+    }
+}
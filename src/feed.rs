@@ -0,0 +1,196 @@
+//! Atom feeds of builds, as provided by `/rssAll`, `/rssFailed` and `/rssLatest`
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_internals::{Path, Result};
+use crate::job::JobName;
+use crate::Jenkins;
+
+/// Feeds of builds provided by Jenkins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedKind {
+    /// All builds
+    All,
+    /// Failed builds
+    Failed,
+    /// Last build of each job, only available for the whole instance
+    Latest,
+}
+
+impl FeedKind {
+    fn endpoint(self) -> &'static str {
+        match self {
+            FeedKind::All => "rssAll",
+            FeedKind::Failed => "rssFailed",
+            FeedKind::Latest => "rssLatest",
+        }
+    }
+}
+
+/// A feed of builds
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Feed {
+    /// Title of the feed
+    pub title: String,
+    /// Last update of the feed
+    pub updated: Option<String>,
+    /// Builds in the feed, most recent first
+    #[serde(rename = "entry", default)]
+    pub entries: Vec<FeedEntry>,
+}
+
+/// A build in a `Feed`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(from = "RawFeedEntry", into = "RawFeedEntry")]
+pub struct FeedEntry {
+    /// Title of the entry, with the job name, build number and status
+    pub title: String,
+    /// URL of the build
+    pub url: String,
+    /// Unique ID of the entry
+    pub id: String,
+    /// Build number
+    pub number: Option<u32>,
+    /// When the build was started
+    pub published: Option<String>,
+    /// Last update of the build
+    pub updated: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RawFeedLink {
+    #[serde(rename = "@href")]
+    href: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RawFeedEntry {
+    title: String,
+    link: RawFeedLink,
+    id: String,
+    published: Option<String>,
+    updated: Option<String>,
+}
+
+impl From<RawFeedEntry> for FeedEntry {
+    fn from(raw: RawFeedEntry) -> Self {
+        // ids are like `tag:hudson.dev.java.net,2008:http://jenkins/job/my-job/:42`
+        let number = raw
+            .id
+            .rsplit(':')
+            .next()
+            .and_then(|number| number.parse().ok());
+        FeedEntry {
+            title: raw.title,
+            url: raw.link.href,
+            id: raw.id,
+            number,
+            published: raw.published,
+            updated: raw.updated,
+        }
+    }
+}
+
+impl From<FeedEntry> for RawFeedEntry {
+    fn from(entry: FeedEntry) -> Self {
+        RawFeedEntry {
+            title: entry.title,
+            link: RawFeedLink { href: entry.url },
+            id: entry.id,
+            published: entry.published,
+            updated: entry.updated,
+        }
+    }
+}
+
+impl Jenkins {
+    async fn get_feed_at(&self, path: &Path<'_>) -> Result<Feed> {
+        let body = self.get_endpoint(path).await?.text().await?;
+        Ok(quick_xml::de::from_str(&body)?)
+    }
+
+    /// Get a feed of builds of the whole instance
+    pub async fn get_feed(&self, kind: FeedKind) -> Result<Feed> {
+        self.get_feed_at(&Path::Feed {
+            job_name: None,
+            feed: kind.endpoint(),
+        })
+        .await
+    }
+
+    /// Get a feed of builds of a job. Jenkins doesn't provide `FeedKind::Latest` for jobs
+    pub async fn get_job_feed<'a, J>(&self, job_name: J, kind: FeedKind) -> Result<Feed>
+    where
+        J: Into<JobName<'a>>,
+    {
+        self.get_feed_at(&job_name.into().to_path_with(|job_name| Path::Feed {
+            job_name: Some(job_name),
+            feed: kind.endpoint(),
+        })?)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static FAILED_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>my job all failed builds</title>
+  <link rel="alternate" type="text/html" href="http://jenkins/job/my%20job/"/>
+  <updated>2024-03-02T10:00:00Z</updated>
+  <author><name>Jenkins Server</name></author>
+  <id>urn:uuid:903deee0-7bfa-11db-9fe1-0800200c9a66</id>
+  <entry>
+    <title>my job #12 (broken since this build)</title>
+    <link rel="alternate" type="text/html" href="http://jenkins/job/my%20job/12/"/>
+    <id>tag:hudson.dev.java.net,2008:http://jenkins/job/my%20job/:12</id>
+    <published>2024-03-02T10:00:00Z</published>
+    <updated>2024-03-02T10:00:00Z</updated>
+  </entry>
+  <entry>
+    <title>my job #9 (broken for a long time)</title>
+    <link rel="alternate" type="text/html" href="http://jenkins/job/my%20job/9/"/>
+    <id>tag:hudson.dev.java.net,2008:http://jenkins/job/my%20job/:9</id>
+    <published>2024-03-01T10:00:00Z</published>
+    <updated>2024-03-01T10:00:00Z</updated>
+  </entry>
+</feed>"#;
+
+    #[tokio::test]
+    async fn can_get_job_feed() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/job/my%20job/rssFailed")
+            .with_body(FAILED_FEED)
+            .create();
+
+        let feed = jenkins_client
+            .get_job_feed("my job", FeedKind::Failed)
+            .await
+            .unwrap();
+
+        assert_eq!(feed.title, "my job all failed builds");
+        assert_eq!(feed.entries.len(), 2);
+        assert_eq!(feed.entries[0].url, "http://jenkins/job/my%20job/12/");
+        assert_eq!(feed.entries[0].number, Some(12));
+        assert_eq!(feed.entries[1].title, "my job #9 (broken for a long time)");
+        mock.assert();
+    }
+
+    #[test]
+    fn can_round_trip_feed() {
+        let feed: Feed = quick_xml::de::from_str(FAILED_FEED).unwrap();
+
+        let json = serde_json::to_value(&feed).unwrap();
+        let reloaded: Feed = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(reloaded.entries[0].url, "http://jenkins/job/my%20job/12/");
+        assert_eq!(reloaded.entries[0].number, Some(12));
+        assert_eq!(serde_json::to_value(&reloaded).unwrap(), json);
+    }
+}
@@ -45,3 +45,220 @@ macro_rules! specialize {
         }
     };
 }
+
+/// Deserialize a field that Jenkins may send as `null`, like descriptions on older versions, as
+/// its default value
+pub(crate) fn default_on_null<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + serde::Deserialize<'de>,
+{
+    Ok(<Option<T> as serde::Deserialize>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Queue ID of a build not known by Jenkins, like builds started before Jenkins 1.601
+pub(crate) fn unknown_queue_id() -> i32 {
+    -1
+}
+
+macro_rules! jenkins_classes {
+    ($($(#[$attr:meta])* $variant:ident => $class:expr,)*) => {
+        /// `_class` provided by Jenkins, with the classes known to this crate as variants
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum JenkinsClass {
+            $($(#[$attr])* $variant,)*
+            /// A class unknown to this crate
+            Unknown(String),
+        }
+
+        impl JenkinsClass {
+            /// Full name of the Java class
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(JenkinsClass::$variant => $class,)*
+                    JenkinsClass::Unknown(class) => class,
+                }
+            }
+        }
+
+        impl From<&str> for JenkinsClass {
+            fn from(class: &str) -> Self {
+                match class {
+                    $($class => JenkinsClass::$variant,)*
+                    _ => JenkinsClass::Unknown(class.to_string()),
+                }
+            }
+        }
+    };
+}
+
+jenkins_classes!(
+    /// `FreeStyleProject`
+    FreeStyleProject => "hudson.model.FreeStyleProject",
+    /// `WorkflowJob`
+    WorkflowJob => "org.jenkinsci.plugins.workflow.job.WorkflowJob",
+    /// `MatrixProject`
+    MatrixProject => "hudson.matrix.MatrixProject",
+    /// `MatrixConfiguration`
+    MatrixConfiguration => "hudson.matrix.MatrixConfiguration",
+    /// `MavenModuleSet`
+    MavenModuleSet => "hudson.maven.MavenModuleSet",
+    /// `MavenModule`
+    MavenModule => "hudson.maven.MavenModule",
+    /// `MultiJobProject`
+    MultiJobProject => "com.tikal.jenkins.plugins.multijob.MultiJobProject",
+    /// `BuildFlowJob`
+    BuildFlowJob => "com.cloudbees.plugins.flow.BuildFlow",
+    /// `ExternalJob`
+    ExternalJob => "hudson.model.ExternalJob",
+    /// `Folder`
+    Folder => "com.cloudbees.hudson.plugins.folder.Folder",
+    /// `WorkflowMultiBranchProject`
+    WorkflowMultiBranchProject => "org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject",
+    /// `OrganizationFolder`
+    OrganizationFolder => "jenkins.branch.OrganizationFolder",
+    /// `FreeStyleBuild`
+    FreeStyleBuild => "hudson.model.FreeStyleBuild",
+    /// `WorkflowRun`
+    WorkflowRun => "org.jenkinsci.plugins.workflow.job.WorkflowRun",
+    /// `MatrixBuild`
+    MatrixBuild => "hudson.matrix.MatrixBuild",
+    /// `MatrixRun`
+    MatrixRun => "hudson.matrix.MatrixRun",
+    /// `MavenModuleSetBuild`
+    MavenModuleSetBuild => "hudson.maven.MavenModuleSetBuild",
+    /// `MavenBuild`
+    MavenBuild => "hudson.maven.MavenBuild",
+    /// `MultiJobBuild`
+    MultiJobBuild => "com.tikal.jenkins.plugins.multijob.MultiJobBuild",
+    /// `BuildFlowRun`
+    BuildFlowRun => "com.cloudbees.plugins.flow.FlowRun",
+    /// `ListView`
+    ListView => "hudson.model.ListView",
+);
+
+impl JenkinsClass {
+    /// Is this the class of a pipeline job, of one of its builds, or of a multibranch pipeline
+    pub fn is_pipeline(&self) -> bool {
+        matches!(
+            self,
+            JenkinsClass::WorkflowJob
+                | JenkinsClass::WorkflowRun
+                | JenkinsClass::WorkflowMultiBranchProject
+        )
+    }
+
+    /// Is this the class of an item containing jobs
+    pub fn is_folder(&self) -> bool {
+        matches!(
+            self,
+            JenkinsClass::Folder
+                | JenkinsClass::WorkflowMultiBranchProject
+                | JenkinsClass::OrganizationFolder
+        )
+    }
+
+    /// Is this the class of a job that can have builds
+    pub fn is_job(&self) -> bool {
+        matches!(
+            self,
+            JenkinsClass::FreeStyleProject
+                | JenkinsClass::WorkflowJob
+                | JenkinsClass::MatrixProject
+                | JenkinsClass::MatrixConfiguration
+                | JenkinsClass::MavenModuleSet
+                | JenkinsClass::MavenModule
+                | JenkinsClass::MultiJobProject
+                | JenkinsClass::BuildFlowJob
+                | JenkinsClass::ExternalJob
+        )
+    }
+
+    /// Is this the class of a build
+    pub fn is_build(&self) -> bool {
+        matches!(
+            self,
+            JenkinsClass::FreeStyleBuild
+                | JenkinsClass::WorkflowRun
+                | JenkinsClass::MatrixBuild
+                | JenkinsClass::MatrixRun
+                | JenkinsClass::MavenModuleSetBuild
+                | JenkinsClass::MavenBuild
+                | JenkinsClass::MultiJobBuild
+                | JenkinsClass::BuildFlowRun
+        )
+    }
+
+    /// Is this the class of the type `T`
+    pub fn is<T: Class>(&self) -> bool {
+        self.as_str() == T::with_class()
+    }
+}
+
+impl From<String> for JenkinsClass {
+    fn from(class: String) -> Self {
+        match JenkinsClass::from(class.as_str()) {
+            JenkinsClass::Unknown(_) => JenkinsClass::Unknown(class),
+            known => known,
+        }
+    }
+}
+
+impl From<JenkinsClass> for String {
+    fn from(class: JenkinsClass) -> Self {
+        class.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for JenkinsClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::{CommonJob, ShortJob, WorkflowJob};
+
+    #[test]
+    fn can_dispatch_on_jenkins_class() {
+        let jobs: Vec<ShortJob> = serde_json::from_value(serde_json::json!([
+            {"_class": "org.jenkinsci.plugins.workflow.job.WorkflowJob", "name": "app", "url": "http://jenkins/job/app/", "color": "blue"},
+            {"_class": "com.cloudbees.hudson.plugins.folder.Folder", "name": "team", "url": "http://jenkins/job/team/"},
+            {"_class": "org.example.CustomProject", "name": "custom", "url": "http://jenkins/job/custom/", "color": "red"}
+        ]))
+        .unwrap();
+
+        let classes = jobs
+            .iter()
+            .map(|job| job.jenkins_class().unwrap())
+            .collect::<Vec<_>>();
+        assert!(classes[0].is_pipeline() && classes[0].is_job());
+        assert!(classes[0].is::<WorkflowJob>());
+        assert!(classes[1].is_folder() && !classes[1].is_job());
+        assert_eq!(
+            classes[2],
+            JenkinsClass::Unknown("org.example.CustomProject".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&classes[2]).unwrap(),
+            "org.example.CustomProject"
+        );
+
+        let job: CommonJob = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.model.FreeStyleProject",
+            "name": "build",
+            "displayName": "build",
+            "fullDisplayName": "build",
+            "fullName": "build",
+            "url": "http://jenkins/job/build/",
+            "buildable": true,
+            "healthReport": [],
+            "actions": []
+        }))
+        .unwrap();
+        assert_eq!(job.jenkins_class(), Some(JenkinsClass::FreeStyleProject));
+    }
+}
@@ -1,14 +1,14 @@
 //! Jenkins Home, describing state of the master
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::client_internals::{Path, Result};
+use crate::client_internals::{Path, ResponseExt, Result};
 use crate::job::ShortJob;
 use crate::view::ShortView;
 use crate::Jenkins;
 
 /// Describe how Jenkins allocates jobs to agents
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Mode {
     /// Any job can be started on this node
@@ -36,6 +36,18 @@ impl<'de> Deserialize<'de> for AgentPort {
         deserializer.deserialize_i32(AgentPortI32Visitor)
     }
 }
+impl Serialize for AgentPort {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            AgentPort::Disabled => serializer.serialize_i32(-1),
+            AgentPort::Random => serializer.serialize_i32(0),
+            AgentPort::Fixed(port) => serializer.serialize_u32(*port),
+        }
+    }
+}
 struct AgentPortI32Visitor;
 impl<'de> serde::de::Visitor<'de> for AgentPortI32Visitor {
     type Value = AgentPort;
@@ -117,7 +129,7 @@ impl<'de> serde::de::Visitor<'de> for AgentPortI32Visitor {
 }
 
 /// Index of Jenkins, with details about the master, a list of `Job` and a list of `View`
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Home {
     /// Mode of the node for job selections
@@ -147,6 +159,6 @@ pub struct Home {
 impl Jenkins {
     /// Get Jenkins `Home`
     pub async fn get_home(&self) -> Result<Home> {
-        Ok(self.get(&Path::Home).await?.json().await?)
+        self.get(&Path::Home).await?.parse_json().await
     }
 }
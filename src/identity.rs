@@ -0,0 +1,165 @@
+//! Identity of a Jenkins instance
+//!
+//! Each Jenkins instance has an RSA key pair, whose public key is sent in the
+//! `X-Instance-Identity` header. Checking it allows to make sure the expected instance is
+//! reached, even if DNS records were tampered with.
+
+use serde::Deserialize;
+
+use crate::client::Result;
+use crate::client_internals::path::Path;
+use crate::client_internals::ResponseExt;
+use crate::Jenkins;
+
+const INSTANCE_IDENTITY_HEADER: &str = "X-Instance-Identity";
+
+/// Endpoint of the instance identity plugin
+const INSTANCE_IDENTITY_PATH: &str = "/instance-identity/api/json";
+
+/// Endpoint of the agent listener, sending the identity in a header
+const AGENT_LISTENER_PATH: &str = "/tcpSlaveAgentListener/";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInstanceIdentity {
+    public_key: String,
+}
+
+/// Public key identifying a Jenkins instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceIdentity {
+    /// Public key, as the base64 encoding of its DER form
+    pub public_key: String,
+}
+
+impl InstanceIdentity {
+    /// Public key in PEM format
+    pub fn to_pem(&self) -> String {
+        let mut pem = String::from("-----BEGIN PUBLIC KEY-----\n");
+        let key = self.public_key.as_bytes();
+        for line in key.chunks(64) {
+            pem.push_str(&String::from_utf8_lossy(line));
+            pem.push('\n');
+        }
+        pem.push_str("-----END PUBLIC KEY-----\n");
+        pem
+    }
+
+    /// Is this identity the one with the public key `public_key`, either in PEM format or
+    /// encoded in base64
+    pub fn matches(&self, public_key: &str) -> bool {
+        strip_pem(public_key) == self.public_key
+    }
+}
+
+/// Keep only the base64 encoding of a public key that may be in PEM format
+fn strip_pem(public_key: &str) -> String {
+    public_key
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .flat_map(|line| line.chars())
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+impl Jenkins {
+    /// Get the public key identifying this Jenkins instance, if Jenkins sends it
+    ///
+    /// The key is read from the instance identity plugin, falling back to the header sent by
+    /// the agent listener if the plugin can't be reached.
+    pub async fn get_instance_identity(&self) -> Result<Option<InstanceIdentity>> {
+        let from_plugin: Result<RawInstanceIdentity> = async {
+            self.get_endpoint(&Path::Raw {
+                path: INSTANCE_IDENTITY_PATH,
+            })
+            .await?
+            .parse_json()
+            .await
+        }
+        .await;
+        if let Ok(identity) = from_plugin {
+            let public_key = strip_pem(&identity.public_key);
+            if !public_key.is_empty() {
+                return Ok(Some(InstanceIdentity { public_key }));
+            }
+        }
+
+        let response = self
+            .get_endpoint(&Path::Raw {
+                path: AGENT_LISTENER_PATH,
+            })
+            .await?;
+        Ok(response
+            .headers()
+            .get(INSTANCE_IDENTITY_HEADER)
+            .and_then(|key| key.to_str().ok())
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|public_key| InstanceIdentity {
+                public_key: public_key.to_string(),
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    const PUBLIC_KEY: &str = "MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAs1PtEBnF9OZZDIp9ANwnVcUu8vq2rbgFzD0yR3OuJm6R3wIDAQAB";
+
+    #[tokio::test]
+    async fn can_get_instance_identity() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let plugin = server
+            .mock("GET", "/instance-identity/api/json")
+            .with_body(
+                serde_json::json!({
+                    "_class": "org.jenkinsci.main.modules.instance_identity.IdentityRootAction",
+                    "publicKey": format!(
+                        "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+                        PUBLIC_KEY
+                    ),
+                })
+                .to_string(),
+            )
+            .create();
+
+        let identity = jenkins_client
+            .get_instance_identity()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(identity.public_key, PUBLIC_KEY);
+        plugin.assert();
+    }
+
+    #[tokio::test]
+    async fn can_get_instance_identity_from_agent_listener() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let plugin = server
+            .mock("GET", "/instance-identity/api/json")
+            .with_status(403)
+            .create();
+        let listener = server
+            .mock("GET", "/tcpSlaveAgentListener/")
+            .with_header("x-instance-identity", PUBLIC_KEY)
+            .create();
+
+        let identity = jenkins_client
+            .get_instance_identity()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(identity.public_key, PUBLIC_KEY);
+        let pem = identity.to_pem();
+        assert!(pem.starts_with("-----BEGIN PUBLIC KEY-----\nMIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAs1PtEBnF9OZZDIp9ANwn\n"));
+        assert!(identity.matches(&pem));
+        assert!(!identity.matches("MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAs1Pt"));
+        plugin.assert();
+        listener.assert();
+    }
+}
@@ -0,0 +1,124 @@
+//! Categories of the items that can be created in Jenkins
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_internals::{Path, ResponseExt, Result};
+use crate::Jenkins;
+
+/// A type of item that can be created, like a freestyle project or a folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatableItem {
+    /// Class of the item, like `hudson.model.FreeStyleProject`
+    pub class: String,
+    /// Name of the type of item
+    pub display_name: String,
+    /// Description of the type of item
+    pub description: Option<String>,
+    /// Icon of the type of item
+    pub icon_class_name: Option<String>,
+    /// Order of the type of item in its category
+    pub order: Option<i32>,
+}
+
+/// A category of items that can be created, with the types of items it contains
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemCategory {
+    /// ID of the category, like `standalone-projects`
+    pub id: String,
+    /// Name of the category
+    pub name: String,
+    /// Description of the category
+    pub description: Option<String>,
+    /// Order of the category
+    pub order: Option<i32>,
+    /// Minimum number of items for the category to be displayed
+    pub min_to_show: Option<u32>,
+    /// Types of items in this category
+    pub items: Vec<CreatableItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemCategories {
+    categories: Vec<ItemCategory>,
+}
+
+impl Jenkins {
+    /// Get the categories of items that the current user can create, with the types of items
+    /// in each category. Types of items are provided by plugins, so this shows which plugins
+    /// are available
+    pub async fn get_item_categories(&self) -> Result<Vec<ItemCategory>> {
+        let response: ItemCategories = self
+            .get_endpoint_with_params(&Path::ItemCategories, [("depth", "3")])
+            .await?
+            .parse_json()
+            .await?;
+        Ok(response.categories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_get_item_categories() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/view/all/itemCategories")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "depth".to_string(),
+                "3".to_string(),
+            ))
+            .with_body(
+                r#"{
+                    "_class": "jenkins.model.item_category.Categories",
+                    "categories": [
+                        {
+                            "description": "Create projects items",
+                            "id": "standalone-projects",
+                            "items": [
+                                {
+                                    "class": "hudson.model.FreeStyleProject",
+                                    "description": "Classic build job",
+                                    "displayName": "Freestyle project",
+                                    "iconFilePathPattern": "static/abc/images/:size/freestyleproject.png",
+                                    "iconClassName": "icon-freestyle-project",
+                                    "order": 1
+                                },
+                                {
+                                    "class": "org.jenkinsci.plugins.workflow.job.WorkflowJob",
+                                    "description": "Pipeline job",
+                                    "displayName": "Pipeline",
+                                    "iconFilePathPattern": "static/abc/images/:size/pipelinejob.png",
+                                    "order": 2
+                                }
+                            ],
+                            "minToShow": 1,
+                            "name": "Nested projects",
+                            "order": 1
+                        }
+                    ]
+                }"#,
+            )
+            .create();
+
+        let categories = jenkins_client.get_item_categories().await.unwrap();
+
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].id, "standalone-projects");
+        assert_eq!(
+            categories[0]
+                .items
+                .iter()
+                .map(|item| item.class.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "hudson.model.FreeStyleProject",
+                "org.jenkinsci.plugins.workflow.job.WorkflowJob"
+            ]
+        );
+        mock.assert();
+    }
+}
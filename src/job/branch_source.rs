@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::client::Result;
+
+/// A branch source of a multibranch project, as configured in its `config.xml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchSource {
+    /// Class of the SCM source, like `org.jenkinsci.plugins.github_branch_source.GitHubSCMSource`
+    pub class: String,
+    /// ID of the source in the project
+    pub id: Option<String>,
+    /// ID of the credentials used to scan the repository
+    pub credentials_id: Option<String>,
+    /// Owner of the repository, for GitHub and Bitbucket sources
+    pub repo_owner: Option<String>,
+    /// Name of the repository, for GitHub and Bitbucket sources
+    pub repository: Option<String>,
+    /// URL of the API of the server, for GitHub sources
+    pub api_uri: Option<String>,
+    /// URL of the repository, for Git sources
+    pub remote: Option<String>,
+    /// Classes of the behaviors (traits) configured on the source
+    pub behaviors: Vec<String>,
+}
+
+/// An SCM navigator of an organization folder, as configured in its `config.xml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SCMNavigator {
+    /// Class of the navigator, like `org.jenkinsci.plugins.github_branch_source.GitHubSCMNavigator`
+    pub class: String,
+    /// ID of the credentials used to scan the organization
+    pub credentials_id: Option<String>,
+    /// Owner of the repositories
+    pub repo_owner: Option<String>,
+    /// URL of the API of the server, for GitHub navigators
+    pub api_uri: Option<String>,
+    /// Classes of the behaviors (traits) configured on the navigator
+    pub behaviors: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct SCMConfig {
+    class: String,
+    fields: HashMap<String, String>,
+    behaviors: Vec<String>,
+}
+
+impl From<SCMConfig> for BranchSource {
+    fn from(mut config: SCMConfig) -> Self {
+        BranchSource {
+            class: config.class,
+            id: config.fields.remove("id"),
+            credentials_id: config.fields.remove("credentialsId"),
+            repo_owner: config.fields.remove("repoOwner"),
+            repository: config.fields.remove("repository"),
+            api_uri: config.fields.remove("apiUri"),
+            remote: config.fields.remove("remote"),
+            behaviors: config.behaviors,
+        }
+    }
+}
+
+impl From<SCMConfig> for SCMNavigator {
+    fn from(mut config: SCMConfig) -> Self {
+        SCMNavigator {
+            class: config.class,
+            credentials_id: config.fields.remove("credentialsId"),
+            repo_owner: config.fields.remove("repoOwner"),
+            api_uri: config.fields.remove("apiUri"),
+            behaviors: config.behaviors,
+        }
+    }
+}
+
+/// XStream escapes `_` as `__` and `$` as `_-` in element names
+fn xstream_decode(name: &str) -> String {
+    let mut decoded = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('_', Some('_')) => {
+                let _ = chars.next();
+                decoded.push('_');
+            }
+            ('_', Some('-')) => {
+                let _ = chars.next();
+                decoded.push('$');
+            }
+            (c, _) => decoded.push(c),
+        }
+    }
+    decoded
+}
+
+fn class_attribute(element: &BytesStart<'_>) -> Result<Option<String>> {
+    Ok(match element.try_get_attribute("class")? {
+        Some(attribute) => Some(attribute.unescape_value()?.into_owned()),
+        None => None,
+    })
+}
+
+/// Parse the configurations of SCMs found in a `config.xml`. `starts_config` is given the names
+/// of the parents of an element and the element, and returns the class of the SCM if the
+/// element holds the configuration of one
+fn parse_scm_configs<F>(config_xml: &str, starts_config: F) -> Result<Vec<SCMConfig>>
+where
+    F: Fn(&[String], &str, Option<String>) -> Option<String>,
+{
+    let mut reader = Reader::from_str(config_xml);
+    reader.config_mut().trim_text(true);
+
+    let mut configs = vec![];
+    let mut parents: Vec<String> = vec![];
+    // depth of the element holding the SCM configuration being parsed
+    let mut current: Option<(usize, SCMConfig)> = None;
+    let mut text = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = xstream_decode(std::str::from_utf8(element.name().as_ref())?);
+                match current {
+                    Some((depth, ref mut config)) => {
+                        if parents.len() == depth + 2 && parents[depth + 1] == "traits" {
+                            config.behaviors.push(name.clone());
+                        }
+                    }
+                    None => {
+                        if let Some(class) =
+                            starts_config(&parents, &name, class_attribute(&element)?)
+                        {
+                            current = Some((
+                                parents.len(),
+                                SCMConfig {
+                                    class,
+                                    ..Default::default()
+                                },
+                            ));
+                        }
+                    }
+                }
+                parents.push(name);
+                text.clear();
+            }
+            Event::Empty(element) => {
+                if let Some((depth, ref mut config)) = current {
+                    if parents.len() == depth + 2 && parents[depth + 1] == "traits" {
+                        config.behaviors.push(xstream_decode(std::str::from_utf8(
+                            element.name().as_ref(),
+                        )?));
+                    }
+                }
+            }
+            Event::Text(content) => text.push_str(&content.unescape()?),
+            Event::End(_) => {
+                let name = parents.pop().unwrap_or_default();
+                match current {
+                    Some((depth, _)) if depth == parents.len() => {
+                        if let Some((_, config)) = current.take() {
+                            configs.push(config);
+                        }
+                    }
+                    Some((depth, ref mut config)) if depth + 1 == parents.len() => {
+                        let _ = config.fields.insert(name, text.clone());
+                    }
+                    _ => (),
+                }
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(configs)
+}
+
+pub(crate) fn parse_branch_sources(config_xml: &str) -> Result<Vec<BranchSource>> {
+    Ok(parse_scm_configs(config_xml, |parents, name, class| {
+        if name == "source"
+            && parents.last().map(String::as_str) == Some("jenkins.branch.BranchSource")
+        {
+            class
+        } else {
+            None
+        }
+    })?
+    .into_iter()
+    .map(BranchSource::from)
+    .collect())
+}
+
+pub(crate) fn parse_navigators(config_xml: &str) -> Result<Vec<SCMNavigator>> {
+    Ok(parse_scm_configs(config_xml, |parents, name, _| {
+        if parents.len() == 2 && parents[1] == "navigators" {
+            Some(name.to_string())
+        } else {
+            None
+        }
+    })?
+    .into_iter()
+    .map(SCMNavigator::from)
+    .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_github_branch_source() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject plugin="workflow-multibranch@2.26">
+  <description></description>
+  <sources class="jenkins.branch.MultiBranchProject$BranchSourceList" plugin="branch-api@2.7.0">
+    <data>
+      <jenkins.branch.BranchSource>
+        <source class="org.jenkinsci.plugins.github_branch_source.GitHubSCMSource" plugin="github-branch-source@2.11.1">
+          <id>2c4e5ba6-3c8f-4fb3-8a0a-7d1de8b2bd88</id>
+          <apiUri>https://api.github.com</apiUri>
+          <credentialsId>github-token</credentialsId>
+          <repoOwner>my-org</repoOwner>
+          <repository>my-repo</repository>
+          <traits>
+            <org.jenkinsci.plugins.github__branch__source.BranchDiscoveryTrait>
+              <strategyId>1</strategyId>
+            </org.jenkinsci.plugins.github__branch__source.BranchDiscoveryTrait>
+            <jenkins.plugins.git.traits.CleanBeforeCheckoutTrait/>
+          </traits>
+        </source>
+        <strategy class="jenkins.branch.DefaultBranchPropertyStrategy">
+          <properties class="empty-list"/>
+        </strategy>
+      </jenkins.branch.BranchSource>
+      <jenkins.branch.BranchSource>
+        <source class="jenkins.plugins.git.GitSCMSource" plugin="git@4.2.2">
+          <id>d39c5bd4-8a4b-4a53-a1cd-b9b1c6c2ab1d</id>
+          <remote>https://git.example.com/repo.git</remote>
+          <credentialsId></credentialsId>
+        </source>
+      </jenkins.branch.BranchSource>
+    </data>
+  </sources>
+</org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject>"#;
+
+        let sources = parse_branch_sources(config_xml).unwrap();
+
+        assert_eq!(
+            sources,
+            vec![
+                BranchSource {
+                    class: "org.jenkinsci.plugins.github_branch_source.GitHubSCMSource".to_string(),
+                    id: Some("2c4e5ba6-3c8f-4fb3-8a0a-7d1de8b2bd88".to_string()),
+                    credentials_id: Some("github-token".to_string()),
+                    repo_owner: Some("my-org".to_string()),
+                    repository: Some("my-repo".to_string()),
+                    api_uri: Some("https://api.github.com".to_string()),
+                    remote: None,
+                    behaviors: vec![
+                        "org.jenkinsci.plugins.github_branch_source.BranchDiscoveryTrait"
+                            .to_string(),
+                        "jenkins.plugins.git.traits.CleanBeforeCheckoutTrait".to_string(),
+                    ],
+                },
+                BranchSource {
+                    class: "jenkins.plugins.git.GitSCMSource".to_string(),
+                    id: Some("d39c5bd4-8a4b-4a53-a1cd-b9b1c6c2ab1d".to_string()),
+                    credentials_id: Some("".to_string()),
+                    repo_owner: None,
+                    repository: None,
+                    api_uri: None,
+                    remote: Some("https://git.example.com/repo.git".to_string()),
+                    behaviors: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_parse_github_navigator() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<jenkins.branch.OrganizationFolder plugin="branch-api@2.7.0">
+  <navigators>
+    <org.jenkinsci.plugins.github__branch__source.GitHubSCMNavigator plugin="github-branch-source@2.11.1">
+      <repoOwner>my-org</repoOwner>
+      <apiUri>https://github.example.com/api/v3</apiUri>
+      <credentialsId>github-app</credentialsId>
+      <traits>
+        <org.jenkinsci.plugins.github__branch__source.OriginPullRequestDiscoveryTrait>
+          <strategyId>1</strategyId>
+        </org.jenkinsci.plugins.github__branch__source.OriginPullRequestDiscoveryTrait>
+      </traits>
+    </org.jenkinsci.plugins.github__branch__source.GitHubSCMNavigator>
+  </navigators>
+</jenkins.branch.OrganizationFolder>"#;
+
+        let navigators = parse_navigators(config_xml).unwrap();
+
+        assert_eq!(
+            navigators,
+            vec![SCMNavigator {
+                class: "org.jenkinsci.plugins.github_branch_source.GitHubSCMNavigator".to_string(),
+                credentials_id: Some("github-app".to_string()),
+                repo_owner: Some("my-org".to_string()),
+                api_uri: Some("https://github.example.com/api/v3".to_string()),
+                behaviors: vec![
+                    "org.jenkinsci.plugins.github_branch_source.OriginPullRequestDiscoveryTrait"
+                        .to_string()
+                ],
+            }]
+        );
+    }
+}
@@ -1,24 +1,90 @@
 //! Helper to build a job
 
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use log::warn;
 use reqwest::header::LOCATION;
+use reqwest::multipart::{Form, Part};
+use reqwest::Response;
 
 use serde::{self, Serialize};
 
 use crate::client::{self, Result};
 use crate::client_internals::{Name, Path};
-use crate::job::{Job, JobName};
+use crate::job::common::job_endpoint;
+use crate::job::{Job, JobName, ParameterValue};
 use crate::queue::ShortQueueItem;
 use crate::Jenkins;
 
+/// Get the `ShortQueueItem` Jenkins redirects to after triggering a build
+pub(crate) fn queue_item_from_response(response: &Response) -> Result<ShortQueueItem> {
+    if let Some(location) = response.headers().get(LOCATION) {
+        Ok(ShortQueueItem {
+            url: location.to_str().unwrap().to_string(),
+            extra_fields: None,
+        })
+    } else {
+        Err(client::Error::InvalidUrl {
+            url: "".to_string(),
+            expected: client::error::ExpectedType::QueueItem,
+        }
+        .into())
+    }
+}
+
+/// Trigger a build with a GET request to `build`, the `build` endpoint of a job, authenticated
+/// by its remote trigger `token` and without a CSRF crumb
+pub(crate) async fn trigger_remotely(
+    jenkins_client: &Jenkins,
+    build: &Path<'_>,
+    token: &str,
+    cause: Option<&str>,
+) -> Result<ShortQueueItem> {
+    let mut qps = vec![("token", token)];
+    if let Some(cause) = cause {
+        qps.push(("cause", cause));
+    }
+    let response = jenkins_client.get_endpoint_with_params(build, &qps).await?;
+    queue_item_from_response(&response)
+}
+
+/// A file parameter, uploaded as a part of a `multipart/form-data` body
+#[derive(Debug)]
+struct FileParameter {
+    name: String,
+    file_name: String,
+    content: Vec<u8>,
+}
+
+/// Build a `multipart/form-data` form with the form-encoded `parameters` and the `files`
+fn multipart_form(parameters: &str, files: Vec<FileParameter>) -> Result<Form> {
+    let mut form = Form::new();
+    for (name, value) in url::form_urlencoded::parse(parameters.as_bytes()) {
+        form = form.text(name.into_owned(), value.into_owned());
+    }
+    for file in files {
+        form = form.part(
+            file.name,
+            Part::bytes(file.content)
+                .file_name(file.file_name)
+                .mime_str("application/octet-stream")?,
+        );
+    }
+    Ok(form)
+}
+
 /// Helper to build a job
 #[derive(Debug)]
 pub struct JobBuilder<'a, 'b, 'c, 'd> {
-    job_name: Name<'a>,
+    job: Path<'a>,
     jenkins_client: &'b Jenkins,
-    delay: Option<u32>,
+    delay: Option<String>,
     cause: Option<&'c str>,
     token: Option<&'d str>,
     parameters: Option<String>,
+    files: Vec<FileParameter>,
+    skip_if_queued: bool,
 }
 
 impl<'a, 'b, 'c, 'd> JobBuilder<'a, 'b, 'c, 'd> {
@@ -28,150 +94,174 @@ impl<'a, 'b, 'c, 'd> JobBuilder<'a, 'b, 'c, 'd> {
         T: Job,
     {
         let path = jenkins_client.url_to_path(job.url());
-        if let Path::Job {
-            name,
-            configuration: None,
-        } = path
-        {
-            return Ok(JobBuilder {
-                job_name: name,
-                jenkins_client,
-                delay: None,
-                cause: None,
-                token: None,
-                parameters: None,
-            });
-        } else if let Path::InFolder {
-            folder_name: _folder_name,
-            path: sub_path,
-        } = path
-        {
-            if let Path::Job {
-                name,
-                configuration: None,
-            } = sub_path.as_ref()
-            {
-                return Ok(JobBuilder {
-                    job_name: name.clone(),
-                    jenkins_client,
-                    delay: None,
-                    cause: None,
-                    token: None,
-                    parameters: None,
-                });
+        if job_endpoint(path.clone(), |name| Path::BuildJob { name }).is_none() {
+            return Err(client::Error::InvalidUrl {
+                url: job.url().to_string(),
+                expected: client::error::ExpectedType::Job,
             }
+            .into());
         }
-        Err(client::Error::InvalidUrl {
-            url: job.url().to_string(),
-            expected: client::error::ExpectedType::Job,
-        }
-        .into())
+        Ok(Self::for_job(path, jenkins_client))
     }
 
     pub(crate) fn new_from_job_name<J>(name: J, jenkins_client: &'b Jenkins) -> Result<Self>
     where
         J: Into<JobName<'a>>,
     {
-        Ok(JobBuilder {
-            job_name: Name::Name(name.into().0),
+        Ok(Self::for_job(name.into().to_path()?, jenkins_client))
+    }
+
+    pub(crate) fn for_job(job: Path<'a>, jenkins_client: &'b Jenkins) -> Self {
+        JobBuilder {
+            job,
             jenkins_client,
             delay: None,
             cause: None,
             token: None,
             parameters: None,
-        })
+            files: Vec::new(),
+            skip_if_queued: false,
+        }
+    }
+
+    /// Path of the endpoint built by `endpoint` from the name of the job, in its folders
+    fn job_endpoint<F>(&self, endpoint: F) -> Path<'a>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        job_endpoint(self.job.clone(), endpoint).expect("path of the job checked when created")
+    }
+
+    /// Find an item of the queue waiting to build the same job with the same parameters
+    async fn find_queued_duplicate(&self) -> Result<Option<ShortQueueItem>> {
+        let job_path = self.job.to_string();
+        let parameters: BTreeMap<String, String> = self
+            .parameters
+            .as_deref()
+            .map(|parameters| {
+                url::form_urlencoded::parse(parameters.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let queue = self.jenkins_client.get_queue().await?;
+        Ok(queue
+            .items
+            .iter()
+            .find(|item| {
+                item.task.as_job().is_some_and(|job| {
+                    self.jenkins_client.url_to_path(&job.url).to_string() == job_path && {
+                        let queued = item.parameters();
+                        parameters
+                            .iter()
+                            .all(|(name, value)| queued.get(name) == Some(value))
+                    }
+                })
+            })
+            .map(|item| ShortQueueItem {
+                url: self
+                    .jenkins_client
+                    .url(&format!("/{}", item.url.trim_start_matches('/'))),
+                extra_fields: None,
+            }))
     }
 
     /// Trigger the build
     pub async fn send(self) -> Result<ShortQueueItem> {
+        if self.skip_if_queued {
+            if !self.files.is_empty() {
+                warn!("builds with file parameters are triggered even if already queued");
+            } else if let Some(queued) = self.find_queued_duplicate().await? {
+                return Ok(queued);
+            }
+        }
+        let build = self.job_endpoint(|name| Path::BuildJob { name });
+        let build_with_parameters = self.job_endpoint(|name| Path::BuildJobWithParameters { name });
+        if !self.files.is_empty() {
+            let mut qps: Vec<(&str, &str)> = Vec::new();
+            if let Some(token) = self.token {
+                qps.push(("token", token));
+            }
+            if let Some(ref delay) = self.delay {
+                qps.push(("delay", delay));
+            }
+            let form = multipart_form(self.parameters.as_deref().unwrap_or_default(), self.files)?;
+            let response = self
+                .jenkins_client
+                .post_with_multipart_form(&build_with_parameters, form, &qps)
+                .await?;
+            return queue_item_from_response(&response);
+        }
         let response = match (self.token, self.parameters) {
             (Some(token), None) => {
                 let bound_cause = self.cause.unwrap_or("");
-                let bound_delay = format!("{}", self.delay.unwrap_or(0));
+                let bound_delay = self.delay.as_deref().unwrap_or_default();
                 let mut qps: Vec<(&str, &str)> = Vec::new();
                 qps.push(("token", token));
                 if self.cause.is_some() {
                     qps.push(("cause", bound_cause));
                 }
                 if self.delay.is_some() {
-                    qps.push(("delay", &bound_delay));
+                    qps.push(("delay", bound_delay));
                 }
 
                 self.jenkins_client
-                    .get_with_params(
-                        &Path::BuildJob {
-                            name: self.job_name,
-                        },
-                        &qps,
-                    )
+                    .get_endpoint_with_params(&build, &qps)
                     .await?
             }
             (Some(token), Some(parameters)) => {
-                let bound_delay = format!("{}", self.delay.unwrap_or(0));
-                let mut qps: Vec<(&str, &str)> = Vec::new();
+                let bound_delay = self.delay.as_deref().unwrap_or_default();
+                let mut qps: Vec<(&str, &str)> = vec![("token", token)];
                 if self.delay.is_some() {
-                    qps.push(("delay", &bound_delay));
+                    qps.push(("delay", bound_delay));
                 }
                 self.jenkins_client
-                    .post_with_body(
-                        &Path::BuildJobWithParameters {
-                            name: self.job_name,
-                        },
-                        format!("token={}&{}", token, parameters),
-                        &qps,
-                    )
+                    .post_with_body(&build_with_parameters, parameters, &qps)
                     .await?
             }
             (None, None) => {
-                let bound_delay = format!("{}", self.delay.unwrap_or(0));
+                let bound_delay = self.delay.as_deref().unwrap_or_default();
                 let mut qps: Vec<(&str, &str)> = Vec::new();
                 if self.delay.is_some() {
-                    qps.push(("delay", &bound_delay));
+                    qps.push(("delay", bound_delay));
                 }
-                self.jenkins_client
-                    .post_with_body(
-                        &Path::BuildJob {
-                            name: self.job_name,
-                        },
-                        "",
-                        &qps,
-                    )
-                    .await?
+                self.jenkins_client.post_with_body(&build, "", &qps).await?
             }
             (None, Some(parameters)) => {
-                let bound_delay = format!("{}", self.delay.unwrap_or(0));
+                let bound_delay = self.delay.as_deref().unwrap_or_default();
                 let mut qps: Vec<(&str, &str)> = Vec::new();
                 if self.delay.is_some() {
-                    qps.push(("delay", &bound_delay));
+                    qps.push(("delay", bound_delay));
                 }
                 self.jenkins_client
-                    .post_with_body(
-                        &Path::BuildJobWithParameters {
-                            name: self.job_name,
-                        },
-                        parameters,
-                        &qps,
-                    )
+                    .post_with_body(&build_with_parameters, parameters, &qps)
                     .await?
             }
         };
-        if let Some(location) = response.headers().get(LOCATION) {
-            Ok(ShortQueueItem {
-                url: location.to_str().unwrap().to_string(),
-                extra_fields: None,
-            })
-        } else {
-            Err(client::Error::InvalidUrl {
-                url: "".to_string(),
-                expected: client::error::ExpectedType::QueueItem,
-            }
-            .into())
-        }
+        queue_item_from_response(&response)
     }
 
     /// Add a delay before the job will be built
     pub fn with_delay(mut self, delay_sec: u32) -> Self {
-        self.delay = Some(delay_sec);
+        self.delay = Some(delay_sec.to_string());
+        self
+    }
+
+    /// Wait `quiet_period` before the job will be built, instead of the quiet period of the job.
+    /// It is sent as `delay=<seconds>sec`, so it is rounded down to the second
+    pub fn with_quiet_period(mut self, quiet_period: Duration) -> Self {
+        self.delay = Some(format!("{}sec", quiet_period.as_secs()));
+        self
+    }
+
+    /// Don't trigger the build if the queue already has an item for this job with the same
+    /// values for the parameters set on this builder, and return that item instead
+    ///
+    /// Parameters are compared with the values shown in the queue, so builds with passwords are
+    /// always triggered. Builds with file parameters are always triggered, as their content is
+    /// not shown in the queue.
+    pub fn skip_if_queued(mut self) -> Self {
+        self.skip_if_queued = true;
         self
     }
 
@@ -186,11 +276,14 @@ impl<'a, 'b, 'c, 'd> JobBuilder<'a, 'b, 'c, 'd> {
         Ok(self)
     }
 
-    /// Build with parameters
+    /// Build with parameters, after the parameters already set
     ///
     /// Supported parameters type: Boolean, Choice, Multi-line string, Password, Run, String
     ///
-    /// Unsupported parameters type: File, Credentials
+    /// Unsupported parameters type: File, Credentials. File parameters can be sent with
+    /// [`with_file_param`](#method.with_file_param)
+    ///
+    /// [`BuildParameters`](struct.BuildParameters.html) can be used to build typed parameters.
     /// # Errors
     /// If used on a `Job` without parameters, sending this build will return an
     /// [`Error::IllegalState`](../enum.Error.html#variant.IllegalState)
@@ -203,7 +296,243 @@ impl<'a, 'b, 'c, 'd> JobBuilder<'a, 'b, 'c, 'd> {
         if self.token.is_some() {
             return Err(client::Error::UnsupportedBuildConfiguration.into());
         }
-        self.parameters = Some(serde_urlencoded::to_string(parameters)?);
+        let parameters = serde_urlencoded::to_string(parameters)?;
+        self.parameters = Some(match self.parameters.take() {
+            Some(previous) if !previous.is_empty() && !parameters.is_empty() => {
+                format!("{}&{}", previous, parameters)
+            }
+            Some(previous) if parameters.is_empty() => previous,
+            _ => parameters,
+        });
         Ok(self)
     }
+
+    /// Add the parameter `name` with `value`, after the parameters already set
+    fn with_param(mut self, name: &str, value: ParameterValue) -> Self {
+        let mut serializer = url::form_urlencoded::Serializer::for_suffix(
+            self.parameters.take().unwrap_or_default(),
+            0,
+        );
+        let _ = serializer.append_pair(name, value.as_form_value());
+        self.parameters = Some(serializer.finish());
+        self
+    }
+
+    /// Build with the string parameter `name`, also used for multi-line string and run
+    /// parameters
+    pub fn with_string_param(self, name: &str, value: &str) -> Self {
+        self.with_param(name, ParameterValue::String(value.to_string()))
+    }
+
+    /// Build with the boolean parameter `name`
+    pub fn with_bool_param(self, name: &str, value: bool) -> Self {
+        self.with_param(name, ParameterValue::Boolean(value))
+    }
+
+    /// Build with the choice parameter `name`, `choice` must be one of its choices
+    pub fn with_choice_param(self, name: &str, choice: &str) -> Self {
+        self.with_param(name, ParameterValue::String(choice.to_string()))
+    }
+
+    /// Build with the password parameter `name`
+    pub fn with_password_param(self, name: &str, password: &str) -> Self {
+        self.with_param(name, ParameterValue::String(password.to_string()))
+    }
+
+    /// Build with the file parameter `name`, uploading `content` as `file_name`. The build is
+    /// then sent as a `multipart/form-data` body
+    pub fn with_file_param<C>(mut self, name: &str, file_name: &str, content: C) -> Self
+    where
+        C: Into<Vec<u8>>,
+    {
+        self.files.push(FileParameter {
+            name: name.to_string(),
+            file_name: file_name.to_string(),
+            content: content.into(),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_skip_build_already_queued() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let queue = server
+            .mock("GET", "/queue/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "items": [{
+                        "blocked": false,
+                        "buildable": true,
+                        "id": 12,
+                        "inQueueSince": 100,
+                        "params": "\nBRANCH='main'\nDEBUG='false'",
+                        "stuck": false,
+                        "task": {"name": "my job", "url": format!("{}/job/my%20job/", server.url())},
+                        "url": "queue/item/12/",
+                        "actions": []
+                    }, {
+                        "blocked": false,
+                        "buildable": true,
+                        "id": 11,
+                        "inQueueSince": 90,
+                        "params": "\nBRANCH='release'",
+                        "stuck": false,
+                        "task": {"name": "my job", "url": format!("{}/job/team/job/my%20job/", server.url())},
+                        "url": "queue/item/11/",
+                        "actions": []
+                    }]
+                })
+                .to_string(),
+            )
+            .expect(2)
+            .create();
+        let build = server
+            .mock("POST", "/job/my%20job/buildWithParameters")
+            .match_body("BRANCH=release")
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/13/", server.url()))
+            .expect(1)
+            .create();
+
+        let queued = jenkins_client
+            .job_builder("my job")
+            .unwrap()
+            .with_parameters(&[("BRANCH", "main")])
+            .unwrap()
+            .skip_if_queued()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(queued.url, format!("{}/queue/item/12/", server.url()));
+
+        let triggered = jenkins_client
+            .job_builder("my job")
+            .unwrap()
+            .with_parameters(&[("BRANCH", "release")])
+            .unwrap()
+            .skip_if_queued()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(triggered.url, format!("{}/queue/item/13/", server.url()));
+
+        queue.assert();
+        build.assert();
+    }
+
+    #[tokio::test]
+    async fn can_build_with_quiet_period() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let build = server
+            .mock("POST", "/job/nightly/build")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "delay".to_string(),
+                "300sec".to_string(),
+            ))
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/14/", server.url()))
+            .create();
+
+        let queued = jenkins_client
+            .job_builder("nightly")
+            .unwrap()
+            .with_quiet_period(std::time::Duration::from_secs(300))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(queued.url, format!("{}/queue/item/14/", server.url()));
+        build.assert();
+    }
+
+    #[tokio::test]
+    async fn can_build_with_typed_parameters() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let form = server
+            .mock("POST", "/job/deploy/buildWithParameters")
+            .match_header("content-type", "application/x-www-form-urlencoded")
+            .match_body("BRANCH=main&DEPLOY=true&ENV=prod&TOKEN=s3cr%26t")
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/15/", server.url()))
+            .create();
+        let multipart = server
+            .mock("POST", "/job/deploy/buildWithParameters")
+            .match_header(
+                "content-type",
+                mockito::Matcher::Regex("^multipart/form-data; boundary=".to_string()),
+            )
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::Regex("name=\"BRANCH\"\r\n\r\nmain\r\n".to_string()),
+                mockito::Matcher::Regex(
+                    "name=\"MANIFEST\"; filename=\"app \\\\\"prod\\\\\".yaml\"\r\n.*\r\n\r\nreplicas: 2\r\n"
+                        .to_string(),
+                ),
+            ]))
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/16/", server.url()))
+            .create();
+
+        let queued = jenkins_client
+            .job_builder("deploy")
+            .unwrap()
+            .with_string_param("BRANCH", "main")
+            .with_bool_param("DEPLOY", true)
+            .with_parameters(&[("ENV", "prod")])
+            .unwrap()
+            .with_password_param("TOKEN", "s3cr&t")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(queued.url, format!("{}/queue/item/15/", server.url()));
+
+        let queued = jenkins_client
+            .job_builder("deploy")
+            .unwrap()
+            .with_string_param("BRANCH", "main")
+            .with_file_param("MANIFEST", "app \"prod\".yaml", "replicas: 2")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(queued.url, format!("{}/queue/item/16/", server.url()));
+
+        form.assert();
+        multipart.assert();
+    }
+
+    #[test]
+    fn can_read_quiet_period() {
+        use crate::job::common::quiet_period_from_config_xml;
+
+        assert_eq!(
+            quiet_period_from_config_xml(
+                "<project>\n  <scm class=\"hudson.scm.NullSCM\"><quietPeriod>1</quietPeriod></scm>\n  <quietPeriod>120</quietPeriod>\n</project>"
+            )
+            .unwrap(),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(
+            quiet_period_from_config_xml("<project>\n  <disabled>false</disabled>\n</project>")
+                .unwrap(),
+            None
+        );
+    }
 }
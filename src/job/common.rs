@@ -1,14 +1,22 @@
 use std::marker::PhantomData;
+use std::time::Duration;
 
+use quick_xml::events::Event;
+use quick_xml::Reader;
 use serde::{self, Deserialize, Serialize};
 
-use crate::helpers::Class;
+use crate::helpers::{Class, JenkinsClass};
 
-use super::JobBuilder;
+use super::{CoveragePoint, FolderPath, JobBuilder, TestResultsMatrix};
 use crate::action::CommonAction;
-use crate::build::{CommonBuild, ShortBuild};
-use crate::client::{self, Result};
-use crate::client_internals::{Name, Path};
+use crate::build::{BuildRange, CommonBuild, ShortBuild};
+use crate::client::{self, Cursor, Page, Result, TreeBuilder};
+use crate::client_internals::{
+    AdvancedQuery, InternalAdvancedQueryParams, Name, Path, ResponseExt,
+};
+use crate::matrix_auth::MatrixAuthorization;
+use crate::notification::{EmailExtConfig, SlackNotifierConfig};
+use crate::property::CommonProperty;
 use crate::queue::ShortQueueItem;
 use crate::view::ViewName;
 use crate::Jenkins;
@@ -55,7 +63,8 @@ pub enum BallColor {
 pub struct HealthReport {
     /// Description of the `HealthReport`
     pub description: String,
-    /// Icon name
+    /// Icon name, not provided by older versions of Jenkins
+    #[serde(default)]
     pub icon_class_name: String,
     /// Icon url
     pub icon_url: String,
@@ -90,15 +99,24 @@ where
     T: Job,
     for<'de> T: Deserialize<'de>,
 {
+    /// `_class` of the job, when it was requested, to check its kind without getting the job
+    pub fn jenkins_class(&self) -> Option<JenkinsClass> {
+        self.extra_fields
+            .as_ref()?
+            .get("_class")?
+            .as_str()
+            .map(JenkinsClass::from)
+    }
+
     /// Get the full details of a `Job` matching the `ShortJob`
     pub async fn get_full_job(&self, jenkins_client: &Jenkins) -> Result<T> {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::Job { .. } = path {
-            let response = jenkins_client.get(&path).await?.json().await?;
+            let response = jenkins_client.get(&path).await?.parse_json().await?;
             return Ok(response);
         } else if let Path::InFolder { path: sub_path, .. } = &path {
             if let Path::Job { .. } = sub_path.as_ref() {
-                let response = jenkins_client.get(&path).await?.json().await?;
+                let response = jenkins_client.get(&path).await?.parse_json().await?;
                 return Ok(response);
             }
         }
@@ -110,9 +128,39 @@ where
     }
 }
 
-/// Helper type to act on a job
+/// Helper type to act on a job, from its name or its full name in nested folders, like
+/// `team/services/api`
 #[derive(Debug)]
 pub struct JobName<'a>(pub &'a str);
+
+impl<'a> JobName<'a> {
+    /// Path to the job, as `Path::InFolder` wrapping each folder around the job
+    pub(crate) fn to_path(&self) -> Result<Path<'a>> {
+        self.to_path_with(|name| Path::Job {
+            name,
+            configuration: None,
+        })
+    }
+
+    /// Path to the endpoint built by `endpoint` from the name of the job, in its folders
+    pub(crate) fn to_path_with<F>(&self, endpoint: F) -> Result<Path<'a>>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        let mut names = self.0.split('/').filter(|name| !name.is_empty()).rev();
+        let job = names.next().ok_or_else(|| client::Error::InvalidUrl {
+            url: self.0.to_string(),
+            expected: client::error::ExpectedType::Job,
+        })?;
+        Ok(
+            names.fold(endpoint(Name::Name(job)), |path, folder| Path::InFolder {
+                folder_name: Name::Name(folder),
+                path: Box::new(path),
+            }),
+        )
+    }
+}
+
 impl<'a> From<&'a str> for JobName<'a> {
     fn from(v: &'a str) -> JobName<'a> {
         JobName(v)
@@ -123,8 +171,13 @@ impl<'a> From<&'a String> for JobName<'a> {
         JobName(v)
     }
 }
-impl<'a> From<&'a ShortJob> for JobName<'a> {
-    fn from(v: &'a ShortJob) -> JobName<'a> {
+impl<'a> From<&'a FolderPath> for JobName<'a> {
+    fn from(v: &'a FolderPath) -> JobName<'a> {
+        JobName(&v.full_name)
+    }
+}
+impl<'a, T: Job> From<&'a ShortJob<T>> for JobName<'a> {
+    fn from(v: &'a ShortJob<T>) -> JobName<'a> {
         JobName(&v.name)
     }
 }
@@ -144,46 +197,66 @@ pub trait Job {
     /// Enable a `Job`. It may need to be refreshed as it may have been updated
     fn enable(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
         async move {
-            let path = jenkins_client.url_to_path(self.url());
-            if let Path::Job {
-                name,
-                configuration: None,
-            } = path
-            {
-                let _ = jenkins_client.post(&Path::JobEnable { name }).await?;
-                Ok(())
-            } else {
-                Err(client::Error::InvalidUrl {
-                    url: self.url().to_string(),
-                    expected: client::error::ExpectedType::Job,
-                }
-                .into())
-            }
+            let path =
+                job_endpoint_path(jenkins_client, self.url(), |name| Path::JobEnable { name })?;
+            let _ = jenkins_client.post(&path).await?;
+            Ok(())
         }
     }
 
     /// Disable a `Job`. It may need to be refreshed as it may have been updated
     fn disable(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
         async move {
-            let path = jenkins_client.url_to_path(self.url());
-            if let Path::Job {
-                name,
-                configuration: None,
-            } = path
-            {
-                let _ = jenkins_client.post(&Path::JobDisable { name }).await?;
-                Ok(())
-            } else {
-                Err(client::Error::InvalidUrl {
-                    url: self.url().to_string(),
-                    expected: client::error::ExpectedType::Job,
-                }
-                .into())
-            }
+            let path =
+                job_endpoint_path(jenkins_client, self.url(), |name| Path::JobDisable { name })?;
+            let _ = jenkins_client.post(&path).await?;
+            Ok(())
         }
     }
 
-    /// Add this job to the view `view_name`
+    /// Shelve a `Job` with the Shelve Project plugin: it is archived then deleted, and can be
+    /// restored with `Jenkins::unshelve_project`
+    fn shelve(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
+        async move {
+            let path =
+                job_endpoint_path(jenkins_client, self.url(), |name| Path::JobShelve { name })?;
+            let _ = jenkins_client.post(&path).await?;
+            Ok(())
+        }
+    }
+
+    /// Run the build discarder of this job now, instead of waiting for its next build, so that
+    /// lowered retention settings take effect immediately
+    fn run_log_rotation(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<()>> {
+        async move {
+            let path = job_path(jenkins_client, self.url())?;
+            let _ = jenkins_client
+                .post(&Path::JobLogRotate {
+                    job: Box::new(path),
+                })
+                .await?;
+            Ok(())
+        }
+    }
+
+    /// Trigger a build of this job with its remote trigger `token`, without a CSRF crumb
+    fn trigger_remotely<'a>(
+        &'a self,
+        jenkins_client: &'a Jenkins,
+        token: &'a str,
+        cause: Option<&'a str>,
+    ) -> impl std::future::Future<Output = Result<ShortQueueItem>> + 'a {
+        async move {
+            let path =
+                job_endpoint_path(jenkins_client, self.url(), |name| Path::BuildJob { name })?;
+            super::builder::trigger_remotely(jenkins_client, &path, token, cause).await
+        }
+    }
+
+    /// Add this job to the view `view_name`. Jobs in folders are added with their full name
     fn add_to_view<'a, V>(
         &self,
         jenkins_client: &Jenkins,
@@ -193,30 +266,15 @@ pub trait Job {
         V: Into<ViewName<'a>>,
     {
         async move {
-            let path = jenkins_client.url_to_path(self.url());
-            if let Path::Job {
-                name,
-                configuration: None,
-            } = path
-            {
-                let _ = jenkins_client
-                    .post(&Path::AddJobToView {
-                        job_name: name,
-                        view_name: Name::Name(view_name.into().0),
-                    })
-                    .await?;
-                Ok(())
-            } else {
-                Err(client::Error::InvalidUrl {
-                    url: self.url().to_string(),
-                    expected: client::error::ExpectedType::Job,
-                }
-                .into())
-            }
+            let full_name = job_full_name(&job_path(jenkins_client, self.url())?);
+            jenkins_client
+                .add_job_to_view(view_name, full_name.as_str())
+                .await
         }
     }
 
-    /// Remove this job from the view `view_name`
+    /// Remove this job from the view `view_name`. Jobs in folders are removed with their full
+    /// name
     fn remove_from_view<'a, V>(
         &self,
         jenkins_client: &Jenkins,
@@ -226,26 +284,10 @@ pub trait Job {
         V: Into<ViewName<'a>>,
     {
         async move {
-            let path = jenkins_client.url_to_path(self.url());
-            if let Path::Job {
-                name,
-                configuration: None,
-            } = path
-            {
-                let _ = jenkins_client
-                    .post(&Path::RemoveJobFromView {
-                        job_name: name,
-                        view_name: Name::Name(view_name.into().0),
-                    })
-                    .await?;
-                Ok(())
-            } else {
-                Err(client::Error::InvalidUrl {
-                    url: self.url().to_string(),
-                    expected: client::error::ExpectedType::Job,
-                }
-                .into())
-            }
+            let full_name = job_full_name(&job_path(jenkins_client, self.url())?);
+            jenkins_client
+                .remove_job_from_view(view_name, full_name.as_str())
+                .await
         }
     }
 
@@ -258,7 +300,7 @@ pub trait Job {
             let path = jenkins_client.url_to_path(self.url());
             if let Path::Job { name, .. } = path {
                 let response = jenkins_client
-                    .get(&Path::ConfigXML {
+                    .get_endpoint(&Path::ConfigXML {
                         job_name: name,
                         folder_name: None,
                     })
@@ -273,7 +315,7 @@ pub trait Job {
             {
                 if let Path::Job { name, .. } = sub_path.as_ref() {
                     let response = jenkins_client
-                        .get(&Path::ConfigXML {
+                        .get_endpoint(&Path::ConfigXML {
                             job_name: name.clone(),
                             folder_name: Some(folder_name.clone()),
                         })
@@ -291,6 +333,179 @@ pub trait Job {
             .into())
         }
     }
+
+    /// Get the matrix of permissions of this job or folder, if it has one
+    fn get_matrix_authorization(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Option<MatrixAuthorization>>> {
+        async move { MatrixAuthorization::from_config_xml(&self.get_config_xml(jenkins_client).await?) }
+    }
+
+    /// Get the configuration of the Email Extension publisher of this job, if it has one
+    fn get_email_ext_config(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Option<EmailExtConfig>>> {
+        async move { EmailExtConfig::from_config_xml(&self.get_config_xml(jenkins_client).await?) }
+    }
+
+    /// Get the configuration of the Slack notifier of this job, if it has one
+    fn get_slack_notifier_config(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Option<SlackNotifierConfig>>> {
+        async move { SlackNotifierConfig::from_config_xml(&self.get_config_xml(jenkins_client).await?) }
+    }
+
+    /// Get a page of `count` builds of this job, skipping the `start` most recent ones. Unlike
+    /// `builds`, which is limited to the 100 most recent builds, this can reach all the builds
+    fn get_builds_page(
+        &self,
+        jenkins_client: &Jenkins,
+        start: u32,
+        count: u32,
+    ) -> impl std::future::Future<Output = Result<Vec<ShortBuild>>> {
+        async move {
+            Ok(self
+                .get_builds_at(jenkins_client, Cursor::at(start, count))
+                .await?
+                .items)
+        }
+    }
+
+    /// Get the page of builds of this job at `cursor`, from the most recent
+    fn get_builds_at(
+        &self,
+        jenkins_client: &Jenkins,
+        cursor: Cursor,
+    ) -> impl std::future::Future<Output = Result<Page<ShortBuild>>> {
+        async move {
+            #[derive(Deserialize)]
+            #[serde(rename_all = "camelCase")]
+            struct AllBuilds {
+                all_builds: Vec<ShortBuild>,
+            }
+
+            let path = job_path(jenkins_client, self.url())?;
+            let page: AllBuilds = jenkins_client
+                .get_with_params(
+                    &path,
+                    InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                        TreeBuilder::new()
+                            .with_field(
+                                TreeBuilder::object("allBuilds")
+                                    .with_subfield("number")
+                                    .with_subfield("url")
+                                    .with_subfield("displayName")
+                                    .with_subfield("timestamp")
+                                    .with_cursor(cursor),
+                            )
+                            .build(),
+                    )),
+                )
+                .await?
+                .parse_json()
+                .await?;
+            Ok(Page::new(page.all_builds, cursor))
+        }
+    }
+
+    /// Get the coverage of the `count` most recent builds of this job from the Coverage plugin,
+    /// from the oldest. Builds without coverage are skipped
+    fn get_coverage_trend(
+        &self,
+        jenkins_client: &Jenkins,
+        count: u32,
+    ) -> impl std::future::Future<Output = Result<Vec<CoveragePoint>>> {
+        super::coverage::get_coverage_trend(jenkins_client, self, count)
+    }
+
+    /// Get the results of the tests of this job in its `builds` most recent builds, with the Test
+    /// Results Analyzer plugin
+    fn get_test_results_matrix(
+        &self,
+        jenkins_client: &Jenkins,
+        builds: u32,
+    ) -> impl std::future::Future<Output = Result<TestResultsMatrix>> {
+        super::test_history::get_test_results_matrix(jenkins_client, self.url(), builds)
+    }
+
+    /// Get the builds of this job started between `from` (included) and `to` (excluded),
+    /// timestamps in milliseconds like `CommonBuild::timestamp`, from the most recent
+    ///
+    /// Builds are fetched by pages, and older pages are not fetched once a build started
+    /// before `from` is found.
+    fn get_builds_between(
+        &self,
+        jenkins_client: &Jenkins,
+        from: u64,
+        to: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<ShortBuild>>> {
+        async move {
+            let mut builds = vec![];
+            let mut cursor = Some(Cursor::first(100));
+            while let Some(current) = cursor {
+                let page = self.get_builds_at(jenkins_client, current).await?;
+                cursor = page.next_page();
+                for build in page {
+                    match build.timestamp {
+                        Some(timestamp) if timestamp < from => return Ok(builds),
+                        Some(timestamp) if timestamp < to => builds.push(build),
+                        _ => (),
+                    }
+                }
+            }
+            Ok(builds)
+        }
+    }
+
+    /// Delete the builds of this job in `range`, like `"1-3,5".parse()?`, returning the numbers
+    /// of the builds deleted
+    ///
+    /// The builds in the range are all listed before deleting the first one, so that the
+    /// deletions don't shift the pages of builds.
+    ///
+    /// # Errors
+    /// If a build can't be deleted, like a build kept forever, the deletion stops with an
+    /// [`Error::BuildDeletionFailed`](../client/enum.Error.html#variant.BuildDeletionFailed)
+    /// listing the builds already deleted
+    fn delete_builds_in_range<'a>(
+        &'a self,
+        jenkins_client: &'a Jenkins,
+        range: &'a BuildRange,
+    ) -> impl std::future::Future<Output = Result<Vec<u32>>> + 'a {
+        async move {
+            let mut builds = vec![];
+            let mut cursor = Some(Cursor::first(100));
+            'pages: while let Some(current) = cursor {
+                let page = self.get_builds_at(jenkins_client, current).await?;
+                cursor = page.next_page();
+                for build in page {
+                    if build.number < range.min() {
+                        break 'pages;
+                    }
+                    if range.contains(build.number) {
+                        builds.push(build);
+                    }
+                }
+            }
+
+            let mut deleted = vec![];
+            for build in builds {
+                if let Err(source) = build.delete(jenkins_client).await {
+                    return Err(client::Error::BuildDeletionFailed {
+                        build: build.number,
+                        deleted,
+                        source,
+                    }
+                    .into());
+                }
+                deleted.push(build.number);
+            }
+            Ok(deleted)
+        }
+    }
 }
 
 macro_rules! job_base_with_common_fields_and_impl {
@@ -363,6 +578,12 @@ macro_rules! job_base_with_common_fields_and_impl {
             /// Link to the last build
             #[serde(default)]
             pub last_build: Option<ShortBuild<$build_type>>,
+            /// HealthReport of the job
+            #[serde(default)]
+            pub health_report: Vec<HealthReport>,
+            /// Properties of the job
+            #[serde(default)]
+            pub property: Vec<CommonProperty>,
             $(
                 $(#[$field_attr])*
                 pub $field: $field_type,
@@ -381,6 +602,16 @@ macro_rules! job_base_with_common_fields_and_impl {
                 &self.name
             }
         }
+
+        impl $name {
+            /// Disk space used by this job, computed by the Disk Usage plugin
+            pub fn disk_usage(&self) -> Option<crate::action::ProjectDiskUsageAction> {
+                self.actions
+                    .iter()
+                    .flatten()
+                    .find_map(|action| action.as_variant().ok())
+            }
+        }
     };
 }
 
@@ -459,23 +690,18 @@ macro_rules! job_buildable_with_common_fields_and_impl {
                 pub last_failed_build: Option<ShortBuild<$build_type>>,
                 /// List of builds of the job
                 pub builds: Vec<ShortBuild>,
-                /// HealthReport of the job
-                pub health_report: Vec<HealthReport>,
                 /// Queue item of this job if it's waiting
                 pub queue_item: Option<ShortQueueItem>,
                 $(
                     $(#[$field_attr])*
                     pub $field: $field_type,
                 )*
-                $($(
-                    $(#[$private_field_attr])*
-                    $private_field: $private_field_type,
-                )*)*
-                private_fields {
-                    /// Properties of the job
-                    #[allow(dead_code)]
-                    property: Vec<CommonProperty>,
-                }
+                $(private_fields {
+                    $(
+                        $(#[$private_field_attr])*
+                        $private_field: $private_field_type
+                    ),*
+                })*
             }
         }
     };
@@ -489,6 +715,39 @@ job_base_with_common_fields_and_impl!(
         /// _class provided by Jenkins
         #[serde(rename = "_class")]
         pub class: Option<String>,
+        /// Description of the job
+        #[serde(default)]
+        pub description: Option<String>,
+        /// Ball Color for the status of the job
+        #[serde(default)]
+        pub color: Option<BallColor>,
+        /// Is this job currently in build queue
+        #[serde(default)]
+        pub in_queue: Option<bool>,
+        /// Next build number
+        #[serde(default)]
+        pub next_build_number: Option<u32>,
+        /// List of builds of the job
+        #[serde(default)]
+        pub builds: Vec<ShortBuild>,
+        /// Link to the first build
+        #[serde(default)]
+        pub first_build: Option<ShortBuild>,
+        /// Link to the last successful build
+        #[serde(default)]
+        pub last_successful_build: Option<ShortBuild>,
+        /// Link to the last failed build
+        #[serde(default)]
+        pub last_failed_build: Option<ShortBuild>,
+        /// Link to the last complete build
+        #[serde(default)]
+        pub last_completed_build: Option<ShortBuild>,
+        /// Queue item of this job if it's waiting
+        #[serde(default)]
+        pub queue_item: Option<ShortQueueItem>,
+        /// Jobs in this job, if it is a folder
+        #[serde(default)]
+        pub jobs: Vec<ShortJob>,
 
         #[cfg(feature = "extra-fields-visibility")]
         /// Extra fields not parsed for a common object
@@ -503,7 +762,12 @@ job_base_with_common_fields_and_impl!(
 );
 specialize!(CommonJob => Job);
 
-impl CommonJob {}
+impl CommonJob {
+    /// `_class` of the job, to check its kind without parsing it as a specialization
+    pub fn jenkins_class(&self) -> Option<JenkinsClass> {
+        self.class.as_deref().map(JenkinsClass::from)
+    }
+}
 
 /// Common trait for jobs that can be build
 pub trait BuildableJob: Job + Sized {
@@ -522,6 +786,112 @@ pub trait BuildableJob: Job + Sized {
     ) -> Result<JobBuilder<'a, 'b, 'c, 'd>> {
         JobBuilder::new(self, jenkins_client)
     }
+
+    /// Get the quiet period configured on this job, or `None` if it uses the quiet period of
+    /// Jenkins
+    fn get_quiet_period(
+        &self,
+        jenkins_client: &Jenkins,
+    ) -> impl std::future::Future<Output = Result<Option<Duration>>> {
+        async move { quiet_period_from_config_xml(&self.get_config_xml(jenkins_client).await?) }
+    }
+}
+
+/// Get the path of the job at `url`, checking that it is a job
+pub(crate) fn job_path<'a>(jenkins_client: &Jenkins, url: &'a str) -> Result<Path<'a>> {
+    let path = jenkins_client.url_to_path(url);
+    let is_job = match &path {
+        Path::Job { .. } => true,
+        Path::InFolder { path: sub_path, .. } => matches!(sub_path.as_ref(), Path::Job { .. }),
+        _ => false,
+    };
+    if is_job {
+        Ok(path)
+    } else {
+        Err(client::Error::InvalidUrl {
+            url: url.to_string(),
+            expected: client::error::ExpectedType::Job,
+        }
+        .into())
+    }
+}
+
+/// Map the path of a job to the path of the endpoint built by `endpoint` from its name, keeping
+/// the folders of the job. Returns `None` if `path` is not the path of a job
+pub(crate) fn job_endpoint<'a, F>(path: Path<'a>, endpoint: F) -> Option<Path<'a>>
+where
+    F: FnOnce(Name<'a>) -> Path<'a>,
+{
+    match path {
+        Path::Job {
+            name,
+            configuration: None,
+        } => Some(endpoint(name)),
+        Path::InFolder { folder_name, path } => Some(Path::InFolder {
+            folder_name,
+            path: Box::new(job_endpoint(*path, endpoint)?),
+        }),
+        _ => None,
+    }
+}
+
+/// Get the path of the endpoint built by `endpoint` from the name of the job at `url`, keeping
+/// the folders of the job
+pub(crate) fn job_endpoint_path<'a, F>(
+    jenkins_client: &Jenkins,
+    url: &'a str,
+    endpoint: F,
+) -> Result<Path<'a>>
+where
+    F: FnOnce(Name<'a>) -> Path<'a>,
+{
+    job_endpoint(jenkins_client.url_to_path(url), endpoint).ok_or_else(|| {
+        client::Error::InvalidUrl {
+            url: url.to_string(),
+            expected: client::error::ExpectedType::Job,
+        }
+        .into()
+    })
+}
+
+/// Get the full name of the job at `path`, like `folder/job`
+pub(crate) fn job_full_name(path: &Path<'_>) -> String {
+    path.to_string()
+        .trim_start_matches("/job/")
+        .split("/job/")
+        .map(|name| {
+            urlencoding::decode(name)
+                .map(|name| name.into_owned())
+                .unwrap_or_else(|_| name.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Read the `<quietPeriod>` of a job from its `config.xml`, in seconds
+pub(crate) fn quiet_period_from_config_xml(config_xml: &str) -> Result<Option<Duration>> {
+    let mut reader = Reader::from_str(config_xml);
+    reader.config_mut().trim_text(true);
+
+    let mut depth = 0;
+    let mut in_quiet_period = false;
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                depth += 1;
+                in_quiet_period = depth == 2 && element.name().as_ref() == b"quietPeriod";
+            }
+            Event::Text(text) if in_quiet_period => {
+                return Ok(Some(Duration::from_secs(text.unescape()?.trim().parse()?)));
+            }
+            Event::End(_) => {
+                depth -= 1;
+                in_quiet_period = false;
+            }
+            Event::Eof => return Ok(None),
+            _ => (),
+        }
+    }
 }
 
 /// Common trait for jobs that can poll a SCM
@@ -529,21 +899,42 @@ pub trait SCMPollable: Job + Sized {
     /// Poll configured SCM for changes
     fn poll_scm(&self, jenkins_client: &Jenkins) -> impl std::future::Future<Output = Result<()>> {
         async move {
-            let path = jenkins_client.url_to_path(self.url());
-            if let Path::Job {
-                name,
-                configuration: None,
-            } = path
-            {
-                let _ = jenkins_client.post(&Path::PollSCMJob { name }).await?;
-                Ok(())
-            } else {
-                Err(client::Error::InvalidUrl {
-                    url: self.url().to_string(),
-                    expected: client::error::ExpectedType::Job,
-                }
-                .into())
-            }
+            let path =
+                job_endpoint_path(jenkins_client, self.url(), |name| Path::PollSCMJob { name })?;
+            let _ = jenkins_client.post(&path).await?;
+            Ok(())
         }
     }
 }
+
+/// A job known only by its name and URL, to test the methods of the job traits
+#[cfg(test)]
+pub(crate) struct TestJob {
+    pub(crate) name: &'static str,
+    pub(crate) url: String,
+}
+
+#[cfg(test)]
+impl TestJob {
+    /// The job `name` at `path` on the server at `server_url`, like `/job/folder/job/name/`
+    pub(crate) fn new(name: &'static str, server_url: &str, path: &str) -> Self {
+        TestJob {
+            name,
+            url: format!("{}{}", server_url, path),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Job for TestJob {
+    fn url(&self) -> &str {
+        &self.url
+    }
+    fn name(&self) -> &str {
+        self.name
+    }
+}
+#[cfg(test)]
+impl BuildableJob for TestJob {}
+#[cfg(test)]
+impl SCMPollable for TestJob {}
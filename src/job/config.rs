@@ -0,0 +1,149 @@
+use crate::client::Result;
+use crate::client_internals::Path;
+use crate::job::FolderPath;
+use crate::Jenkins;
+
+impl Jenkins {
+    /// Create the job `path` from its `config_xml`, in nested folders if `path` has some
+    ///
+    /// Jenkins refuses to create a job with the name of an existing item.
+    pub async fn create_job<P>(&self, path: P, config_xml: &str) -> Result<()>
+    where
+        P: Into<FolderPath>,
+    {
+        let path = path.into();
+        let create_item = path.to_path_with(|_| Path::CreateItem)?;
+        let _ = self
+            .post_with_xml_body(
+                &create_item,
+                config_xml.to_string(),
+                &[("name", path.name().unwrap_or_default())],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Get the config.xml of the job `path`, in nested folders if `path` has some
+    pub async fn get_job_config<P>(&self, path: P) -> Result<String>
+    where
+        P: Into<FolderPath>,
+    {
+        let path = path.into();
+        Ok(self
+            .get_endpoint(&path.to_path_with(|job_name| Path::ConfigXML {
+                job_name,
+                folder_name: None,
+            })?)
+            .await?
+            .text()
+            .await?)
+    }
+
+    /// Replace the config.xml of the job `path`, in nested folders if `path` has some
+    pub async fn update_job_config<P>(&self, path: P, config_xml: &str) -> Result<()>
+    where
+        P: Into<FolderPath>,
+    {
+        let path = path.into();
+        let _ = self
+            .post_with_xml_body(
+                &path.to_path_with(|job_name| Path::ConfigXML {
+                    job_name,
+                    folder_name: None,
+                })?,
+                config_xml.to_string(),
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Delete the job `path` with all its builds, in nested folders if `path` has some
+    pub async fn delete_job<P>(&self, path: P) -> Result<()>
+    where
+        P: Into<FolderPath>,
+    {
+        let path = path.into();
+        let _ = self
+            .post(&path.to_path_with(|name| Path::JobDelete { name })?)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static CONFIG_XML: &str = "<project><description>app</description></project>";
+
+    #[tokio::test]
+    async fn can_create_job_in_folder() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server
+            .mock("POST", "/job/team/job/services/createItem")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "api v2".to_string(),
+            ))
+            .match_header("content-type", "text/xml")
+            .match_body(CONFIG_XML)
+            .create();
+
+        jenkins_client
+            .create_job("team/services/api v2", CONFIG_XML)
+            .await
+            .unwrap();
+        mock.assert();
+
+        assert!(jenkins_client.create_job("", CONFIG_XML).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn can_get_and_update_job_config() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let get = server
+            .mock("GET", "/job/team/job/app/config.xml")
+            .with_body(CONFIG_XML)
+            .create();
+        let update = server
+            .mock("POST", "/job/team/job/app/config.xml")
+            .match_header("content-type", "text/xml")
+            .match_body("<project/>")
+            .create();
+
+        let config = jenkins_client
+            .get_job_config(["team", "app"])
+            .await
+            .unwrap();
+        assert_eq!(config, CONFIG_XML);
+        jenkins_client
+            .update_job_config("team/app", "<project/>")
+            .await
+            .unwrap();
+        get.assert();
+        update.assert();
+    }
+
+    #[tokio::test]
+    async fn can_delete_job() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server.mock("POST", "/job/app/doDelete").create();
+
+        jenkins_client.delete_job("app").await.unwrap();
+        mock.assert();
+    }
+}
@@ -0,0 +1,354 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::client::Result;
+use crate::job::JobName;
+use crate::Jenkins;
+
+/// A difference between the `config.xml` of two jobs
+///
+/// Values are identified by the path of their element from the root of the document, like
+/// `/project/builders/hudson.tasks.Shell[2]/command`, with `[n]` added when an element has
+/// several siblings with the same name, and `@name` for attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigDifference {
+    /// A value is only present in the configuration of the second job
+    Added {
+        /// Path of the value
+        path: String,
+        /// Value in the second job
+        value: String,
+    },
+    /// A value is only present in the configuration of the first job
+    Removed {
+        /// Path of the value
+        path: String,
+        /// Value in the first job
+        value: String,
+    },
+    /// A value is different between the two jobs
+    Changed {
+        /// Path of the value
+        path: String,
+        /// Value in the first job
+        left: String,
+        /// Value in the second job
+        right: String,
+    },
+}
+
+impl ConfigDifference {
+    /// Path of the value that is different
+    pub fn path(&self) -> &str {
+        match self {
+            ConfigDifference::Added { path, .. }
+            | ConfigDifference::Removed { path, .. }
+            | ConfigDifference::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// An element of a `config.xml`, with its text if it has no child elements
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn from_start(start: &BytesStart<'_>) -> Result<Self> {
+        let mut attributes = vec![];
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            attributes.push((
+                std::str::from_utf8(attribute.key.as_ref())?.to_string(),
+                attribute.unescape_value()?.into_owned(),
+            ));
+        }
+        Ok(Element {
+            name: std::str::from_utf8(start.name().as_ref())?.to_string(),
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse the root element of a `config.xml`
+fn parse_config_xml(config_xml: &str) -> Result<Element> {
+    let mut reader = Reader::from_str(config_xml);
+    reader.config_mut().trim_text(true);
+
+    let mut parents: Vec<Element> = vec![];
+    loop {
+        let element = match reader.read_event()? {
+            Event::Start(start) => {
+                parents.push(Element::from_start(&start)?);
+                continue;
+            }
+            Event::Empty(start) => Element::from_start(&start)?,
+            Event::Text(content) => {
+                if let Some(parent) = parents.last_mut() {
+                    parent.text.push_str(&content.unescape()?);
+                }
+                continue;
+            }
+            Event::CData(content) => {
+                if let Some(parent) = parents.last_mut() {
+                    parent.text.push_str(std::str::from_utf8(&content)?);
+                }
+                continue;
+            }
+            Event::End(_) => parents.pop().unwrap_or_default(),
+            Event::Eof => return Err("config.xml has no root element".into()),
+            _ => continue,
+        };
+        match parents.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => return Ok(element),
+        }
+    }
+}
+
+fn push_difference(
+    differences: &mut Vec<ConfigDifference>,
+    path: String,
+    left: Option<&str>,
+    right: Option<&str>,
+) {
+    match (left, right) {
+        (Some(value), None) => differences.push(ConfigDifference::Removed {
+            path,
+            value: value.to_string(),
+        }),
+        (None, Some(value)) => differences.push(ConfigDifference::Added {
+            path,
+            value: value.to_string(),
+        }),
+        (Some(left), Some(right)) if left != right => differences.push(ConfigDifference::Changed {
+            path,
+            left: left.to_string(),
+            right: right.to_string(),
+        }),
+        _ => (),
+    }
+}
+
+/// Text of an element without child elements
+fn leaf_value(element: Option<&Element>) -> Option<&str> {
+    element
+        .filter(|element| element.children.is_empty())
+        .map(|element| element.text.trim())
+}
+
+/// Child elements, grouped by name in the order they first appear
+fn children_by_name(element: Option<&Element>) -> Vec<(&str, Vec<&Element>)> {
+    let mut groups: Vec<(&str, Vec<&Element>)> = vec![];
+    for child in element.into_iter().flat_map(|element| &element.children) {
+        let name = child.name.as_str();
+        match groups.iter_mut().find(|(group, _)| *group == name) {
+            Some((_, elements)) => elements.push(child),
+            None => groups.push((name, vec![child])),
+        }
+    }
+    groups
+}
+
+/// Child elements named `name`, from the groups of `children_by_name`
+fn elements_named<'a>(groups: &[(&str, Vec<&'a Element>)], name: &str) -> Vec<&'a Element> {
+    groups
+        .iter()
+        .find(|(group, _)| *group == name)
+        .map(|(_, elements)| elements.clone())
+        .unwrap_or_default()
+}
+
+/// Compare two elements found at the same path, one of them possibly missing
+fn diff_elements(
+    left: Option<&Element>,
+    right: Option<&Element>,
+    path: &str,
+    differences: &mut Vec<ConfigDifference>,
+) {
+    push_difference(
+        differences,
+        path.to_string(),
+        leaf_value(left),
+        leaf_value(right),
+    );
+
+    let mut attributes: Vec<&str> = vec![];
+    for (attribute, _) in left
+        .into_iter()
+        .chain(right)
+        .flat_map(|element| &element.attributes)
+    {
+        if !attributes.contains(&attribute.as_str()) {
+            attributes.push(attribute);
+        }
+    }
+    for attribute in attributes {
+        push_difference(
+            differences,
+            format!("{}/@{}", path, attribute),
+            left.and_then(|element| element.attribute(attribute)),
+            right.and_then(|element| element.attribute(attribute)),
+        );
+    }
+
+    let left_children = children_by_name(left);
+    let right_children = children_by_name(right);
+    let mut names: Vec<&str> = left_children.iter().map(|(name, _)| *name).collect();
+    names.extend(
+        right_children
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| left_children.iter().all(|(left, _)| left != name)),
+    );
+    for name in names {
+        let left_elements = elements_named(&left_children, name);
+        let right_elements = elements_named(&right_children, name);
+        let count = left_elements.len().max(right_elements.len());
+        for index in 0..count {
+            let child_path = if count > 1 {
+                format!("{}/{}[{}]", path, name, index + 1)
+            } else {
+                format!("{}/{}", path, name)
+            };
+            diff_elements(
+                left_elements.get(index).copied(),
+                right_elements.get(index).copied(),
+                &child_path,
+                differences,
+            );
+        }
+    }
+}
+
+/// Compare two `config.xml` documents, returning the differences in the order of the documents
+pub(crate) fn diff_config_xml(left: &str, right: &str) -> Result<Vec<ConfigDifference>> {
+    let left = parse_config_xml(left)?;
+    let right = parse_config_xml(right)?;
+
+    let mut differences = vec![];
+    if left.name == right.name {
+        diff_elements(
+            Some(&left),
+            Some(&right),
+            &format!("/{}", left.name),
+            &mut differences,
+        );
+    } else {
+        diff_elements(
+            Some(&left),
+            None,
+            &format!("/{}", left.name),
+            &mut differences,
+        );
+        diff_elements(
+            None,
+            Some(&right),
+            &format!("/{}", right.name),
+            &mut differences,
+        );
+    }
+    Ok(differences)
+}
+
+impl Jenkins {
+    /// Compare the configurations of the jobs `job_a` and `job_b`, in nested folders if they
+    /// have some, returning what changes from `job_a` to `job_b`
+    pub async fn diff_job_config<'a, 'b, A, B>(
+        &self,
+        job_a: A,
+        job_b: B,
+    ) -> Result<Vec<ConfigDifference>>
+    where
+        A: Into<JobName<'a>>,
+        B: Into<JobName<'b>>,
+    {
+        let left = self.get_job_config(job_a.into().0).await?;
+        let right = self.get_job_config(job_b.into().0).await?;
+        diff_config_xml(&left, &right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigDifference;
+
+    #[tokio::test]
+    async fn can_diff_job_configs() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock_a = server
+            .mock("GET", "/job/build-a/config.xml")
+            .with_body(
+                r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <description>first</description>
+  <disabled>false</disabled>
+  <builders>
+    <hudson.tasks.Shell>
+      <command>make</command>
+    </hudson.tasks.Shell>
+  </builders>
+  <publishers/>
+</project>"#,
+            )
+            .create();
+        let mock_b = server
+            .mock("GET", "/job/team/job/build-b/config.xml")
+            .with_body(
+                r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <description>second</description>
+  <disabled>false</disabled>
+  <builders>
+    <hudson.tasks.Shell>
+      <command>make</command>
+    </hudson.tasks.Shell>
+    <hudson.tasks.Shell>
+      <command>make test</command>
+    </hudson.tasks.Shell>
+  </builders>
+</project>"#,
+            )
+            .create();
+
+        let differences = jenkins_client
+            .diff_job_config("build-a", "team/build-b")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            differences,
+            vec![
+                ConfigDifference::Changed {
+                    path: "/project/description".to_string(),
+                    left: "first".to_string(),
+                    right: "second".to_string(),
+                },
+                ConfigDifference::Added {
+                    path: "/project/builders/hudson.tasks.Shell[2]/command".to_string(),
+                    value: "make test".to_string(),
+                },
+                ConfigDifference::Removed {
+                    path: "/project/publishers".to_string(),
+                    value: "".to_string(),
+                },
+            ]
+        );
+        mock_a.assert();
+        mock_b.assert();
+    }
+}
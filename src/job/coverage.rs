@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use super::Job;
+use crate::client::Result;
+use crate::client_internals::{is_not_found, Path, ResponseExt};
+use crate::Jenkins;
+
+/// Coverage of a build, as reported by the Coverage plugin
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoveragePoint {
+    /// Number of the build
+    pub build_number: u32,
+    /// When the build started, in milliseconds
+    pub timestamp: Option<u64>,
+    /// Coverage of the project in percent, by metric like `line`, `branch` or `method`
+    pub percentages: BTreeMap<String, f64>,
+}
+
+impl CoveragePoint {
+    /// Coverage in percent for `metric`, like `line`
+    pub fn metric(&self, metric: &str) -> Option<f64> {
+        self.percentages.get(metric).copied()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoverageApi {
+    #[serde(default)]
+    project_statistics: BTreeMap<String, String>,
+}
+
+/// Parse the statistics of the Coverage plugin, keeping the ones in percent like `83.33%`
+fn percentages(statistics: BTreeMap<String, String>) -> BTreeMap<String, f64> {
+    statistics
+        .into_iter()
+        .filter_map(|(metric, value)| {
+            let percentage = value.trim().strip_suffix('%')?.trim().parse().ok()?;
+            Some((metric, percentage))
+        })
+        .collect()
+}
+
+/// Get the coverage of the `count` most recent builds of `job`, from the oldest. Builds without
+/// coverage are skipped
+pub(crate) async fn get_coverage_trend<J: Job + ?Sized>(
+    jenkins_client: &Jenkins,
+    job: &J,
+    count: u32,
+) -> Result<Vec<CoveragePoint>> {
+    let builds = job.get_builds_page(jenkins_client, 0, count).await?;
+    let mut trend = vec![];
+    for build in builds.iter().rev() {
+        let path = Path::Coverage {
+            build: Box::new(jenkins_client.url_to_path(&build.url)),
+        };
+        let coverage: CoverageApi = match jenkins_client.get(&path).await {
+            Ok(response) => response.parse_json().await?,
+            Err(error) if is_not_found(error.as_ref()) => continue,
+            Err(error) => return Err(error),
+        };
+        trend.push(CoveragePoint {
+            build_number: build.number,
+            timestamp: build.timestamp,
+            percentages: percentages(coverage.project_statistics),
+        });
+    }
+    Ok(trend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_coverage_trend() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let job = crate::job::TestJob::new("app", &server.url(), "/job/app/");
+
+        let builds = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "allBuilds[number,url,displayName,timestamp]{0,3}".to_string(),
+            ))
+            .with_body(
+                serde_json::json!({
+                    "allBuilds": (10..13).rev().map(|number| serde_json::json!({
+                        "number": number,
+                        "url": format!("{}/job/app/{}/", server.url(), number),
+                        "timestamp": number * 1000,
+                    })).collect::<Vec<_>>()
+                })
+                .to_string(),
+            )
+            .create();
+        let mut coverages = vec![];
+        for (number, line) in [(10, "80.00%"), (12, "82.50%")] {
+            coverages.push(
+                server
+                    .mock(
+                        "GET",
+                        format!("/job/app/{}/coverage/api/json", number).as_str(),
+                    )
+                    .match_query(mockito::Matcher::Any)
+                    .with_body(
+                        serde_json::json!({
+                            "projectStatistics": {
+                                "line": line,
+                                "branch": "60.00%",
+                                "loc": "400"
+                            }
+                        })
+                        .to_string(),
+                    )
+                    .create(),
+            );
+        }
+        let missing = server
+            .mock("GET", "/job/app/11/coverage/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .create();
+
+        let trend = get_coverage_trend(&jenkins_client, &job, 3).await.unwrap();
+
+        assert_eq!(
+            trend
+                .iter()
+                .map(|point| (point.build_number, point.metric("line")))
+                .collect::<Vec<_>>(),
+            vec![(10, Some(80.0)), (12, Some(82.5))]
+        );
+        assert_eq!(trend[0].timestamp, Some(10_000));
+        assert_eq!(trend[0].metric("loc"), None);
+        builds.assert();
+        for coverage in coverages {
+            coverage.assert();
+        }
+        missing.assert();
+    }
+}
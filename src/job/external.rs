@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -12,7 +12,7 @@ use super::{BallColor, HealthReport};
 
 job_buildable_with_common_fields_and_impl!(
     /// An external job
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct ExternalJob {}
 );
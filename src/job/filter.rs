@@ -0,0 +1,233 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::client::{Result, TreeBuilder};
+use crate::Jenkins;
+
+/// Summary of a `Job`, with what is needed to find stale jobs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobSummary {
+    /// Full name of the job, like `folder/job`
+    pub full_name: String,
+    /// URL of the job
+    pub url: String,
+    /// Is the job disabled
+    pub disabled: bool,
+    /// Can the job be built
+    pub buildable: bool,
+    /// Timestamp of the last build, in milliseconds since the epoch
+    pub last_build: Option<u64>,
+    /// Timestamp of the last successful build, in milliseconds since the epoch
+    pub last_successful_build: Option<u64>,
+}
+
+/// Filter on `JobSummary`, all the conditions set must match
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JobFilter {
+    disabled: Option<bool>,
+    buildable: Option<bool>,
+    not_built_for: Option<Duration>,
+    without_successful_build: bool,
+}
+
+impl JobFilter {
+    /// Create a filter keeping all the jobs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only the disabled jobs
+    pub fn disabled(mut self) -> Self {
+        self.disabled = Some(true);
+        self
+    }
+
+    /// Keep only the jobs that can't be built, either disabled or not buildable by nature
+    pub fn not_buildable(mut self) -> Self {
+        self.buildable = Some(false);
+        self
+    }
+
+    /// Keep only the jobs not built since `duration`, including the jobs never built
+    pub fn not_built_for(mut self, duration: Duration) -> Self {
+        self.not_built_for = Some(duration);
+        self
+    }
+
+    /// Keep only the jobs without a successful build
+    pub fn without_successful_build(mut self) -> Self {
+        self.without_successful_build = true;
+        self
+    }
+
+    /// Does `job` match this filter
+    pub fn matches(&self, job: &JobSummary) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_millis() as u64)
+            .unwrap_or_default();
+        self.disabled
+            .is_none_or(|disabled| job.disabled == disabled)
+            && self
+                .buildable
+                .is_none_or(|buildable| job.buildable == buildable)
+            && self.not_built_for.is_none_or(|duration| {
+                job.last_build
+                    .is_none_or(|timestamp| timestamp + (duration.as_millis() as u64) < now)
+            })
+            && !(self.without_successful_build && job.last_successful_build.is_some())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BuildTimestamp {
+    timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawJob {
+    url: String,
+    disabled: Option<bool>,
+    buildable: Option<bool>,
+    color: Option<String>,
+    last_build: Option<BuildTimestamp>,
+    last_successful_build: Option<BuildTimestamp>,
+}
+
+impl Jenkins {
+    /// List the jobs, in all folders, matching `filter`
+    ///
+    /// The fields needed are fetched with one tree query per folder, and filtered locally.
+    pub async fn list_jobs_where(&self, filter: &JobFilter) -> Result<Vec<JobSummary>> {
+        let folders = self
+            .walk_folders::<(), RawJob>(
+                |tree| tree,
+                |jobs| {
+                    jobs.with_subfield("url")
+                        .with_subfield("disabled")
+                        .with_subfield("buildable")
+                        .with_subfield("color")
+                        .with_subfield(TreeBuilder::object("lastBuild").with_subfield("timestamp"))
+                        .with_subfield(
+                            TreeBuilder::object("lastSuccessfulBuild").with_subfield("timestamp"),
+                        )
+                },
+            )
+            .await?;
+
+        let mut jobs = vec![];
+        for job in folders.into_iter().flat_map(|folder| folder.jobs) {
+            if job.is_folder {
+                continue;
+            }
+            let raw = job.fields;
+            let disabled = raw.disabled.unwrap_or_else(|| {
+                raw.color
+                    .as_deref()
+                    .is_some_and(|color| color.starts_with("disabled"))
+            });
+            let summary = JobSummary {
+                full_name: job.path.full_name(),
+                url: raw.url,
+                disabled,
+                buildable: raw.buildable.unwrap_or(!disabled),
+                last_build: raw.last_build.map(|build| build.timestamp),
+                last_successful_build: raw.last_successful_build.map(|build| build.timestamp),
+            };
+            if filter.matches(&summary) {
+                jobs.push(summary);
+            }
+        }
+        Ok(jobs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_list_stale_jobs() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let jenkins_client = crate::JenkinsBuilder::new(&url).build().unwrap();
+
+        let root = server
+            .mock("GET", "/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "jobs": [
+                        {
+                            "name": "team",
+                            "url": format!("{}/job/team/", url),
+                            "jobs": [{"name": "old"}]
+                        },
+                        {
+                            "name": "active",
+                            "url": format!("{}/job/active/", url),
+                            "buildable": true,
+                            "color": "blue",
+                            "lastBuild": {"timestamp": 4_102_444_800_000_u64},
+                            "lastSuccessfulBuild": {"timestamp": 4_102_444_800_000_u64}
+                        },
+                        {
+                            "name": "never-built",
+                            "url": format!("{}/job/never-built/", url),
+                            "buildable": true,
+                            "color": "notbuilt"
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+        let team = server
+            .mock("GET", "/job/team/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "jobs": [
+                        {
+                            "name": "old",
+                            "url": format!("{}/job/team/job/old/", url),
+                            "disabled": true,
+                            "buildable": false,
+                            "color": "disabled",
+                            "lastBuild": {"timestamp": 1000},
+                            "lastSuccessfulBuild": null
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let stale = jenkins_client
+            .list_jobs_where(&JobFilter::new().not_built_for(Duration::from_secs(86_400 * 30)))
+            .await
+            .unwrap();
+        let mut names = stale
+            .iter()
+            .map(|job| job.full_name.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, vec!["never-built", "team/old"]);
+
+        let disabled = stale
+            .into_iter()
+            .filter(|job| {
+                JobFilter::new()
+                    .disabled()
+                    .without_successful_build()
+                    .matches(job)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(disabled.len(), 1);
+        assert_eq!(disabled[0].full_name, "team/old");
+        root.assert();
+        team.assert();
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -13,10 +13,11 @@ use super::{BallColor, HealthReport};
 
 job_buildable_with_common_fields_and_impl!(
     /// A build flow job
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct BuildFlowJob<BuildType = BuildFlowRun> {
         /// Description of the job
+        #[serde(default, deserialize_with = "crate::helpers::default_on_null")]
         pub description: String,
         /// Is concurrent build enabled for the job?
         pub concurrent_build: bool,
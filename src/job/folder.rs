@@ -1,15 +1,16 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
-use super::Job;
+use super::{HealthReport, Job};
 use crate::action::CommonAction;
 use crate::build::{CommonBuild, ShortBuild};
 use crate::job::ShortJob;
+use crate::property::CommonProperty;
 
 job_base_with_common_fields_and_impl!(
     /// A folder
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct Folder {
         /// List of the jobs in the folder
@@ -0,0 +1,309 @@
+use std::fmt;
+
+use crate::client::Result;
+use crate::client_internals::{Name, Path};
+use crate::job::JobName;
+
+/// Path to a job in nested folders
+///
+/// It can be built step by step, or from the names of the folders and of the job, or from the
+/// full name of the job. Methods taking a job name accept it, as well as a full name:
+///
+/// ```rust
+/// # use jenkins_api::job::FolderPath;
+/// let path = FolderPath::new().folder("team").folder("services").job("api");
+/// assert_eq!(path.full_name(), "team/services/api");
+/// assert_eq!(FolderPath::from(&path), FolderPath::from(&["team", "services", "api"]));
+/// assert_eq!(FolderPath::from(path), FolderPath::from("team/services/api"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FolderPath {
+    pub(super) full_name: String,
+}
+
+impl FolderPath {
+    /// Create an empty path, at the root of Jenkins
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path from the full name of a job, like `team/services/api`
+    pub fn from_full_name(full_name: &str) -> Self {
+        full_name
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .fold(FolderPath::new(), |path, name| path.folder(name))
+    }
+
+    /// Go into the folder `name`
+    pub fn folder(mut self, name: &str) -> Self {
+        if !self.full_name.is_empty() {
+            self.full_name.push('/');
+        }
+        self.full_name.push_str(name);
+        self
+    }
+
+    /// Point to the job `name` in the current folder
+    pub fn job(self, name: &str) -> JobPath {
+        JobPath {
+            full_name: self.folder(name).full_name,
+        }
+    }
+
+    /// Full name of the job, like `team/services/api`
+    pub fn full_name(&self) -> String {
+        self.full_name.clone()
+    }
+
+    /// Name of the job, without its folders
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.names().last()
+    }
+
+    /// Names of the folders and of the job
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.full_name.split('/').filter(|name| !name.is_empty())
+    }
+
+    /// Path to the folder, or to the root of Jenkins when the path is empty
+    pub(crate) fn to_folder_path(&self) -> Path<'_> {
+        self.to_path().unwrap_or(Path::Home)
+    }
+
+    /// Path to the job, as `Path::InFolder` wrapping each folder around the job
+    pub(crate) fn to_path(&self) -> Result<Path<'_>> {
+        JobName::from(self).to_path()
+    }
+
+    /// Path to the endpoint built by `endpoint` from the name of the job, in its folders
+    pub(crate) fn to_path_with<'a, F>(&'a self, endpoint: F) -> Result<Path<'a>>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        JobName::from(self).to_path_with(endpoint)
+    }
+}
+
+/// Path to a job in nested folders, ending with the job set by
+/// [`FolderPath::job`](struct.FolderPath.html#method.job)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobPath {
+    full_name: String,
+}
+
+impl JobPath {
+    /// Full name of the job, like `team/services/api`
+    pub fn full_name(&self) -> String {
+        self.full_name.clone()
+    }
+
+    /// Names of the folders and of the job
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.full_name.split('/').filter(|name| !name.is_empty())
+    }
+
+    /// Path to the job, as `Path::InFolder` wrapping each folder around `/job/<name>`
+    pub(crate) fn to_path(&self) -> Result<Path<'_>> {
+        JobName::from(self).to_path()
+    }
+
+    /// Path to the endpoint built by `endpoint` from the name of the job, in its folders
+    pub(crate) fn to_path_with<'a, F>(&'a self, endpoint: F) -> Result<Path<'a>>
+    where
+        F: FnOnce(Name<'a>) -> Path<'a>,
+    {
+        JobName::from(self).to_path_with(endpoint)
+    }
+}
+
+impl From<JobPath> for FolderPath {
+    fn from(path: JobPath) -> Self {
+        FolderPath {
+            full_name: path.full_name,
+        }
+    }
+}
+
+impl From<&JobPath> for FolderPath {
+    fn from(path: &JobPath) -> Self {
+        FolderPath::from(path.clone())
+    }
+}
+
+impl<'a> From<&'a JobPath> for JobName<'a> {
+    fn from(path: &'a JobPath) -> JobName<'a> {
+        JobName(&path.full_name)
+    }
+}
+
+impl fmt::Display for JobPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.full_name)
+    }
+}
+
+impl From<&str> for FolderPath {
+    fn from(full_name: &str) -> Self {
+        FolderPath::from_full_name(full_name)
+    }
+}
+
+impl From<&FolderPath> for FolderPath {
+    fn from(path: &FolderPath) -> Self {
+        path.clone()
+    }
+}
+
+impl From<&[&str]> for FolderPath {
+    fn from(names: &[&str]) -> Self {
+        names
+            .iter()
+            .fold(FolderPath::new(), |path, name| path.folder(name))
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for FolderPath {
+    fn from(names: [&str; N]) -> Self {
+        FolderPath::from(&names[..])
+    }
+}
+
+impl<const N: usize> From<&[&str; N]> for FolderPath {
+    fn from(names: &[&str; N]) -> Self {
+        FolderPath::from(&names[..])
+    }
+}
+
+impl fmt::Display for FolderPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.full_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_render_folder_path() {
+        let job = FolderPath::new()
+            .folder("team")
+            .folder("my services")
+            .job("api");
+        assert_eq!(
+            job.to_path().unwrap().to_string(),
+            "/job/team/job/my%20services/job/api"
+        );
+
+        let path = FolderPath::from(job);
+        assert_eq!(path, FolderPath::from_full_name("team/my services/api/"));
+        assert_eq!(path, FolderPath::from(["team", "my services", "api"]));
+        assert_eq!(path.name(), Some("api"));
+        assert_eq!(
+            path.to_path().unwrap().to_string(),
+            "/job/team/job/my%20services/job/api"
+        );
+        assert!(FolderPath::new().to_path().is_err());
+    }
+
+    #[tokio::test]
+    async fn can_act_on_job_in_nested_folders() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let path = FolderPath::from(["team", "services", "api"]);
+
+        let job = server
+            .mock("GET", "/job/team/job/services/job/api/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "hudson.model.FreeStyleProject",
+                    "name": "api",
+                    "displayName": "api",
+                    "url": format!("{}/job/team/job/services/job/api/", server.url()),
+                    "buildable": true,
+                    "builds": [],
+                    "color": "blue",
+                    "inQueue": false,
+                    "keepDependencies": false,
+                    "nextBuildNumber": 13,
+                    "property": [],
+                    "actions": [],
+                    "healthReport": []
+                })
+                .to_string(),
+            )
+            .expect(2)
+            .create();
+        let build = server
+            .mock("GET", "/job/team/job/services/job/api/12/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "hudson.model.FreeStyleBuild",
+                    "actions": [],
+                    "artifacts": [],
+                    "building": false,
+                    "displayName": "#12",
+                    "duration": 1000,
+                    "estimatedDuration": 1000,
+                    "fullDisplayName": "team » services » api #12",
+                    "id": "12",
+                    "keepLog": false,
+                    "number": 12,
+                    "queueId": 3,
+                    "result": "SUCCESS",
+                    "timestamp": 1000,
+                    "url": format!("{}/job/team/job/services/job/api/12/", server.url()),
+                    "changeSet": {"items": [], "kind": null},
+                    "culprits": []
+                })
+                .to_string(),
+            )
+            .create();
+        let config = server
+            .mock("GET", "/job/team/job/services/job/api/config.xml")
+            .with_body("<project/>")
+            .create();
+        let console = server
+            .mock("GET", "/job/team/job/services/job/api/12/consoleText")
+            .with_body("Finished: SUCCESS\n")
+            .create();
+        let build_job = server
+            .mock("POST", "/job/team/job/services/job/api/build")
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/4/", server.url()))
+            .expect(2)
+            .create();
+
+        let _ = jenkins_client.get_job("team/services/api").await.unwrap();
+        let _ = jenkins_client.get_job(&path).await.unwrap();
+        let found = jenkins_client.get_build(&path, 12).await.unwrap();
+        assert_eq!(found.number, 12);
+        let output = jenkins_client
+            .get_console("team/services/api", 12)
+            .await
+            .unwrap();
+        assert_eq!(output, "Finished: SUCCESS\n");
+        let queued = jenkins_client.build_job(&path).await.unwrap();
+        assert_eq!(queued.url, format!("{}/queue/item/4/", server.url()));
+        let _ = jenkins_client
+            .job_builder(&path)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        let config_xml = jenkins_client.get_job_config(&path).await.unwrap();
+        assert_eq!(config_xml, "<project/>");
+
+        job.assert();
+        build.assert();
+        config.assert();
+        console.assert();
+        build_job.assert();
+    }
+}
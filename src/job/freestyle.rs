@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -13,13 +13,17 @@ use super::{BallColor, HealthReport};
 
 job_buildable_with_common_fields_and_impl!(
     /// A free style project
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct FreeStyleProject<BuildType = FreeStyleBuild> {
         /// Description of the job
+        #[serde(default, deserialize_with = "crate::helpers::default_on_null")]
         pub description: String,
         /// Is concurrent build enabled for the job?
         pub concurrent_build: bool,
+        /// Is the job disabled
+        #[serde(default)]
+        pub disabled: bool,
         /// SCM configured for the job
         pub scm: CommonSCM,
         /// List of the upstream projects
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -15,10 +15,11 @@ use super::{BallColor, HealthReport};
 
 job_buildable_with_common_fields_and_impl!(
     /// A matrix project
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MatrixProject<BuildType = MatrixBuild> {
         /// Description of the job
+        #[serde(default, deserialize_with = "crate::helpers::default_on_null")]
         pub description: String,
         /// Is concurrent build enabled for the job?
         pub concurrent_build: bool,
@@ -41,7 +42,7 @@ impl SCMPollable for MatrixProject {}
 
 job_buildable_with_common_fields_and_impl!(
     /// A matrix configuration
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MatrixConfiguration<BuildType = MatrixRun> {
         /// Description of the job
@@ -1,22 +1,26 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
 use super::{BuildableJob, Job, SCMPollable, ShortJob};
 use crate::action::CommonAction;
-use crate::build::{MavenBuild, MavenModuleSetBuild, ShortBuild};
+use crate::build::{BuildNumber, MavenBuild, MavenModuleSetBuild, ShortBuild};
+use crate::client::{self, Result};
+use crate::client_internals::{Path, ResponseExt};
 use crate::property::CommonProperty;
 use crate::queue::ShortQueueItem;
 use crate::scm::CommonSCM;
+use crate::Jenkins;
 
 use super::{BallColor, HealthReport};
 
 job_buildable_with_common_fields_and_impl!(
     /// A maven project
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MavenModuleSet<BuildType = MavenModuleSetBuild> {
         /// Description of the job
+        #[serde(default, deserialize_with = "crate::helpers::default_on_null")]
         pub description: String,
         /// Is concurrent build enabled for the job?
         pub concurrent_build: bool,
@@ -37,9 +41,20 @@ register_class!("hudson.maven.MavenModuleSet" => MavenModuleSet);
 impl BuildableJob for MavenModuleSet {}
 impl SCMPollable for MavenModuleSet {}
 
+impl MavenModuleSet {
+    /// Get the full details of all the modules of this project
+    pub async fn get_modules(&self, jenkins_client: &Jenkins) -> Result<Vec<MavenModule>> {
+        let mut modules = Vec::with_capacity(self.modules.len());
+        for module in &self.modules {
+            modules.push(module.get_full_job(jenkins_client).await?);
+        }
+        Ok(modules)
+    }
+}
+
 job_buildable_with_common_fields_and_impl!(
     /// A maven module
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct MavenModule<BuildType = MavenBuild> {
         /// Description of the job
@@ -58,4 +73,124 @@ job_buildable_with_common_fields_and_impl!(
 );
 register_class!("hudson.maven.MavenModule" => MavenModule);
 
-impl MavenModule {}
+impl MavenModule {
+    /// Get a `MavenBuild` of this module from its `build_number`
+    pub async fn get_build<B>(
+        &self,
+        jenkins_client: &Jenkins,
+        build_number: B,
+    ) -> Result<MavenBuild>
+    where
+        B: Into<BuildNumber>,
+    {
+        let path = match jenkins_client.url_to_path(&self.url) {
+            Path::Job {
+                name,
+                configuration: Some(configuration),
+            } => Some(Path::Build {
+                job_name: name,
+                number: build_number.into(),
+                configuration: Some(configuration),
+            }),
+            Path::InFolder {
+                folder_name,
+                path: sub_path,
+            } => match *sub_path {
+                Path::Job {
+                    name,
+                    configuration: Some(configuration),
+                } => Some(Path::InFolder {
+                    folder_name,
+                    path: Box::new(Path::Build {
+                        job_name: name,
+                        number: build_number.into(),
+                        configuration: Some(configuration),
+                    }),
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(path) = path {
+            Ok(jenkins_client.get(&path).await?.parse_json().await?)
+        } else {
+            Err(client::Error::InvalidUrl {
+                url: self.url.clone(),
+                expected: client::error::ExpectedType::Job,
+            }
+            .into())
+        }
+    }
+
+    /// Get the full details of all the builds of this module
+    pub async fn get_builds(&self, jenkins_client: &Jenkins) -> Result<Vec<MavenBuild>> {
+        let mut builds = Vec::with_capacity(self.builds.len());
+        for build in &self.builds {
+            builds.push(self.get_build(jenkins_client, build.number).await?);
+        }
+        Ok(builds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_build_of_module() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let module: MavenModule = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.maven.MavenModule",
+            "name": "org.example:module",
+            "displayName": "module",
+            "url": format!("{}/job/maven%20job/org.example$module/", server.url()),
+            "actions": [],
+            "keepDependencies": false,
+            "nextBuildNumber": 2,
+            "inQueue": false,
+            "builds": [],
+            "healthReport": [],
+            "property": [],
+            "concurrentBuild": false,
+            "scm": {"_class": "hudson.scm.NullSCM"},
+            "upstreamProjects": [],
+            "downstreamProjects": []
+        }))
+        .unwrap();
+
+        let mock = server
+            .mock("GET", "/job/maven%20job/org.example$module/1/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "hudson.maven.MavenBuild",
+                    "url": format!("{}/job/maven%20job/org.example$module/1/", server.url()),
+                    "number": 1,
+                    "duration": 10,
+                    "estimatedDuration": 10,
+                    "timestamp": 1_500_000_000_000u64,
+                    "keepLog": false,
+                    "result": "SUCCESS",
+                    "displayName": "#1",
+                    "building": false,
+                    "id": "1",
+                    "queueId": 2,
+                    "actions": [],
+                    "artifacts": [],
+                    "changeSet": {"items": []},
+                    "builtOn": "",
+                    "mavenArtifacts": {"url": "mavenArtifacts"},
+                    "culprits": []
+                })
+                .to_string(),
+            )
+            .create();
+
+        let build = module.get_build(&jenkins_client, 1).await;
+
+        assert_eq!(build.unwrap().number, 1);
+        mock.assert();
+    }
+}
@@ -0,0 +1,248 @@
+use serde::Deserialize;
+
+use crate::build::ShortBuild;
+use crate::client::{self, AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::path::Path as PrivatePath;
+use crate::client_internals::{InternalAdvancedQueryParams, ResponseExt};
+use crate::job::{FolderPath, JobName};
+use crate::Jenkins;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobHistory {
+    full_name: String,
+    first_build: Option<ShortBuild>,
+    last_build: Option<ShortBuild>,
+}
+
+/// Full names of the job, of the destination folder and of the job once in the destination
+fn migration_names<'a>(job: &'a str, destination: &'a str) -> (&'a str, &'a str, String) {
+    let job = job.trim_matches('/');
+    let destination = destination.trim_matches('/');
+    let name = job.rsplit('/').next().unwrap_or(job);
+    let expected_full_name = if destination.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", destination, name)
+    };
+    (job, destination, expected_full_name)
+}
+
+impl Jenkins {
+    async fn get_job_history(&self, full_name: &str) -> Result<JobHistory> {
+        self.get_with_params(
+            &JobName(full_name).to_path()?,
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                TreeBuilder::new()
+                    .with_field("fullName")
+                    .with_field(TreeBuilder::object("firstBuild").with_subfield("number"))
+                    .with_field(TreeBuilder::object("lastBuild").with_subfield("number"))
+                    .build(),
+            )),
+        )
+        .await?
+        .parse_json()
+        .await
+    }
+
+    /// Move the job with the full name `job` (like `folder/job`) to the folder `destination`, or
+    /// to the root of Jenkins if `destination` is empty, returning the new full name of the job
+    ///
+    /// Moving a job with the Folders plugin keeps its builds. After the move, the job is checked
+    /// to be in `destination` with the same builds, returning `Error::JobMigrationFailed`
+    /// otherwise. To keep the job where it is, use `copy_job` instead.
+    pub async fn migrate_job(&self, job: &str, destination: &str) -> Result<String> {
+        let (job, destination, expected_full_name) = migration_names(job, destination);
+        let failed = |reason: String| client::Error::JobMigrationFailed {
+            job: job.to_string(),
+            destination: destination.to_string(),
+            reason,
+        };
+
+        let before = self.get_job_history(job).await?;
+        let _ = self
+            .post_with_body(
+                &JobName(job).to_path_with(|name| PrivatePath::MoveJob {
+                    job: Box::new(PrivatePath::Job {
+                        name,
+                        configuration: None,
+                    }),
+                })?,
+                url::form_urlencoded::Serializer::new(String::new())
+                    .append_pair("destination", &format!("/{}", destination))
+                    .finish(),
+                &[],
+            )
+            .await?;
+
+        let after = self
+            .get_job_history(&expected_full_name)
+            .await
+            .map_err(|error| failed(format!("job not found after the move: {}", error)))?;
+        if after.full_name != expected_full_name {
+            return Err(failed(format!("job found as {}", after.full_name)).into());
+        }
+        let numbers = |history: &JobHistory| {
+            (
+                history.first_build.as_ref().map(|build| build.number),
+                history.last_build.as_ref().map(|build| build.number),
+            )
+        };
+        if numbers(&before) != numbers(&after) {
+            return Err(failed(format!(
+                "builds changed from {:?} to {:?}",
+                numbers(&before),
+                numbers(&after)
+            ))
+            .into());
+        }
+        Ok(after.full_name)
+    }
+
+    /// Copy the job with the full name `job` (like `folder/job`) to the folder `destination`, or
+    /// to the root of Jenkins if `destination` is empty, returning the full name of the copy
+    ///
+    /// Jenkins copies the configuration of the job but not its builds. After the copy, the job is
+    /// checked to be in `destination`, returning `Error::JobMigrationFailed` otherwise.
+    pub async fn copy_job(&self, job: &str, destination: &str) -> Result<String> {
+        let (job, destination, expected_full_name) = migration_names(job, destination);
+        let failed = |reason: String| client::Error::JobMigrationFailed {
+            job: job.to_string(),
+            destination: destination.to_string(),
+            reason,
+        };
+
+        let copy = FolderPath::from(expected_full_name.as_str());
+        let _ = self
+            .post_with_body(
+                &copy.to_path_with(|_| PrivatePath::CreateItem)?,
+                "",
+                &[
+                    ("name", copy.name().unwrap_or_default()),
+                    ("mode", "copy"),
+                    ("from", &format!("/{}", job)),
+                ],
+            )
+            .await?;
+
+        let after = self
+            .get_job_history(&expected_full_name)
+            .await
+            .map_err(|error| failed(format!("job not found after the copy: {}", error)))?;
+        if after.full_name != expected_full_name {
+            return Err(failed(format!("job found as {}", after.full_name)).into());
+        }
+        Ok(after.full_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn history(server_url: &str, full_name: &str, first: u32, last: u32) -> String {
+        serde_json::json!({
+            "fullName": full_name,
+            "firstBuild": {"number": first, "url": format!("{}/job/x/{}/", server_url, first)},
+            "lastBuild": {"number": last, "url": format!("{}/job/x/{}/", server_url, last)}
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn can_migrate_job() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let before = server
+            .mock("GET", "/job/legacy/job/my%20job/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(history(&server.url(), "legacy/my job", 3, 42))
+            .create();
+        let move_job = server
+            .mock("POST", "/job/legacy/job/my%20job/move/move")
+            .match_body("destination=%2Fteam%2Fapps")
+            .create();
+        let after = server
+            .mock("GET", "/job/team/job/apps/job/my%20job/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(history(&server.url(), "team/apps/my job", 3, 42))
+            .create();
+
+        let full_name = jenkins_client
+            .migrate_job("legacy/my job", "team/apps")
+            .await
+            .unwrap();
+
+        assert_eq!(full_name, "team/apps/my job");
+        before.assert();
+        move_job.assert();
+        after.assert();
+    }
+
+    #[tokio::test]
+    async fn should_fail_migration_when_builds_are_lost() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _before = server
+            .mock("GET", "/job/legacy/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(history(&server.url(), "legacy/app", 3, 42))
+            .create();
+        let _move_job = server
+            .mock("POST", "/job/legacy/job/app/move/move")
+            .create();
+        let _after = server
+            .mock("GET", "/job/app/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"fullName":"app","firstBuild":null,"lastBuild":null}"#)
+            .create();
+
+        let error = jenkins_client
+            .migrate_job("legacy/app", "")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<crate::client::Error>(),
+            Some(crate::client::Error::JobMigrationFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn can_copy_job() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let copy = server
+            .mock("POST", "/job/team/job/apps/createItem")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("name".to_string(), "my job".to_string()),
+                mockito::Matcher::UrlEncoded("mode".to_string(), "copy".to_string()),
+                mockito::Matcher::UrlEncoded("from".to_string(), "/legacy/my job".to_string()),
+            ]))
+            .create();
+        let after = server
+            .mock("GET", "/job/team/job/apps/job/my%20job/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"fullName":"team/apps/my job","firstBuild":null,"lastBuild":null}"#)
+            .create();
+
+        let full_name = jenkins_client
+            .copy_job("legacy/my job", "team/apps")
+            .await
+            .unwrap();
+
+        assert_eq!(full_name, "team/apps/my job");
+        copy.assert();
+        after.assert();
+    }
+}
@@ -1,14 +1,40 @@
 //! Jenkins Jobs
 
-use crate::client_internals::{Name, Path, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Cursor, Page, TreeBuilder};
+use crate::client_internals::{Path, ResponseExt, Result};
 use crate::queue::ShortQueueItem;
 use crate::Jenkins;
 
 pub mod builder;
 use self::builder::JobBuilder;
+mod config;
+#[cfg(feature = "config-diff")]
+mod config_diff;
+#[cfg(feature = "config-diff")]
+pub use self::config_diff::ConfigDifference;
+mod coverage;
+pub use self::coverage::CoveragePoint;
+mod filter;
+pub use self::filter::{JobFilter, JobSummary};
+mod folder_path;
+pub use self::folder_path::{FolderPath, JobPath};
+mod migrate;
+mod parameters;
+mod shelve;
+mod test_history;
+pub use self::parameters::{BuildParameters, ParameterValue};
+pub use self::shelve::ShelvedProject;
+pub use self::test_history::{TestHistory, TestResultsMatrix, TestStatus};
+mod validation;
+pub use self::validation::FormValidation;
+pub(crate) mod walk;
 
 #[macro_use]
 mod common;
+#[cfg(test)]
+pub(crate) use self::common::TestJob;
 pub use self::common::{
     BallColor, BuildableJob, CommonJob, HealthReport, Job, JobName, SCMPollable, ShortJob,
 };
@@ -28,54 +54,467 @@ mod external;
 pub use self::external::ExternalJob;
 mod folder;
 pub use self::folder::Folder;
+mod branch_source;
+pub use self::branch_source::{BranchSource, SCMNavigator};
 mod multibranch_pipeline;
-pub use self::multibranch_pipeline::WorkflowMultiBranchProject;
+pub use self::multibranch_pipeline::{OrganizationFolder, WorkflowMultiBranchProject};
 
 impl Jenkins {
-    /// Get a `Job` from it's `job_name`
+    /// Get a `Job` from it's `job_name`, or its full name or `FolderPath` in nested folders
+    ///
+    /// ```rust
+    /// # async fn example(jenkins: &jenkins_api::Jenkins) -> jenkins_api::client::Result<()> {
+    /// let job = jenkins.get_job("team/services/api").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn get_job<'a, J>(&self, job_name: J) -> Result<CommonJob>
     where
         J: Into<JobName<'a>>,
     {
         let response = self
-            .get(&Path::Job {
-                name: Name::Name(job_name.into().0),
-                configuration: None,
-            })
+            .get(&job_name.into().to_path()?)
             .await?
-            .json()
+            .parse_json()
             .await?;
         Ok(response)
     }
 
-    /// Build a `Job` from it's `job_name`
+    /// Get the page of jobs at the root of Jenkins at `cursor`
+    pub async fn get_jobs_at(&self, cursor: Cursor) -> Result<Page<ShortJob>> {
+        #[derive(Deserialize)]
+        struct Jobs {
+            jobs: Vec<ShortJob>,
+        }
+
+        let jobs: Jobs = self
+            .get_object_as(
+                crate::client::Path::Home,
+                TreeBuilder::new()
+                    .with_field(
+                        TreeBuilder::object("jobs")
+                            .with_subfield("_class")
+                            .with_subfield("name")
+                            .with_subfield("url")
+                            .with_subfield("color")
+                            .with_cursor(cursor),
+                    )
+                    .build(),
+            )
+            .await?;
+        Ok(Page::new(jobs.jobs, cursor))
+    }
+
+    /// Build a `Job` from it's `job_name`, or its full name or `FolderPath` in nested folders
     pub async fn build_job<'a, J>(&self, job_name: J) -> Result<ShortQueueItem>
     where
         J: Into<JobName<'a>>,
     {
-        JobBuilder::new_from_job_name(job_name.into().0, self)?
-            .send()
-            .await
+        JobBuilder::new_from_job_name(job_name, self)?.send().await
     }
 
-    /// Create a `JobBuilder` to setup a build of a `Job` from it's `job_name`
-    pub fn job_builder<'a, 'b, 'c, 'd>(
+    /// Create a `JobBuilder` to setup a build of a `Job` from it's `job_name`, or its full
+    /// name or `FolderPath` in nested folders
+    pub fn job_builder<'a, 'b, 'c, 'd, J>(
         &'b self,
-        job_name: &'a str,
-    ) -> Result<JobBuilder<'a, 'b, 'c, 'd>> {
+        job_name: J,
+    ) -> Result<JobBuilder<'a, 'b, 'c, 'd>>
+    where
+        J: Into<JobName<'a>>,
+    {
         JobBuilder::new_from_job_name(job_name, self)
     }
 
+    /// Trigger a build of a `Job` from it's `job_name` with its remote trigger `token`
+    ///
+    /// The request is authenticated by the token, so it is sent without a CSRF crumb and works
+    /// without a user. `parameters` are sent as a form to `buildWithParameters`.
+    pub async fn trigger_job_with_token<'a, J, T>(
+        &self,
+        job_name: J,
+        token: &str,
+        cause: Option<&str>,
+        parameters: Option<&T>,
+    ) -> Result<ShortQueueItem>
+    where
+        J: Into<JobName<'a>>,
+        T: Serialize,
+    {
+        let job_name = job_name.into();
+        let mut qps = vec![("token", token)];
+        if let Some(cause) = cause {
+            qps.push(("cause", cause));
+        }
+        let response = match parameters {
+            Some(parameters) => {
+                self.post_with_body_without_crumb(
+                    &job_name.to_path_with(|name| Path::BuildJobWithParameters { name })?,
+                    serde_urlencoded::to_string(parameters)?,
+                    &qps,
+                )
+                .await?
+            }
+            None => {
+                self.post_with_body_without_crumb(
+                    &job_name.to_path_with(|name| Path::BuildJob { name })?,
+                    "",
+                    &qps,
+                )
+                .await?
+            }
+        };
+        builder::queue_item_from_response(&response)
+    }
+
+    /// Trigger a build of a `Job` from it's `job_name` with its remote trigger `token`, using a
+    /// GET request like the URL given by Jenkins in the job configuration
+    ///
+    /// The request is authenticated by the token, so it is sent without a CSRF crumb.
+    pub async fn trigger_job_remotely<'a, J>(
+        &self,
+        job_name: J,
+        token: &str,
+        cause: Option<&str>,
+    ) -> Result<ShortQueueItem>
+    where
+        J: Into<JobName<'a>>,
+    {
+        builder::trigger_remotely(
+            self,
+            &job_name
+                .into()
+                .to_path_with(|name| Path::BuildJob { name })?,
+            token,
+            cause,
+        )
+        .await
+    }
+
     /// Poll SCM of a `Job` from it's `job_name`
     pub async fn poll_scm_job<'a, J>(&self, job_name: J) -> Result<()>
     where
         J: Into<JobName<'a>>,
     {
         let _ = self
-            .post(&Path::PollSCMJob {
-                name: Name::Name(job_name.into().0),
-            })
+            .post(
+                &job_name
+                    .into()
+                    .to_path_with(|name| Path::PollSCMJob { name })?,
+            )
             .await?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_trigger_job_with_token_without_crumb() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let crumb = server
+            .mock("GET", mockito::Matcher::Regex("^/crumbIssuer".to_string()))
+            .expect(0)
+            .create();
+        let trigger = server
+            .mock("POST", "/job/my%20job/buildWithParameters")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("token".to_string(), "s3cr3t".to_string()),
+                mockito::Matcher::UrlEncoded("cause".to_string(), "release".to_string()),
+            ]))
+            .match_body("VERSION=1.2&NOTES=a+%26+b")
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/12/", server.url()))
+            .create();
+
+        let queue_item = jenkins_client
+            .trigger_job_with_token(
+                "my job",
+                "s3cr3t",
+                Some("release"),
+                Some(&[("VERSION", "1.2"), ("NOTES", "a & b")]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(queue_item.url, format!("{}/queue/item/12/", server.url()));
+        crumb.assert();
+        trigger.assert();
+    }
+
+    #[tokio::test]
+    async fn can_trigger_job_remotely() {
+        use super::Job as _;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let job = super::TestJob::new("reports", &server.url(), "/job/team/job/reports/");
+
+        let crumb = server
+            .mock("GET", mockito::Matcher::Regex("^/crumbIssuer".to_string()))
+            .expect(0)
+            .create();
+        let by_name = server
+            .mock("GET", "/job/reports/build")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "token".to_string(),
+                "s3cr3t".to_string(),
+            ))
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/12/", server.url()))
+            .create();
+        let in_folder = server
+            .mock("GET", "/job/team/job/reports/build")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("token".to_string(), "s3cr3t".to_string()),
+                mockito::Matcher::UrlEncoded("cause".to_string(), "nightly".to_string()),
+            ]))
+            .with_status(201)
+            .with_header("location", &format!("{}/queue/item/13/", server.url()))
+            .create();
+
+        let queue_item = jenkins_client
+            .trigger_job_remotely("reports", "s3cr3t", None)
+            .await
+            .unwrap();
+        assert_eq!(queue_item.url, format!("{}/queue/item/12/", server.url()));
+        let queue_item = job
+            .trigger_remotely(&jenkins_client, "s3cr3t", Some("nightly"))
+            .await
+            .unwrap();
+        assert_eq!(queue_item.url, format!("{}/queue/item/13/", server.url()));
+
+        crumb.assert();
+        by_name.assert();
+        in_folder.assert();
+    }
+
+    #[tokio::test]
+    async fn can_act_on_job_in_folder() {
+        use super::{BuildableJob as _, Job as _, SCMPollable as _};
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let job = super::TestJob::new("reports", &server.url(), "/job/team/job/reports/");
+
+        let mocks = vec![
+            server
+                .mock("POST", "/job/team/job/reports/disable")
+                .create(),
+            server.mock("POST", "/job/team/job/reports/enable").create(),
+            server
+                .mock("POST", "/job/team/job/reports/polling")
+                .create(),
+            server
+                .mock("POST", "/view/nightly/addJobToView")
+                .match_query(mockito::Matcher::UrlEncoded(
+                    "name".to_string(),
+                    "team/reports".to_string(),
+                ))
+                .create(),
+            server
+                .mock("POST", "/job/team/job/reports/build")
+                .with_status(201)
+                .with_header("location", &format!("{}/queue/item/12/", server.url()))
+                .create(),
+        ];
+
+        job.disable(&jenkins_client).await.unwrap();
+        job.enable(&jenkins_client).await.unwrap();
+        job.poll_scm(&jenkins_client).await.unwrap();
+        job.add_to_view(&jenkins_client, "nightly").await.unwrap();
+        let queue_item = job.build(&jenkins_client).await.unwrap();
+
+        assert_eq!(queue_item.url, format!("{}/queue/item/12/", server.url()));
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    fn builds_page(server_url: &str, numbers: std::ops::Range<u32>) -> String {
+        serde_json::json!({
+            "allBuilds": numbers
+                .rev()
+                .map(|number| serde_json::json!({
+                    "number": number,
+                    "url": format!("{}/job/reports/{}/", server_url, number),
+                    "timestamp": u64::from(number) * 1000,
+                }))
+                .collect::<Vec<_>>()
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn can_get_builds_between() {
+        use super::Job as _;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+        let job = super::TestJob::new("reports", &server.url(), "/job/reports/");
+
+        let mut pages = vec![];
+        for (range, numbers) in [("{0,100}", 151..251), ("{100,200}", 51..151)] {
+            pages.push(
+                server
+                    .mock("GET", "/job/reports/api/json")
+                    .match_query(mockito::Matcher::UrlEncoded(
+                        "tree".to_string(),
+                        format!("allBuilds[number,url,displayName,timestamp]{}", range),
+                    ))
+                    .with_body(builds_page(&server.url(), numbers))
+                    .create(),
+            );
+        }
+        let older = server
+            .mock("GET", "/job/reports/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "allBuilds[number,url,displayName,timestamp]{200,300}".to_string(),
+            ))
+            .expect(0)
+            .create();
+
+        let builds = job
+            .get_builds_between(&jenkins_client, 120_000, 160_000)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            builds.iter().map(|build| build.number).collect::<Vec<_>>(),
+            (120..160).rev().collect::<Vec<_>>()
+        );
+        for page in pages {
+            page.assert();
+        }
+        older.assert();
+    }
+
+    #[tokio::test]
+    async fn can_run_log_rotation() {
+        use super::Job as _;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let job = super::TestJob::new("reports", &server.url(), "/job/team/job/reports/");
+
+        let mock = server
+            .mock("POST", "/job/team/job/reports/logRotate")
+            .create();
+
+        job.run_log_rotation(&jenkins_client).await.unwrap();
+
+        mock.assert();
+    }
+
+    #[test]
+    fn can_get_job_name_from_jobs() {
+        use super::{CommonJob, JobName, ShortJob, WorkflowJob};
+
+        let short: ShortJob<WorkflowJob> = serde_json::from_value(serde_json::json!({
+            "name": "deploy",
+            "url": "http://localhost:8080/job/deploy/"
+        }))
+        .unwrap();
+        let job: CommonJob = serde_json::from_value(serde_json::json!({
+            "name": "deploy",
+            "displayName": "Deploy",
+            "url": "http://localhost:8080/job/deploy/",
+            "actions": []
+        }))
+        .unwrap();
+        let name = String::from("deploy");
+
+        assert_eq!(JobName::from(&short).0, "deploy");
+        assert_eq!(JobName::from(&job).0, "deploy");
+        assert_eq!(JobName::from(&name).0, "deploy");
+    }
+
+    #[test]
+    fn can_read_health_report_of_common_job() {
+        let job: super::CommonJob = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.model.FreeStyleProject",
+            "name": "reports",
+            "displayName": "reports",
+            "url": "http://localhost/job/reports/",
+            "actions": [],
+            "buildable": true,
+            "color": "blue",
+            "description": "Monthly reports",
+            "healthReport": [{
+                "description": "Build stability: No recent builds failed.",
+                "iconClassName": "icon-health-80plus",
+                "iconUrl": "health-80plus.png",
+                "score": 100
+            }],
+            "property": [{
+                "_class": "hudson.plugins.throttleconcurrents.ThrottleJobProperty",
+                "maxConcurrentTotal": 1
+            }],
+            "nextBuildNumber": 12
+        }))
+        .unwrap();
+
+        assert_eq!(job.health_report[0].score, 100);
+        assert_eq!(job.description.as_deref(), Some("Monthly reports"));
+        assert_eq!(job.next_build_number, Some(12));
+        assert_eq!(
+            job.property[0]
+                .as_variant::<crate::property::ThrottleJobProperty>()
+                .unwrap()
+                .max_concurrent_total,
+            1
+        );
+    }
+
+    fn short_job(name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "_class": "hudson.model.FreeStyleProject",
+            "name": name,
+            "url": format!("http://jenkins/job/{}/", name),
+            "color": "blue"
+        })
+    }
+
+    #[tokio::test]
+    async fn can_page_through_jobs() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let first = server
+            .mock("GET", "/api/json")
+            .match_query(mockito::Matcher::Regex(
+                r"jobs%5B.*%5D%7B0%2C2%7D".to_string(),
+            ))
+            .with_body(
+                serde_json::json!({ "jobs": [short_job("api"), short_job("web")] }).to_string(),
+            )
+            .create();
+        let second = server
+            .mock("GET", "/api/json")
+            .match_query(mockito::Matcher::Regex(
+                r"jobs%5B.*%5D%7B2%2C4%7D".to_string(),
+            ))
+            .with_body(serde_json::json!({ "jobs": [short_job("worker")] }).to_string())
+            .create();
+
+        let mut names = vec![];
+        let mut cursor = Some(crate::client::Cursor::first(2));
+        while let Some(current) = cursor {
+            let page = jenkins_client.get_jobs_at(current).await.unwrap();
+            cursor = page.next_page();
+            names.extend(page.into_iter().map(|job| job.name));
+        }
+
+        assert_eq!(names, vec!["api", "web", "worker"]);
+        first.assert();
+        second.assert();
+    }
+}
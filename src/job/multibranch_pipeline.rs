@@ -1,15 +1,19 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
-use super::Job;
+use super::branch_source::{self, BranchSource, SCMNavigator};
+use super::{HealthReport, Job};
 use crate::action::CommonAction;
 use crate::build::{CommonBuild, ShortBuild};
+use crate::client::Result;
 use crate::job::ShortJob;
+use crate::property::CommonProperty;
+use crate::Jenkins;
 
 job_base_with_common_fields_and_impl!(
     /// A pipeline project
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct WorkflowMultiBranchProject {
         /// List of the jobs in the pipline
@@ -18,4 +22,27 @@ job_base_with_common_fields_and_impl!(
 );
 register_class!("org.jenkinsci.plugins.workflow.multibranch.WorkflowMultiBranchProject" => WorkflowMultiBranchProject);
 
-impl WorkflowMultiBranchProject {}
+impl WorkflowMultiBranchProject {
+    /// Get the branch sources configured for this project
+    pub async fn get_branch_sources(&self, jenkins_client: &Jenkins) -> Result<Vec<BranchSource>> {
+        branch_source::parse_branch_sources(&self.get_config_xml(jenkins_client).await?)
+    }
+}
+
+job_base_with_common_fields_and_impl!(
+    /// An organization folder, creating multibranch projects for repositories of an organization
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct OrganizationFolder {
+        /// List of the multibranch projects in the folder
+        pub jobs: Vec<ShortJob>,
+    }
+);
+register_class!("jenkins.branch.OrganizationFolder" => OrganizationFolder);
+
+impl OrganizationFolder {
+    /// Get the SCM navigators configured for this folder
+    pub async fn get_navigators(&self, jenkins_client: &Jenkins) -> Result<Vec<SCMNavigator>> {
+        branch_source::parse_navigators(&self.get_config_xml(jenkins_client).await?)
+    }
+}
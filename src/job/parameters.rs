@@ -0,0 +1,121 @@
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+/// Value of a parameter of a build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParameterValue {
+    /// Value of a string, text, choice, password or run parameter
+    String(String),
+    /// Value of a boolean parameter
+    Boolean(bool),
+}
+
+impl ParameterValue {
+    pub(crate) fn as_form_value(&self) -> &str {
+        match self {
+            ParameterValue::String(value) => value,
+            ParameterValue::Boolean(true) => "true",
+            ParameterValue::Boolean(false) => "false",
+        }
+    }
+}
+
+impl<'a> From<&'a str> for ParameterValue {
+    fn from(value: &'a str) -> Self {
+        ParameterValue::String(value.to_string())
+    }
+}
+impl From<String> for ParameterValue {
+    fn from(value: String) -> Self {
+        ParameterValue::String(value)
+    }
+}
+impl From<bool> for ParameterValue {
+    fn from(value: bool) -> Self {
+        ParameterValue::Boolean(value)
+    }
+}
+macro_rules! into_parametervalue {
+    ($type_from:ty) => {
+        impl From<$type_from> for ParameterValue {
+            fn from(value: $type_from) -> Self {
+                ParameterValue::String(value.to_string())
+            }
+        }
+    };
+}
+into_parametervalue!(u32);
+into_parametervalue!(u64);
+into_parametervalue!(i32);
+into_parametervalue!(i64);
+
+/// Parameters of a build, form-encoded when triggering it
+///
+/// Names and values are encoded separately, so they can contain any character, including `&`,
+/// `=`, newlines or unicode.
+///
+/// ```rust
+/// # use jenkins_api::job::BuildParameters;
+/// let parameters = BuildParameters::new()
+///     .with("VERSION", "1.2")
+///     .with("DRY_RUN", true)
+///     .with("NOTES", "fixes & improvements");
+/// assert_eq!(
+///     parameters.to_form(),
+///     "VERSION=1.2&DRY_RUN=true&NOTES=fixes+%26+improvements"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildParameters {
+    parameters: Vec<(String, ParameterValue)>,
+}
+
+impl BuildParameters {
+    /// Create an empty list of parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter
+    pub fn with<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<ParameterValue>,
+    {
+        self.parameters.push((name.into(), value.into()));
+        self
+    }
+
+    /// Is there no parameter?
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Iterate over the parameters, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ParameterValue)> {
+        self.parameters
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Encode the parameters as an `application/x-www-form-urlencoded` body
+    pub fn to_form(&self) -> String {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        for (name, value) in &self.parameters {
+            let _ = serializer.append_pair(name, value.as_form_value());
+        }
+        serializer.finish()
+    }
+}
+
+impl Serialize for BuildParameters {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.parameters.len()))?;
+        for (name, value) in &self.parameters {
+            seq.serialize_element(&(name, value.as_form_value()))?;
+        }
+        seq.end()
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::Class;
 
@@ -12,15 +12,106 @@ use super::{BallColor, HealthReport};
 
 job_buildable_with_common_fields_and_impl!(
     /// A pipeline project
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct WorkflowJob<BuildType = WorkflowRun> {
         /// Description of the job
+        #[serde(default, deserialize_with = "crate::helpers::default_on_null")]
         pub description: String,
-        /// Is concurrent build enabled for the job?
+        /// Is concurrent build enabled for the job? Not provided by older versions of the
+        /// Pipeline plugin
+        #[serde(default)]
         pub concurrent_build: bool,
+        /// Is the job disabled, not provided by older versions of the Pipeline plugin
+        #[serde(default)]
+        pub disabled: bool,
+        /// Is resuming the builds of the job after a restart of Jenkins blocked
+        #[serde(default)]
+        pub resume_blocked: bool,
     }
 );
 register_class!("org.jenkinsci.plugins.workflow.job.WorkflowJob" => WorkflowJob);
 
 impl BuildableJob for WorkflowJob {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_pipeline() {
+        let job: WorkflowJob = serde_json::from_value(serde_json::json!({
+            "_class": "org.jenkinsci.plugins.workflow.job.WorkflowJob",
+            "actions": [{}, {"_class": "hudson.plugins.jobConfigHistory.JobConfigHistoryProjectAction"}],
+            "description": "Deploys the app",
+            "displayName": "deploy",
+            "displayNameOrNull": null,
+            "fullDisplayName": "team » deploy",
+            "fullName": "team/deploy",
+            "name": "deploy",
+            "url": "http://jenkins/job/team/job/deploy/",
+            "buildable": false,
+            "builds": [],
+            "color": "disabled",
+            "firstBuild": null,
+            "healthReport": [{
+                "description": "Build stability: No recent builds failed.",
+                "iconClassName": "icon-health-80plus",
+                "iconUrl": "health-80plus.png",
+                "score": 100
+            }],
+            "inQueue": false,
+            "keepDependencies": false,
+            "lastBuild": null,
+            "lastCompletedBuild": null,
+            "lastFailedBuild": null,
+            "lastStableBuild": null,
+            "lastSuccessfulBuild": null,
+            "lastUnstableBuild": null,
+            "lastUnsuccessfulBuild": null,
+            "nextBuildNumber": 1,
+            "property": [],
+            "queueItem": null,
+            "concurrentBuild": true,
+            "disabled": true,
+            "resumeBlocked": false
+        }))
+        .unwrap();
+
+        assert_eq!(job.full_name.as_deref(), Some("team/deploy"));
+        assert!(job.concurrent_build);
+        assert!(job.disabled);
+        assert!(!job.resume_blocked);
+        assert_eq!(job.health_report[0].icon_class_name, "icon-health-80plus");
+    }
+
+    #[test]
+    fn can_parse_pipeline_without_optional_fields() {
+        let job: WorkflowJob = serde_json::from_value(serde_json::json!({
+            "_class": "org.jenkinsci.plugins.workflow.job.WorkflowJob",
+            "name": "legacy",
+            "displayName": "legacy",
+            "url": "http://jenkins/job/legacy/",
+            "actions": [],
+            "description": null,
+            "buildable": true,
+            "builds": [],
+            "color": "blue",
+            "healthReport": [{
+                "description": "Build stability: No recent builds failed.",
+                "iconUrl": "health-80plus.png",
+                "score": 100
+            }],
+            "inQueue": false,
+            "keepDependencies": false,
+            "nextBuildNumber": 4,
+            "property": []
+        }))
+        .unwrap();
+
+        assert_eq!(job.description, "");
+        assert!(!job.concurrent_build);
+        assert!(!job.disabled);
+        assert_eq!(job.health_report[0].icon_class_name, "");
+    }
+}
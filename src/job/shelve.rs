@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use crate::client_internals::{Path, ResponseExt, Result};
+use crate::Jenkins;
+
+/// A project shelved with the Shelve Project plugin
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShelvedProject {
+    /// Name of the project
+    pub project_name: String,
+    /// Name of the archive of the project
+    pub archive: String,
+    /// When was the project shelved
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShelvedProjects {
+    shelved_projects: Vec<ShelvedProject>,
+}
+
+impl Jenkins {
+    /// Get the projects shelved with the Shelve Project plugin
+    pub async fn get_shelved_projects(&self) -> Result<Vec<ShelvedProject>> {
+        let response: ShelvedProjects =
+            self.get(&Path::ShelvedProjects).await?.parse_json().await?;
+        Ok(response.shelved_projects)
+    }
+
+    /// Restore a shelved project
+    pub async fn unshelve_project(&self, project: &ShelvedProject) -> Result<()> {
+        self.manage_shelved_project(project, "unshelve").await
+    }
+
+    /// Delete the archive of a shelved project, it can't be restored after
+    pub async fn delete_shelved_project(&self, project: &ShelvedProject) -> Result<()> {
+        self.manage_shelved_project(project, "delete").await
+    }
+
+    async fn manage_shelved_project(&self, project: &ShelvedProject, action: &str) -> Result<()> {
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("archives", &project.archive)
+            .append_pair(action, action)
+            .finish();
+        let _ = self
+            .post_with_body(&Path::ManageShelvedProject, body, &[])
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_shelve_and_unshelve_project() {
+        use crate::job::Job as _;
+
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let shelve = server
+            .mock("POST", "/job/dormant/shelve/shelveProject")
+            .create();
+        let list = server
+            .mock("GET", "/shelvedProjects/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                r#"{"shelvedProjects":[{"projectName":"dormant","archive":"dormant-1600000000000.zip","timestamp":1600000000000}]}"#,
+            )
+            .create();
+        let unshelve = server
+            .mock("POST", "/shelvedProjects/manageShelvedProject")
+            .match_body("archives=dormant-1600000000000.zip&unshelve=unshelve")
+            .create();
+
+        crate::job::TestJob::new("dormant", &server.url(), "/job/dormant/")
+            .shelve(&jenkins_client)
+            .await
+            .unwrap();
+        let shelved = jenkins_client.get_shelved_projects().await.unwrap();
+        assert_eq!(shelved.len(), 1);
+        assert_eq!(shelved[0].project_name, "dormant");
+        jenkins_client.unshelve_project(&shelved[0]).await.unwrap();
+
+        shelve.assert();
+        list.assert();
+        unshelve.assert();
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::common::job_path;
+use crate::client::Result;
+use crate::client_internals::{Path, ResponseExt};
+use crate::Jenkins;
+
+/// Status of a test in a build, as reported by the Test Results Analyzer plugin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TestStatus {
+    /// The test passed
+    Passed,
+    /// The test failed
+    Failed,
+    /// The test was skipped
+    Skipped,
+    /// The test was not run in this build
+    #[serde(rename = "N/A")]
+    NotRun,
+    /// Unknown status
+    #[serde(other)]
+    Unknown,
+}
+
+/// Results of a test across builds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestHistory {
+    /// Full name of the test: package, class and test case separated by `.`
+    pub name: String,
+    /// Status of the test in each build, by build number
+    pub results: BTreeMap<u32, TestStatus>,
+}
+
+impl TestHistory {
+    /// Number of times the status of the test changed between passed and failed, from the oldest
+    /// build to the most recent one. Builds where the test didn't run are ignored
+    pub fn flips(&self) -> usize {
+        let statuses = self
+            .results
+            .values()
+            .filter(|status| matches!(status, TestStatus::Passed | TestStatus::Failed))
+            .collect::<Vec<_>>();
+        statuses
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .count()
+    }
+
+    /// Does the test have both passed and failed in the builds
+    pub fn is_flaky(&self) -> bool {
+        self.flips() > 0
+    }
+}
+
+/// Matrix of the results of the tests of a job across its recent builds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestResultsMatrix {
+    /// Numbers of the builds in the matrix
+    pub builds: Vec<u32>,
+    /// Results of each test case, ordered by name
+    pub tests: Vec<TestHistory>,
+}
+
+fn number_or_string<'de, D>(deserializer: D) -> std::result::Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u32),
+        String(String),
+    }
+
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(number) => Ok(number),
+        NumberOrString::String(number) => number.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBuildResult {
+    #[serde(deserialize_with = "number_or_string")]
+    build_number: u32,
+    status: TestStatus,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawNode {
+    text: String,
+    #[serde(default)]
+    build_results: Vec<RawBuildResult>,
+    #[serde(default)]
+    children: Vec<RawNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawTree {
+    #[serde(default)]
+    builds: Vec<serde_json::Value>,
+    #[serde(default)]
+    results: Vec<RawNode>,
+}
+
+/// Flatten the tree of packages, classes and test cases, keeping test cases
+fn collect_tests(node: RawNode, prefix: &str, tests: &mut Vec<TestHistory>) {
+    let name = if prefix.is_empty() {
+        node.text
+    } else {
+        format!("{}.{}", prefix, node.text)
+    };
+    if node.children.is_empty() {
+        tests.push(TestHistory {
+            name,
+            results: node
+                .build_results
+                .into_iter()
+                .map(|result| (result.build_number, result.status))
+                .collect(),
+        });
+    } else {
+        for child in node.children {
+            collect_tests(child, &name, tests);
+        }
+    }
+}
+
+impl From<RawTree> for TestResultsMatrix {
+    fn from(tree: RawTree) -> Self {
+        let mut tests = vec![];
+        for node in tree.results {
+            collect_tests(node, "", &mut tests);
+        }
+        tests.sort_by(|a, b| a.name.cmp(&b.name));
+        TestResultsMatrix {
+            builds: tree
+                .builds
+                .iter()
+                .filter_map(|build| match build {
+                    serde_json::Value::Number(number) => number.as_u64(),
+                    serde_json::Value::String(number) => number.parse().ok(),
+                    _ => None,
+                })
+                .map(|number| number as u32)
+                .collect(),
+            tests,
+        }
+    }
+}
+
+/// Get the results of the tests of the job at `url` in its `builds` most recent builds
+pub(crate) async fn get_test_results_matrix(
+    jenkins_client: &Jenkins,
+    url: &str,
+    builds: u32,
+) -> Result<TestResultsMatrix> {
+    let job = job_path(jenkins_client, url)?;
+    let tree: RawTree = jenkins_client
+        .post_stapler_method(
+            &Path::TestResultsAnalyzer {
+                job: Box::new(job),
+                method: "getTreeResult",
+            },
+            &[builds.to_string()],
+        )
+        .await?
+        .parse_json()
+        .await?;
+    Ok(tree.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_test_results_matrix() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+        let url = format!("{}/job/app/", server.url());
+
+        let mock = server
+            .mock("POST", "/job/app/test_results_analyzer/getTreeResult")
+            .match_header(
+                "content-type",
+                "application/x-stapler-method-invocation;charset=UTF-8",
+            )
+            .match_body(r#"["3"]"#)
+            .with_body(
+                serde_json::json!({
+                    "builds": ["12", "11", "10"],
+                    "results": [{
+                        "text": "com.example",
+                        "type": "package",
+                        "buildResults": [],
+                        "children": [{
+                            "text": "ParserTest",
+                            "type": "class",
+                            "children": [
+                                {
+                                    "text": "parsesEmpty",
+                                    "buildResults": [
+                                        {"buildNumber": "12", "status": "PASSED"},
+                                        {"buildNumber": "11", "status": "FAILED"},
+                                        {"buildNumber": "10", "status": "PASSED"}
+                                    ]
+                                },
+                                {
+                                    "text": "parsesLarge",
+                                    "buildResults": [
+                                        {"buildNumber": 12, "status": "PASSED"},
+                                        {"buildNumber": 11, "status": "N/A"},
+                                        {"buildNumber": 10, "status": "PASSED"}
+                                    ]
+                                }
+                            ]
+                        }]
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let matrix = get_test_results_matrix(&jenkins_client, &url, 3)
+            .await
+            .unwrap();
+
+        assert_eq!(matrix.builds, vec![12, 11, 10]);
+        assert_eq!(
+            matrix
+                .tests
+                .iter()
+                .map(|test| (test.name.as_str(), test.flips()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("com.example.ParserTest.parsesEmpty", 2),
+                ("com.example.ParserTest.parsesLarge", 0)
+            ]
+        );
+        assert_eq!(matrix.tests[1].results[&11], TestStatus::NotRun);
+        mock.assert();
+    }
+}
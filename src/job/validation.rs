@@ -0,0 +1,136 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::client_internals::{Path, Result};
+use crate::Jenkins;
+
+/// Result of the validation of a value of a form by Jenkins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormValidation {
+    /// The value is valid
+    Ok,
+    /// The value is valid, with a warning
+    Warning {
+        /// Warning message
+        message: String,
+    },
+    /// The value is invalid
+    Error {
+        /// Error message
+        message: String,
+    },
+}
+
+static VALIDATION_CLASS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*<div class=['"]?(error|warning)"#).unwrap());
+static HTML_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+impl FormValidation {
+    /// Parse the HTML fragment returned by the `check*` endpoints of Jenkins
+    fn from_html(html: &str) -> Self {
+        let message = || {
+            HTML_TAG
+                .replace_all(html, "")
+                .replace("&nbsp;", " ")
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&#39;", "'")
+                .replace("&lsquo;", "\u{2018}")
+                .replace("&rsquo;", "\u{2019}")
+                .replace("&amp;", "&")
+                .trim()
+                .to_string()
+        };
+        match VALIDATION_CLASS
+            .captures(html)
+            .and_then(|captures| captures.get(1))
+        {
+            Some(kind) if kind.as_str() == "error" => FormValidation::Error { message: message() },
+            Some(_) => FormValidation::Warning { message: message() },
+            None => FormValidation::Ok,
+        }
+    }
+}
+
+impl Jenkins {
+    /// Check if a job can be created with the name `name`
+    pub async fn check_job_name(&self, name: &str) -> Result<FormValidation> {
+        let html = self
+            .get_endpoint_with_params(&Path::CheckJobName, [("value", name)])
+            .await?
+            .text()
+            .await?;
+        Ok(FormValidation::from_html(&html))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_check_job_name() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let _valid = server
+            .mock("GET", "/checkJobName")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "value".to_string(),
+                "deploy".to_string(),
+            ))
+            .with_body("<div/>")
+            .create();
+        let _invalid = server
+            .mock("GET", "/checkJobName")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "value".to_string(),
+                "a/b".to_string(),
+            ))
+            .with_body(
+                "<div class=error><img src='/static/abc/images/none.gif' height=16 width=1>&lsquo;/&rsquo; is an unsafe character</div>",
+            )
+            .create();
+        let _existing = server
+            .mock("GET", "/checkJobName")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "value".to_string(),
+                "build".to_string(),
+            ))
+            .with_body(
+                r#"<div class="error">A job already exists with the name &#39;build&#39;</div>"#,
+            )
+            .create();
+
+        assert_eq!(
+            jenkins_client.check_job_name("deploy").await.unwrap(),
+            FormValidation::Ok
+        );
+        assert_eq!(
+            jenkins_client.check_job_name("build").await.unwrap(),
+            FormValidation::Error {
+                message: "A job already exists with the name 'build'".to_string()
+            }
+        );
+        assert_eq!(
+            jenkins_client.check_job_name("a/b").await.unwrap(),
+            FormValidation::Error {
+                message: "\u{2018}/\u{2019} is an unsafe character".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn can_parse_warning() {
+        assert_eq!(
+            FormValidation::from_html(
+                "<div class=warning><img src='/static/abc/images/none.gif'>Name is long</div>"
+            ),
+            FormValidation::Warning {
+                message: "Name is long".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Walk through the jobs of all the folders of Jenkins
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::{FolderPath, JobPath};
+use crate::client::{AdvancedQuery, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, ResponseExt};
+use crate::Jenkins;
+
+/// A folder, or the root of Jenkins, met while walking through the jobs
+#[derive(Debug)]
+pub(crate) struct WalkedFolder<F, J> {
+    /// Path of the folder, empty for the root of Jenkins
+    pub(crate) path: FolderPath,
+    /// Fields requested for the folder
+    pub(crate) fields: F,
+    /// Jobs of the folder, including its sub folders
+    pub(crate) jobs: Vec<WalkedJob<J>>,
+}
+
+/// A job met while walking through the jobs
+#[derive(Debug)]
+pub(crate) struct WalkedJob<J> {
+    /// Path of the job
+    pub(crate) path: JobPath,
+    /// Is the job a folder, that is walked through too
+    pub(crate) is_folder: bool,
+    /// Fields requested for the job
+    pub(crate) fields: J,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound = "F: DeserializeOwned, J: DeserializeOwned")]
+struct RawFolder<F, J> {
+    #[serde(default = "Vec::new")]
+    jobs: Vec<RawJob<J>>,
+    #[serde(flatten)]
+    fields: F,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound = "J: DeserializeOwned")]
+struct RawJob<J> {
+    name: String,
+    jobs: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    fields: J,
+}
+
+impl Jenkins {
+    /// Walk through all the folders from the root of Jenkins, with one tree query per folder
+    ///
+    /// `folder_fields` adds the fields to get for each folder to the tree, parsed as `F`, and
+    /// `job_fields` the fields to get for each job to the `jobs` object, parsed as `J`. The
+    /// name of the jobs and if they are folders are always requested.
+    pub(crate) async fn walk_folders<F, J>(
+        &self,
+        folder_fields: fn(TreeBuilder) -> TreeBuilder,
+        job_fields: fn(TreeBuilder) -> TreeBuilder,
+    ) -> Result<Vec<WalkedFolder<F, J>>>
+    where
+        F: DeserializeOwned,
+        J: DeserializeOwned,
+    {
+        let mut folders = vec![];
+        let mut pending = vec![FolderPath::new()];
+        while let Some(path) = pending.pop() {
+            let tree = folder_fields(
+                TreeBuilder::new().with_field(job_fields(
+                    TreeBuilder::object("jobs")
+                        .with_subfield("name")
+                        .with_subfield(TreeBuilder::object("jobs").with_subfield("name")),
+                )),
+            )
+            .build();
+            let folder: RawFolder<F, J> = self
+                .get_with_params(
+                    &path.to_folder_path(),
+                    InternalAdvancedQueryParams::from(AdvancedQuery::Tree(tree)),
+                )
+                .await?
+                .parse_json()
+                .await?;
+
+            let jobs = folder
+                .jobs
+                .into_iter()
+                .map(|job| {
+                    let job_path = path.clone().job(&job.name);
+                    if job.jobs.is_some() {
+                        pending.push(FolderPath::from(&job_path));
+                    }
+                    WalkedJob {
+                        path: job_path,
+                        is_folder: job.jobs.is_some(),
+                        fields: job.fields,
+                    }
+                })
+                .collect();
+            folders.push(WalkedFolder {
+                path,
+                fields: folder.fields,
+                jobs,
+            });
+        }
+        Ok(folders)
+    }
+}
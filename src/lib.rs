@@ -25,7 +25,7 @@
 //! use jenkins_api::JenkinsBuilder;
 //!
 //! #[tokio::main(flavor = "current_thread")]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 //!     let jenkins = JenkinsBuilder::new("http://localhost:8080")
 //!         .with_user("user", Some("password"))
 //!         .build()?;
@@ -51,11 +51,27 @@ pub mod helpers;
 pub mod action;
 pub mod build;
 pub mod changeset;
+pub mod clock;
+pub mod description;
+pub mod disk_usage;
+pub mod export;
+pub mod failure_cause;
+#[cfg(feature = "feeds")]
+pub mod feed;
 pub mod home;
+pub mod identity;
+pub mod item_category;
 pub mod job;
+pub mod matrix_auth;
 pub mod nodes;
+pub mod notification;
+pub mod poll;
 pub mod property;
 pub mod queue;
+pub mod retention;
+pub mod role_strategy;
 pub mod scm;
+pub mod search;
+pub mod update_center;
 pub mod user;
 pub mod view;
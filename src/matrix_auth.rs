@@ -0,0 +1,320 @@
+//! Permissions granted with the Matrix Authorization Strategy plugin
+//!
+//! The matrix is read from the `config.xml` of Jenkins for global permissions, and from the
+//! `config.xml` of folders and jobs for project-based permissions.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::client_internals::{Path, Result};
+use crate::role_strategy::SidType;
+use crate::Jenkins;
+
+/// Elements holding a matrix of permissions
+const MATRIX_ELEMENTS: [&str; 3] = [
+    "authorizationStrategy",
+    "hudson.security.AuthorizationMatrixProperty",
+    "com.cloudbees.hudson.plugins.folder.properties.AuthorizationMatrixProperty",
+];
+
+/// A permission granted to a user or group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionGrant {
+    /// Name of the user or group
+    pub sid: String,
+    /// Kind of the SID
+    pub sid_type: SidType,
+    /// ID of the permission, like `hudson.model.Item.Build`
+    pub permission: String,
+}
+
+impl PermissionGrant {
+    /// Parse a permission as written in `config.xml`, like `USER:hudson.model.Item.Build:alice`,
+    /// or `hudson.model.Item.Build:alice` for older versions of the plugin
+    fn parse(value: &str) -> Option<Self> {
+        let (sid_type, value) = if let Some(value) = value.strip_prefix("USER:") {
+            (SidType::User, value)
+        } else if let Some(value) = value.strip_prefix("GROUP:") {
+            (SidType::Group, value)
+        } else {
+            (SidType::Either, value)
+        };
+        let (permission, sid) = value.split_once(':')?;
+        Some(PermissionGrant {
+            sid: sid.to_string(),
+            sid_type,
+            permission: permission.to_string(),
+        })
+    }
+}
+
+/// Matrix of permissions of Jenkins, a folder or a job
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MatrixAuthorization {
+    /// Class of the strategy deciding if permissions are inherited from the parent, for folders
+    /// and jobs
+    pub inheritance_strategy: Option<String>,
+    /// Permissions granted
+    pub grants: Vec<PermissionGrant>,
+}
+
+impl MatrixAuthorization {
+    /// Read the matrix of permissions from a `config.xml`, if there is one
+    ///
+    /// Both the `<permission>TYPE:permission:sid</permission>` format and the
+    /// `<entry><user sid="...">` format of version 3.2 and later of the plugin are read.
+    pub fn from_config_xml(config_xml: &str) -> Result<Option<Self>> {
+        let mut reader = Reader::from_str(config_xml);
+        reader.config_mut().trim_text(true);
+
+        let mut matrix: Option<MatrixAuthorization> = None;
+        // depth of the element holding the matrix
+        let mut matrix_depth = None;
+        // SID of the `<user>`, `<group>` or `<either>` element of an entry being read
+        let mut entry_sid: Option<(String, SidType)> = None;
+        let mut parents: Vec<String> = vec![];
+        let mut text = String::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) => {
+                    let name = std::str::from_utf8(element.name().as_ref())?.to_string();
+                    if matrix_depth.is_none() && MATRIX_ELEMENTS.contains(&name.as_str()) {
+                        let is_matrix = match element.try_get_attribute("class")? {
+                            Some(class) => class.unescape_value()?.contains("Matrix"),
+                            None => name != "authorizationStrategy",
+                        };
+                        if is_matrix {
+                            matrix_depth = Some(parents.len());
+                            let _ = matrix.get_or_insert_with(Default::default);
+                        }
+                    }
+                    if matrix_depth.is_some() {
+                        let sid_type = match name.as_str() {
+                            "user" => Some(SidType::User),
+                            "group" => Some(SidType::Group),
+                            "either" => Some(SidType::Either),
+                            _ => None,
+                        };
+                        if let (Some(sid_type), Some(sid)) =
+                            (sid_type, element.try_get_attribute("sid")?)
+                        {
+                            entry_sid = Some((sid.unescape_value()?.into_owned(), sid_type));
+                        }
+                    }
+                    parents.push(name);
+                    text.clear();
+                }
+                Event::Empty(element)
+                    if matrix_depth.is_some()
+                        && element.name().as_ref() == b"inheritanceStrategy" =>
+                {
+                    if let (Some(matrix), Some(class)) =
+                        (matrix.as_mut(), element.try_get_attribute("class")?)
+                    {
+                        matrix.inheritance_strategy = Some(class.unescape_value()?.into_owned());
+                    }
+                }
+                Event::Text(content) => text.push_str(&content.unescape()?),
+                Event::End(_) => {
+                    let name = parents.pop().unwrap_or_default();
+                    if matrix_depth == Some(parents.len()) {
+                        matrix_depth = None;
+                    } else if matrix_depth.is_some() && name == "permission" {
+                        let grant = match entry_sid {
+                            Some((ref sid, sid_type)) => Some(PermissionGrant {
+                                sid: sid.clone(),
+                                sid_type,
+                                permission: text.clone(),
+                            }),
+                            None => PermissionGrant::parse(&text),
+                        };
+                        if let (Some(matrix), Some(grant)) = (matrix.as_mut(), grant) {
+                            matrix.grants.push(grant);
+                        }
+                    } else if matches!(name.as_str(), "user" | "group" | "either") {
+                        entry_sid = None;
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Get the permissions granted to the user or group `sid`
+    pub fn permissions_of<'a>(&'a self, sid: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.grants
+            .iter()
+            .filter(move |grant| grant.sid == sid)
+            .map(|grant| grant.permission.as_str())
+    }
+
+    /// Get the users and groups that are granted `permission`
+    pub fn sids_with<'a>(
+        &'a self,
+        permission: &'a str,
+    ) -> impl Iterator<Item = &'a PermissionGrant> + 'a {
+        self.grants
+            .iter()
+            .filter(move |grant| grant.permission == permission)
+    }
+}
+
+impl Jenkins {
+    /// Get the global matrix of permissions, if Jenkins uses matrix-based security
+    pub async fn get_global_matrix_authorization(&self) -> Result<Option<MatrixAuthorization>> {
+        let config_xml = self
+            .get_endpoint(&Path::Raw {
+                path: "/config.xml",
+            })
+            .await?
+            .text()
+            .await?;
+        MatrixAuthorization::from_config_xml(&config_xml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_job_matrix() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <properties>
+    <hudson.security.AuthorizationMatrixProperty>
+      <inheritanceStrategy class="org.jenkinsci.plugins.matrixauth.inheritance.NonInheritingStrategy"/>
+      <permission>USER:hudson.model.Item.Build:alice</permission>
+      <permission>GROUP:hudson.model.Item.Read:developers</permission>
+      <permission>hudson.model.Item.Read:alice</permission>
+    </hudson.security.AuthorizationMatrixProperty>
+  </properties>
+  <builders/>
+</project>"#;
+
+        let matrix = MatrixAuthorization::from_config_xml(config_xml)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            matrix.inheritance_strategy.as_deref(),
+            Some("org.jenkinsci.plugins.matrixauth.inheritance.NonInheritingStrategy")
+        );
+        assert_eq!(
+            matrix.permissions_of("alice").collect::<Vec<_>>(),
+            vec!["hudson.model.Item.Build", "hudson.model.Item.Read"]
+        );
+        assert_eq!(
+            matrix
+                .sids_with("hudson.model.Item.Read")
+                .map(|grant| (grant.sid.as_str(), grant.sid_type))
+                .collect::<Vec<_>>(),
+            vec![("developers", SidType::Group), ("alice", SidType::Either)]
+        );
+    }
+
+    #[test]
+    fn can_parse_matrix_entries() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<hudson>
+  <authorizationStrategy class="hudson.security.ProjectMatrixAuthorizationStrategy">
+    <permissions>
+      <entry>
+        <group sid="authenticated">
+          <permission>hudson.model.Hudson.Read</permission>
+        </group>
+      </entry>
+      <entry>
+        <user sid="alice">
+          <permission>hudson.model.Hudson.Administer</permission>
+          <permission>hudson.model.Item.Build</permission>
+        </user>
+      </entry>
+      <entry>
+        <either sid="bob">
+          <permission>hudson.model.Item.Read</permission>
+        </either>
+      </entry>
+    </permissions>
+  </authorizationStrategy>
+</hudson>"#;
+
+        let matrix = MatrixAuthorization::from_config_xml(config_xml)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            matrix.grants[0],
+            PermissionGrant {
+                sid: "authenticated".to_string(),
+                sid_type: SidType::Group,
+                permission: "hudson.model.Hudson.Read".to_string(),
+            }
+        );
+        assert_eq!(
+            matrix.permissions_of("alice").collect::<Vec<_>>(),
+            vec!["hudson.model.Hudson.Administer", "hudson.model.Item.Build"]
+        );
+        assert_eq!(
+            matrix
+                .sids_with("hudson.model.Item.Read")
+                .map(|grant| (grant.sid.as_str(), grant.sid_type))
+                .collect::<Vec<_>>(),
+            vec![("bob", SidType::Either)]
+        );
+    }
+
+    #[test]
+    fn ignores_other_authorization_strategies() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<hudson>
+  <authorizationStrategy class="hudson.security.FullControlOnceLoggedInAuthorizationStrategy">
+    <denyAnonymousReadAccess>true</denyAnonymousReadAccess>
+  </authorizationStrategy>
+</hudson>"#;
+
+        assert_eq!(
+            MatrixAuthorization::from_config_xml(config_xml).unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn can_get_global_matrix() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/config.xml")
+            .with_body(
+                r#"<?xml version='1.1' encoding='UTF-8'?>
+<hudson>
+  <authorizationStrategy class="hudson.security.ProjectMatrixAuthorizationStrategy">
+    <permission>GROUP:hudson.model.Hudson.Administer:admins</permission>
+    <permission>GROUP:hudson.model.Hudson.Read:authenticated</permission>
+  </authorizationStrategy>
+</hudson>"#,
+            )
+            .create();
+
+        let matrix = jenkins_client
+            .get_global_matrix_authorization()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            matrix.grants[0],
+            PermissionGrant {
+                sid: "admins".to_string(),
+                sid_type: SidType::Group,
+                permission: "hudson.model.Hudson.Administer".to_string(),
+            }
+        );
+        assert_eq!(matrix.grants.len(), 2);
+        mock.assert();
+    }
+}
@@ -0,0 +1,139 @@
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::labels::replace_element;
+use super::launcher::{xml_element, Launcher};
+use crate::client;
+use crate::home::Mode;
+
+const PERMANENT_AGENT_CLASS: &str = "hudson.slaves.DumbSlave";
+const ALWAYS_RETENTION_STRATEGY: &str = "hudson.slaves.RetentionStrategy$Always";
+
+/// Configuration of a permanent agent, to create or update a node
+///
+/// ```rust
+/// # use jenkins_api::nodes::PermanentAgent;
+/// # use jenkins_api::nodes::launcher::SshLauncher;
+/// let agent = PermanentAgent::new(
+///     "agent-1",
+///     "/home/jenkins",
+///     SshLauncher::new("agent-1.example.com", "agent-key"),
+/// )
+/// .with_executors(4)
+/// .with_labels(&["linux", "docker"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermanentAgent {
+    name: String,
+    description: String,
+    remote_fs: String,
+    num_executors: u32,
+    labels: Vec<String>,
+    mode: Mode,
+    launcher: Launcher,
+}
+
+impl PermanentAgent {
+    /// Agent named `name`, with one executor working in `remote_fs`, started with `launcher`
+    pub fn new<L: Into<Launcher>>(name: &str, remote_fs: &str, launcher: L) -> Self {
+        PermanentAgent {
+            name: name.to_string(),
+            description: String::new(),
+            remote_fs: remote_fs.to_string(),
+            num_executors: 1,
+            labels: vec![],
+            mode: Mode::Normal,
+            launcher: launcher.into(),
+        }
+    }
+
+    /// Name of the agent
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Description of the agent
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Number of executors of the agent
+    pub fn with_executors(mut self, num_executors: u32) -> Self {
+        self.num_executors = num_executors;
+        self
+    }
+
+    /// Labels of the agent
+    pub fn with_labels(mut self, labels: &[&str]) -> Self {
+        self.labels = labels.iter().map(|label| label.to_string()).collect();
+        self
+    }
+
+    /// Only build jobs restricted to this agent
+    pub fn exclusive(mut self) -> Self {
+        self.mode = Mode::Exclusive;
+        self
+    }
+
+    /// Replace the settings of this agent in the `config.xml` of a node, keeping its other
+    /// settings like its node properties untouched
+    pub(crate) fn apply_to_config_xml(&self, config_xml: &str) -> client::Result<String> {
+        let mode = match self.mode {
+            Mode::Normal => "NORMAL",
+            Mode::Exclusive => "EXCLUSIVE",
+        };
+        [
+            ("description", xml_element("description", &self.description)),
+            ("remoteFS", xml_element("remoteFS", &self.remote_fs)),
+            (
+                "numExecutors",
+                xml_element("numExecutors", &self.num_executors.to_string()),
+            ),
+            ("mode", xml_element("mode", mode)),
+            ("launcher", self.launcher.to_config_xml()),
+            ("label", xml_element("label", &self.labels.join(" "))),
+        ]
+        .iter()
+        .try_fold(config_xml.to_string(), |config_xml, (name, element)| {
+            replace_element(&config_xml, name, element)
+        })
+    }
+
+    /// Form expected by Jenkins to create a node
+    pub(crate) fn to_form(&self) -> serde_json::Result<String> {
+        Ok(url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("name", &self.name)
+            .append_pair("type", PERMANENT_AGENT_CLASS)
+            .append_pair("json", &serde_json::to_string(self)?)
+            .finish())
+    }
+}
+
+impl Serialize for PermanentAgent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("name", &self.name)?;
+        map.serialize_entry("nodeDescription", &self.description)?;
+        map.serialize_entry("numExecutors", &self.num_executors)?;
+        map.serialize_entry("remoteFS", &self.remote_fs)?;
+        map.serialize_entry("labelString", &self.labels.join(" "))?;
+        map.serialize_entry("mode", &self.mode)?;
+        map.serialize_entry("launcher", &self.launcher)?;
+        map.serialize_entry(
+            "retentionStrategy",
+            &serde_json::json!({
+                "stapler-class": ALWAYS_RETENTION_STRATEGY,
+                "$class": ALWAYS_RETENTION_STRATEGY,
+            }),
+        )?;
+        map.serialize_entry(
+            "nodeProperties",
+            &serde_json::json!({ "stapler-class-bag": "true" }),
+        )?;
+        map.serialize_entry("type", PERMANENT_AGENT_CLASS)?;
+        map.end()
+    }
+}
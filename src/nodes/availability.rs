@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::computer::CommonComputer;
+use super::ComputerSet;
+use crate::client_internals::Result;
+use crate::Jenkins;
+
+/// State of a node when it was sampled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvailabilitySample {
+    /// When was the node sampled, in milliseconds
+    pub timestamp: u64,
+    /// Was the node online
+    pub online: bool,
+    /// Average response time of the agent, in milliseconds
+    pub response_time: Option<u64>,
+    /// When did the agent connect to the controller
+    pub connect_time: Option<u64>,
+    /// When was the node taken offline, if it was offline
+    pub offline_since: Option<u64>,
+}
+
+impl AvailabilitySample {
+    /// Sample the state of `computer` at `timestamp`
+    pub fn of(computer: &CommonComputer, timestamp: u64) -> Self {
+        AvailabilitySample {
+            timestamp,
+            online: !computer.offline,
+            response_time: computer.response_time().map(|monitor| monitor.average),
+            connect_time: computer.connect_time.filter(|time| *time > 0),
+            offline_since: computer
+                .offline_cause
+                .as_ref()
+                .and_then(|cause| cause.timestamp),
+        }
+    }
+}
+
+/// History of the availability of nodes, built by sampling them regularly
+///
+/// Jenkins only keeps the current state of nodes and a short average of their response time, so
+/// the history is kept by the client, and should be sampled often enough for the precision
+/// needed.
+#[derive(Debug, Clone, Default)]
+pub struct AvailabilityHistory {
+    samples: BTreeMap<String, Vec<AvailabilitySample>>,
+}
+
+impl AvailabilityHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the state of all the nodes of `nodes`
+    pub fn record(&mut self, nodes: &ComputerSet, timestamp: u64) {
+        for computer in &nodes.computers {
+            self.record_node(computer, timestamp);
+        }
+    }
+
+    /// Record the state of a node
+    pub fn record_node(&mut self, computer: &CommonComputer, timestamp: u64) {
+        let samples = self
+            .samples
+            .entry(computer.display_name.clone())
+            .or_default();
+        let sample = AvailabilitySample::of(computer, timestamp);
+        let position = samples.partition_point(|other| other.timestamp <= timestamp);
+        samples.insert(position, sample);
+    }
+
+    /// Names of the nodes in the history
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.samples.keys().map(String::as_str)
+    }
+
+    /// Samples of a node, ordered by timestamp
+    pub fn samples(&self, node: &str) -> &[AvailabilitySample] {
+        self.samples.get(node).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Response times of a node, with the timestamp they were sampled at
+    pub fn response_times<'a>(&'a self, node: &str) -> impl Iterator<Item = (u64, u64)> + 'a {
+        self.samples(node).iter().filter_map(|sample| {
+            sample
+                .response_time
+                .map(|response_time| (sample.timestamp, response_time))
+        })
+    }
+
+    /// Percentage of time a node was online between `from` and `to`, considering that it stays in
+    /// the state of a sample until the next one. Returns `None` if there is no sample before `to`
+    pub fn availability(&self, node: &str, from: u64, to: u64) -> Option<f64> {
+        let samples = self.samples(node);
+        let mut online = 0;
+        let mut total = 0;
+        for (index, sample) in samples.iter().enumerate() {
+            let start = sample.timestamp.max(from);
+            let end = samples
+                .get(index + 1)
+                .map_or(to, |next| next.timestamp)
+                .min(to);
+            if end <= start {
+                continue;
+            }
+            total += end - start;
+            if sample.online {
+                online += end - start;
+            }
+        }
+        if total == 0 {
+            None
+        } else {
+            Some(online as f64 * 100.0 / total as f64)
+        }
+    }
+}
+
+impl Jenkins {
+    /// Record the current state of all nodes in `history`
+    pub async fn sample_node_availability(&self, history: &mut AvailabilityHistory) -> Result<()> {
+        let nodes = self.get_nodes().await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        history.record(&nodes, now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn computer(offline: bool, average: u64) -> CommonComputer {
+        serde_json::from_value(serde_json::json!({
+            "_class": "hudson.slaves.SlaveComputer",
+            "displayName": "agent-1",
+            "description": "",
+            "icon": "computer.png",
+            "iconClassName": "icon-computer",
+            "idle": true,
+            "jnlpAgent": false,
+            "launchSupported": true,
+            "manualLaunchAllowed": true,
+            "numExecutors": 1,
+            "offline": offline,
+            "offlineCause": null,
+            "offlineCauseReason": "",
+            "temporarilyOffline": false,
+            "connectTime": 1_600_000_000_000u64,
+            "monitorData": {
+                "hudson.node_monitors.ResponseTimeMonitor": {
+                    "_class": "hudson.node_monitors.ResponseTimeMonitor$Data",
+                    "timestamp": 1_600_000_000_000u64,
+                    "average": average
+                }
+            },
+            "executors": [],
+            "oneOffExecutors": [],
+            "assignedLabels": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn can_compute_availability() {
+        let mut history = AvailabilityHistory::new();
+        history.record_node(&computer(false, 10), 1_000);
+        history.record_node(&computer(true, 0), 4_000);
+        history.record_node(&computer(false, 30), 5_000);
+
+        assert_eq!(
+            history.response_times("agent-1").collect::<Vec<_>>(),
+            vec![(1_000, 10), (4_000, 0), (5_000, 30)]
+        );
+        assert_eq!(
+            history.samples("agent-1")[0].connect_time,
+            Some(1_600_000_000_000)
+        );
+        assert_eq!(history.availability("agent-1", 0, 9_000), Some(87.5));
+        assert_eq!(
+            history.availability("agent-1", 2_000, 5_000),
+            Some(200.0 / 3.0)
+        );
+        assert_eq!(history.availability("agent-1", 0, 1_000), None);
+        assert_eq!(history.availability("agent-2", 0, 9_000), None);
+    }
+}
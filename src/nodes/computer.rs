@@ -1,10 +1,13 @@
 //! Types to parse a `Computer`
 
 use std::collections::HashMap;
+use std::sync::LazyLock;
 
 use serde::{self, Deserialize, Serialize};
 
+use crate::client::Result;
 use crate::helpers::Class;
+use crate::Jenkins;
 
 use super::monitor;
 
@@ -46,10 +49,12 @@ macro_rules! computer_with_common_fields_and_impl {
             /// Name of the computer
             pub display_name: String,
             /// Description of the computer
+            #[serde(default, deserialize_with = "crate::helpers::default_on_null")]
             pub description: String,
             /// Icon for the computer
             pub icon: String,
-            /// Icon for the computer
+            /// Icon for the computer, not provided by older versions of Jenkins
+            #[serde(default)]
             pub icon_class_name: String,
             /// Is the computer idle
             pub idle: bool,
@@ -64,18 +69,22 @@ macro_rules! computer_with_common_fields_and_impl {
             /// Is the computer offline
             pub offline: bool,
             /// Why is the computer offline
-            pub offline_cause: Option<monitor::CommonMonitorData>,
+            pub offline_cause: Option<OfflineCause>,
             /// Why is the computer offline
             pub offline_cause_reason: Option<String>,
             /// Is the computer temporarily offline
             pub temporarily_offline: bool,
+            /// When did the agent connect to the controller, for agents that are connected
+            #[serde(default)]
+            pub connect_time: Option<u64>,
             /// Monitor data provided by the computer
             pub monitor_data: HashMap<String, monitor::Data>,
             /// Executors of the computer
             pub executors: Vec<Executor>,
             /// One off executors of the computer
             pub one_off_executors: Vec<Executor>,
-            /// Labels assigned to the computer
+            /// Labels assigned to the computer, not provided by older versions of Jenkins
+            #[serde(default)]
             pub assigned_labels: Vec<AssignedLabel>,
 
             // TODO: actions, assignedLabels, loadStatistics
@@ -114,6 +123,87 @@ computer_with_common_fields_and_impl!(/// A Jenkins `Computer`
 );
 specialize!(CommonComputer => Computer);
 
+/// `_class` of the built-in node
+pub(crate) const BUILTIN_COMPUTER_CLASS: &str = "hudson.model.Hudson$MasterComputer";
+
+impl CommonComputer {
+    /// Is this computer the built-in node of the controller
+    pub fn is_builtin(&self) -> bool {
+        self.class.as_deref() == Some(BUILTIN_COMPUTER_CLASS)
+    }
+
+    /// Name of this computer, as used in its URL
+    ///
+    /// It is the display name for agents, but the built-in node is displayed as
+    /// `Built-In Node` while being reached at `(built-in)`, or as `master` while being reached
+    /// at `(master)` before Jenkins 2.307.
+    pub fn name(&self) -> &str {
+        if !self.is_builtin() {
+            &self.display_name
+        } else if self.display_name == "master" {
+            super::LEGACY_BUILTIN_NODE_NAME
+        } else {
+            super::BUILTIN_NODE_NAME
+        }
+    }
+
+    /// Is the label `label` assigned to this computer
+    pub fn has_label(&self, label: &str) -> bool {
+        self.assigned_labels
+            .iter()
+            .any(|assigned| assigned.name == label)
+    }
+
+    /// Get the response time of the agent, as measured by the `ResponseTimeMonitor`
+    pub fn response_time(&self) -> Option<monitor::ResponseTimeMonitor> {
+        match self
+            .monitor_data
+            .get("hudson.node_monitors.ResponseTimeMonitor")
+        {
+            Some(monitor::Data::MonitorData(data)) => data.as_variant().ok(),
+            _ => None,
+        }
+    }
+
+    /// Get the difference between the clock of the agent and the clock of the controller, as
+    /// measured by the `ClockMonitor`
+    pub fn clock_difference(&self) -> Option<monitor::ClockDifference> {
+        match self.monitor_data.get("hudson.node_monitors.ClockMonitor") {
+            Some(monitor::Data::MonitorData(data)) => data.as_variant().ok(),
+            _ => None,
+        }
+    }
+
+    /// Change the message explaining why this computer is temporarily offline, without bringing
+    /// it back online, to tell users about a maintenance for example
+    pub async fn set_offline_message(&self, jenkins_client: &Jenkins, message: &str) -> Result<()> {
+        jenkins_client
+            .set_node_offline_message(self.name(), message)
+            .await
+    }
+
+    /// Mark this computer as temporarily offline with `message` explaining why. See
+    /// `Jenkins::set_node_offline`
+    pub async fn set_offline(&self, jenkins_client: &Jenkins, message: &str) -> Result<()> {
+        jenkins_client.set_node_offline(self.name(), message).await
+    }
+
+    /// Bring this computer back online if it is marked as temporarily offline
+    pub async fn set_online(&self, jenkins_client: &Jenkins) -> Result<()> {
+        jenkins_client.set_node_online(self.name()).await
+    }
+
+    /// Disconnect the agent of this computer, with `message` explaining why
+    pub async fn disconnect(&self, jenkins_client: &Jenkins, message: &str) -> Result<()> {
+        jenkins_client.disconnect_node(self.name(), message).await
+    }
+
+    /// Delete this computer
+    pub async fn delete(&self, jenkins_client: &Jenkins) -> Result<()> {
+        jenkins_client.delete_node(self.name()).await
+    }
+}
+
 computer_with_common_fields_and_impl!(
     /// The master computer
     #[derive(Serialize, Deserialize, Debug)]
@@ -171,3 +261,256 @@ pub struct AssignedLabel {
     /// Name of the label.
     pub name: String,
 }
+
+/// Why a computer was taken offline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OfflineCauseKind {
+    /// Disconnected or taken offline with the CLI
+    ByCli {
+        /// Message given when taking the computer offline
+        message: Option<String>,
+    },
+    /// Disconnected or taken offline by a user from the UI
+    User {
+        /// ID of the user
+        user: Option<String>,
+        /// Message given when taking the computer offline
+        message: Option<String>,
+    },
+    /// The connection to the agent was broken
+    ChannelTermination {
+        /// Error that broke the connection
+        error: Option<String>,
+    },
+    /// Taken offline by its retention strategy because it was idle, like cloud agents
+    Idle,
+    /// Another cause
+    Other,
+}
+
+/// Typed cause of a computer being offline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RawOfflineCause", into = "RawOfflineCause")]
+pub struct OfflineCause {
+    /// _class provided by Jenkins
+    pub class: Option<String>,
+    /// Kind of cause
+    pub kind: OfflineCauseKind,
+    /// Description of the cause, as displayed by Jenkins
+    pub description: Option<String>,
+    /// When was the computer taken offline
+    pub timestamp: Option<u64>,
+}
+
+impl OfflineCause {
+    /// Was the computer taken offline on purpose, by a user or with the CLI, rather than after
+    /// an error or by its retention strategy
+    pub fn is_deliberate(&self) -> bool {
+        matches!(
+            self.kind,
+            OfflineCauseKind::ByCli { .. } | OfflineCauseKind::User { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RawOfflineCause {
+    #[serde(rename = "_class")]
+    class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cause: Option<serde_json::Value>,
+}
+
+static DISCONNECTED_BY: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[^:]*? by (\S+)(?: : (.*))?$").unwrap());
+
+/// Get the user and message from a description like `Disconnected by alice : maintenance`
+fn user_and_message(description: Option<&str>) -> (Option<String>, Option<String>) {
+    let description = match description {
+        Some(description) => description,
+        None => return (None, None),
+    };
+    match DISCONNECTED_BY.captures(description.trim()) {
+        Some(captures) => (
+            captures.get(1).map(|user| user.as_str().to_string()),
+            captures
+                .get(2)
+                .map(|message| message.as_str().to_string())
+                .filter(|message| !message.is_empty()),
+        ),
+        None => (None, None),
+    }
+}
+
+impl From<RawOfflineCause> for OfflineCause {
+    fn from(raw: RawOfflineCause) -> Self {
+        let RawOfflineCause {
+            class,
+            description,
+            timestamp,
+            message,
+            cause,
+        } = raw;
+        let kind = match class.as_deref() {
+            Some("hudson.slaves.OfflineCause$ByCLI") => OfflineCauseKind::ByCli {
+                message: message.or_else(|| user_and_message(description.as_deref()).1),
+            },
+            Some("hudson.slaves.OfflineCause$UserCause") => {
+                let (user, message) = user_and_message(description.as_deref());
+                OfflineCauseKind::User { user, message }
+            }
+            Some("hudson.slaves.OfflineCause$ChannelTermination") => {
+                OfflineCauseKind::ChannelTermination {
+                    error: match cause {
+                        Some(serde_json::Value::String(error)) => Some(error),
+                        Some(serde_json::Value::Object(ref error)) => error
+                            .get("message")
+                            .and_then(serde_json::Value::as_str)
+                            .map(str::to_string),
+                        _ => None,
+                    }
+                    .or_else(|| description.clone()),
+                }
+            }
+            Some("hudson.slaves.OfflineCause$IdleOfflineCause") => OfflineCauseKind::Idle,
+            _ => OfflineCauseKind::Other,
+        };
+        OfflineCause {
+            class,
+            kind,
+            description,
+            timestamp,
+        }
+    }
+}
+
+impl From<OfflineCause> for RawOfflineCause {
+    fn from(cause: OfflineCause) -> Self {
+        let (message, error) = match cause.kind {
+            OfflineCauseKind::ByCli { message } => (message, None),
+            OfflineCauseKind::ChannelTermination { error } => {
+                (None, error.map(serde_json::Value::String))
+            }
+            _ => (None, None),
+        };
+        RawOfflineCause {
+            class: cause.class,
+            description: cause.description,
+            timestamp: cause.timestamp,
+            message,
+            cause: error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_computer_without_optional_fields() {
+        let computer: CommonComputer = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.slaves.SlaveComputer",
+            "actions": [],
+            "description": null,
+            "displayName": "agent-1",
+            "executors": [{}],
+            "icon": "computer.png",
+            "idle": true,
+            "jnlpAgent": true,
+            "launchSupported": false,
+            "loadStatistics": {},
+            "manualLaunchAllowed": true,
+            "monitorData": {},
+            "numExecutors": 1,
+            "offline": false,
+            "offlineCause": null,
+            "offlineCauseReason": "",
+            "oneOffExecutors": [],
+            "temporarilyOffline": false
+        }))
+        .unwrap();
+
+        assert_eq!(computer.description, "");
+        assert!(computer.assigned_labels.is_empty());
+        assert!(!computer.has_label("linux"));
+    }
+
+    #[test]
+    fn can_parse_offline_causes() {
+        let causes: Vec<OfflineCause> = serde_json::from_str(
+            r#"[
+                {"_class":"hudson.slaves.OfflineCause$UserCause","description":"Disconnected by alice : disk replacement","timestamp":1600000000000},
+                {"_class":"hudson.slaves.OfflineCause$ByCLI","description":"Disconnected by bob : upgrade","message":"upgrade","timestamp":1600000000000},
+                {"_class":"hudson.slaves.OfflineCause$ChannelTermination","cause":{"message":"Connection reset"},"timestamp":1600000000000},
+                {"_class":"hudson.slaves.OfflineCause$IdleOfflineCause","description":"Offline due to idle"},
+                {"_class":"hudson.slaves.OfflineCause$LaunchFailed","description":"This agent is offline because Jenkins failed to launch the agent process on it."}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            causes.iter().map(|cause| &cause.kind).collect::<Vec<_>>(),
+            vec![
+                &OfflineCauseKind::User {
+                    user: Some("alice".to_string()),
+                    message: Some("disk replacement".to_string()),
+                },
+                &OfflineCauseKind::ByCli {
+                    message: Some("upgrade".to_string()),
+                },
+                &OfflineCauseKind::ChannelTermination {
+                    error: Some("Connection reset".to_string()),
+                },
+                &OfflineCauseKind::Idle,
+                &OfflineCauseKind::Other,
+            ]
+        );
+        assert_eq!(
+            causes
+                .iter()
+                .map(OfflineCause::is_deliberate)
+                .collect::<Vec<_>>(),
+            vec![true, true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn can_read_clock_difference() {
+        let computer: CommonComputer = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.slaves.SlaveComputer",
+            "displayName": "agent-1",
+            "executors": [],
+            "icon": "computer.png",
+            "idle": true,
+            "jnlpAgent": true,
+            "launchSupported": false,
+            "manualLaunchAllowed": true,
+            "monitorData": {
+                "hudson.node_monitors.ClockMonitor": {
+                    "_class": "hudson.util.ClockDifference",
+                    "diff": -1200
+                },
+                "hudson.node_monitors.ResponseTimeMonitor": {
+                    "_class": "hudson.node_monitors.ResponseTimeMonitor$Data",
+                    "timestamp": 1_600_000_000_000u64,
+                    "average": 42
+                }
+            },
+            "numExecutors": 1,
+            "offline": false,
+            "oneOffExecutors": [],
+            "temporarilyOffline": false
+        }))
+        .unwrap();
+
+        assert_eq!(computer.clock_difference().unwrap().diff, -1200);
+        assert_eq!(computer.response_time().unwrap().average, 42);
+    }
+}
@@ -0,0 +1,149 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use super::computer::ComputerName;
+use crate::client_internals::{Name, Path, Result};
+use crate::Jenkins;
+
+/// Replace the `<label>` of the `config.xml` of a node, adding it if it's missing, and keeping
+/// the rest of the document untouched
+fn replace_label(config_xml: &str, label: &str) -> Result<String> {
+    replace_element(
+        config_xml,
+        "label",
+        &format!("<label>{}</label>", quick_xml::escape::escape(label)),
+    )
+}
+
+/// Replace the top level element `name` of the `config.xml` of a node with `new_element`,
+/// adding it if it's missing, and keeping the rest of the document untouched
+pub(super) fn replace_element(config_xml: &str, name: &str, new_element: &str) -> Result<String> {
+    let mut reader = Reader::from_str(config_xml);
+
+    let mut depth = 0;
+    let mut label_start = None;
+    loop {
+        let before = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Start(element) => {
+                if depth == 1 && element.name().as_ref() == name.as_bytes() {
+                    label_start = Some(before);
+                }
+                depth += 1;
+            }
+            Event::Empty(element) if depth == 1 && element.name().as_ref() == name.as_bytes() => {
+                let after = reader.buffer_position() as usize;
+                return Ok(format!(
+                    "{}{}{}",
+                    &config_xml[..before],
+                    new_element,
+                    &config_xml[after..]
+                ));
+            }
+            Event::End(_) => {
+                depth -= 1;
+                match (depth, label_start) {
+                    (1, Some(start)) => {
+                        let after = reader.buffer_position() as usize;
+                        return Ok(format!(
+                            "{}{}{}",
+                            &config_xml[..start],
+                            new_element,
+                            &config_xml[after..]
+                        ));
+                    }
+                    (0, _) => {
+                        return Ok(format!(
+                            "{}  {}\n{}",
+                            &config_xml[..before],
+                            new_element,
+                            &config_xml[before..]
+                        ))
+                    }
+                    _ => (),
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(config_xml.to_string())
+}
+
+impl Jenkins {
+    /// Replace the labels of a node, leaving the rest of its configuration untouched
+    pub async fn set_node_labels<'a, C>(&self, computer_name: C, labels: &[&str]) -> Result<()>
+    where
+        C: Into<ComputerName<'a>>,
+    {
+        let name = computer_name.into().0;
+        let path = Path::ComputerConfigXML {
+            name: Name::Name(name),
+        };
+        let config_xml = self.get_endpoint(&path).await?.text().await?;
+        let config_xml = replace_label(&config_xml, &labels.join(" "))?;
+        let _ = self.post_with_xml_body(&path, config_xml, &[]).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static AGENT_CONFIG: &str = r#"<?xml version="1.1" encoding="UTF-8"?>
+<slave>
+  <name>agent-1</name>
+  <remoteFS>/home/jenkins</remoteFS>
+  <launcher class="hudson.slaves.JNLPLauncher">
+    <label>not the node label</label>
+  </launcher>
+  <label>linux</label>
+  <nodeProperties/>
+</slave>"#;
+
+    #[test]
+    fn can_replace_label() {
+        assert_eq!(
+            replace_label(AGENT_CONFIG, "linux & docker").unwrap(),
+            AGENT_CONFIG.replace("<label>linux</label>", "<label>linux &amp; docker</label>")
+        );
+        assert_eq!(
+            replace_label("<slave>\n  <label/>\n</slave>", "arm64").unwrap(),
+            "<slave>\n  <label>arm64</label>\n</slave>"
+        );
+        assert_eq!(
+            replace_label("<slave>\n  <name>agent-1</name>\n</slave>", "arm64").unwrap(),
+            "<slave>\n  <name>agent-1</name>\n  <label>arm64</label>\n</slave>"
+        );
+    }
+
+    #[tokio::test]
+    async fn can_set_node_labels() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let get = server
+            .mock("GET", "/computer/agent-1/config.xml")
+            .with_body(AGENT_CONFIG)
+            .create();
+        let post = server
+            .mock("POST", "/computer/agent-1/config.xml")
+            .match_header("content-type", "text/xml")
+            .match_body(mockito::Matcher::Regex(
+                "<label>linux docker</label>\n  <nodeProperties/>".to_string(),
+            ))
+            .create();
+
+        jenkins_client
+            .set_node_labels("agent-1", &["linux", "docker"])
+            .await
+            .unwrap();
+
+        get.assert();
+        post.assert();
+    }
+}
@@ -0,0 +1,332 @@
+//! Launchers starting the agent process of a node
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// How the SSH launcher verifies the key of the host of an agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostKeyVerification {
+    /// Check the key against the `known_hosts` file of the controller
+    KnownHostsFile,
+    /// Trust the key seen on the first connection, optionally after a manual approval
+    ManuallyTrusted {
+        /// Does the first key need to be approved by an administrator
+        require_initial_manual_trust: bool,
+    },
+    /// Check the key against the given one
+    ManuallyProvided {
+        /// Public key of the host, like `ssh-ed25519 AAAA...`
+        key: String,
+    },
+    /// Don't verify the key of the host
+    NonVerifying,
+}
+
+impl HostKeyVerification {
+    fn class(&self) -> &'static str {
+        match self {
+            HostKeyVerification::KnownHostsFile => {
+                "hudson.plugins.sshslaves.verifiers.KnownHostsFileKeyVerificationStrategy"
+            }
+            HostKeyVerification::ManuallyTrusted { .. } => {
+                "hudson.plugins.sshslaves.verifiers.ManuallyTrustedKeyVerificationStrategy"
+            }
+            HostKeyVerification::ManuallyProvided { .. } => {
+                "hudson.plugins.sshslaves.verifiers.ManuallyProvidedKeyVerificationStrategy"
+            }
+            HostKeyVerification::NonVerifying => {
+                "hudson.plugins.sshslaves.verifiers.NonVerifyingKeyVerificationStrategy"
+            }
+        }
+    }
+}
+
+impl Serialize for HostKeyVerification {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("stapler-class", self.class())?;
+        map.serialize_entry("$class", self.class())?;
+        match self {
+            HostKeyVerification::ManuallyTrusted {
+                require_initial_manual_trust,
+            } => map.serialize_entry("requireInitialManualTrust", require_initial_manual_trust)?,
+            HostKeyVerification::ManuallyProvided { key } => map.serialize_entry("key", key)?,
+            HostKeyVerification::KnownHostsFile | HostKeyVerification::NonVerifying => (),
+        }
+        map.end()
+    }
+}
+
+/// Launch an agent by connecting to it with SSH, with the SSH Build Agents plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshLauncher {
+    host: String,
+    port: u16,
+    credentials_id: String,
+    jvm_options: Option<String>,
+    java_path: Option<String>,
+    host_key_verification: HostKeyVerification,
+}
+
+impl SshLauncher {
+    /// Connect to `host` on port 22, authenticating with the credentials `credentials_id`
+    ///
+    /// The key of the host is checked against the `known_hosts` file of the controller.
+    pub fn new(host: &str, credentials_id: &str) -> Self {
+        SshLauncher {
+            host: host.to_string(),
+            port: 22,
+            credentials_id: credentials_id.to_string(),
+            jvm_options: None,
+            java_path: None,
+            host_key_verification: HostKeyVerification::KnownHostsFile,
+        }
+    }
+
+    /// Connect on another port than 22
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Options of the JVM of the agent, like `-Xmx1g`
+    pub fn with_jvm_options(mut self, jvm_options: &str) -> Self {
+        self.jvm_options = Some(jvm_options.to_string());
+        self
+    }
+
+    /// Path of `java` on the agent, when it is not in the `PATH`
+    pub fn with_java_path(mut self, java_path: &str) -> Self {
+        self.java_path = Some(java_path.to_string());
+        self
+    }
+
+    /// How to verify the key of the host
+    pub fn with_host_key_verification(mut self, verification: HostKeyVerification) -> Self {
+        self.host_key_verification = verification;
+        self
+    }
+}
+
+/// Launch an agent by letting it connect to the controller, also known as JNLP
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InboundLauncher {
+    web_socket: bool,
+    tunnel: Option<String>,
+    jvm_options: Option<String>,
+}
+
+impl InboundLauncher {
+    /// Let the agent connect to the TCP port for inbound agents
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let the agent connect with WebSocket over the HTTP port of Jenkins
+    pub fn with_web_socket(mut self) -> Self {
+        self.web_socket = true;
+        self
+    }
+
+    /// Connect through a tunnel, like `host:port`
+    pub fn with_tunnel(mut self, tunnel: &str) -> Self {
+        self.tunnel = Some(tunnel.to_string());
+        self
+    }
+
+    /// Options of the JVM of the agent, like `-Xmx1g`
+    pub fn with_jvm_options(mut self, jvm_options: &str) -> Self {
+        self.jvm_options = Some(jvm_options.to_string());
+        self
+    }
+}
+
+/// How the agent process of a node is started
+///
+/// It is serialized in the JSON form expected by the configuration page of nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Launcher {
+    /// Connect to the agent with SSH
+    Ssh(SshLauncher),
+    /// Let the agent connect to the controller
+    Inbound(InboundLauncher),
+}
+
+impl Launcher {
+    fn class(&self) -> &'static str {
+        match self {
+            Launcher::Ssh(_) => "hudson.plugins.sshslaves.SSHLauncher",
+            Launcher::Inbound(_) => "hudson.slaves.JNLPLauncher",
+        }
+    }
+}
+
+/// Element `name` of a `config.xml`, with the escaped text `value`
+pub(crate) fn xml_element(name: &str, value: &str) -> String {
+    format!("<{0}>{1}</{0}>", name, quick_xml::escape::escape(value))
+}
+
+impl HostKeyVerification {
+    /// Element of the `config.xml` of an agent for this strategy
+    fn to_config_xml(&self) -> String {
+        let fields = match self {
+            HostKeyVerification::ManuallyTrusted {
+                require_initial_manual_trust,
+            } => xml_element(
+                "requireInitialManualTrust",
+                &require_initial_manual_trust.to_string(),
+            ),
+            HostKeyVerification::ManuallyProvided { key } => {
+                // a public key like `ssh-ed25519 AAAA...` is stored as its algorithm and its
+                // base64 encoded bytes
+                let mut parts = key.split_whitespace();
+                format!(
+                    "<key>{}{}</key>",
+                    xml_element("algorithm", parts.next().unwrap_or_default()),
+                    xml_element("key", parts.next().unwrap_or_default())
+                )
+            }
+            HostKeyVerification::KnownHostsFile | HostKeyVerification::NonVerifying => {
+                String::new()
+            }
+        };
+        format!(
+            "<sshHostKeyVerificationStrategy class=\"{}\">{}</sshHostKeyVerificationStrategy>",
+            self.class(),
+            fields
+        )
+    }
+}
+
+impl Launcher {
+    /// Element of the `config.xml` of an agent for this launcher
+    pub(crate) fn to_config_xml(&self) -> String {
+        let fields = match self {
+            Launcher::Ssh(ssh) => [
+                xml_element("host", &ssh.host),
+                xml_element("port", &ssh.port.to_string()),
+                xml_element("credentialsId", &ssh.credentials_id),
+                xml_element("jvmOptions", ssh.jvm_options.as_deref().unwrap_or("")),
+                xml_element("javaPath", ssh.java_path.as_deref().unwrap_or("")),
+                ssh.host_key_verification.to_config_xml(),
+            ]
+            .concat(),
+            Launcher::Inbound(inbound) => [
+                xml_element("webSocket", &inbound.web_socket.to_string()),
+                xml_element("tunnel", inbound.tunnel.as_deref().unwrap_or("")),
+                xml_element("vmargs", inbound.jvm_options.as_deref().unwrap_or("")),
+            ]
+            .concat(),
+        };
+        format!("<launcher class=\"{}\">{}</launcher>", self.class(), fields)
+    }
+}
+
+impl From<SshLauncher> for Launcher {
+    fn from(launcher: SshLauncher) -> Self {
+        Launcher::Ssh(launcher)
+    }
+}
+impl From<InboundLauncher> for Launcher {
+    fn from(launcher: InboundLauncher) -> Self {
+        Launcher::Inbound(launcher)
+    }
+}
+
+impl Serialize for Launcher {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("stapler-class", self.class())?;
+        map.serialize_entry("$class", self.class())?;
+        match self {
+            Launcher::Ssh(ssh) => {
+                map.serialize_entry("host", &ssh.host)?;
+                map.serialize_entry("port", &ssh.port)?;
+                map.serialize_entry("credentialsId", &ssh.credentials_id)?;
+                map.serialize_entry("jvmOptions", ssh.jvm_options.as_deref().unwrap_or(""))?;
+                map.serialize_entry("javaPath", ssh.java_path.as_deref().unwrap_or(""))?;
+                map.serialize_entry("sshHostKeyVerificationStrategy", &ssh.host_key_verification)?;
+            }
+            Launcher::Inbound(inbound) => {
+                map.serialize_entry("webSocket", &inbound.web_socket)?;
+                map.serialize_entry("tunnel", inbound.tunnel.as_deref().unwrap_or(""))?;
+                map.serialize_entry("vmargs", inbound.jvm_options.as_deref().unwrap_or(""))?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_serialize_ssh_launcher() {
+        let launcher: Launcher = SshLauncher::new("agent-1.example.com", "agent-key")
+            .with_port(2222)
+            .with_jvm_options("-Xmx1g")
+            .with_host_key_verification(HostKeyVerification::ManuallyTrusted {
+                require_initial_manual_trust: false,
+            })
+            .into();
+
+        assert_eq!(
+            serde_json::to_value(&launcher).unwrap(),
+            serde_json::json!({
+                "stapler-class": "hudson.plugins.sshslaves.SSHLauncher",
+                "$class": "hudson.plugins.sshslaves.SSHLauncher",
+                "host": "agent-1.example.com",
+                "port": 2222,
+                "credentialsId": "agent-key",
+                "jvmOptions": "-Xmx1g",
+                "javaPath": "",
+                "sshHostKeyVerificationStrategy": {
+                    "stapler-class": "hudson.plugins.sshslaves.verifiers.ManuallyTrustedKeyVerificationStrategy",
+                    "$class": "hudson.plugins.sshslaves.verifiers.ManuallyTrustedKeyVerificationStrategy",
+                    "requireInitialManualTrust": false
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn can_serialize_inbound_launcher() {
+        let launcher: Launcher = InboundLauncher::new().with_web_socket().into();
+
+        assert_eq!(
+            serde_json::to_value(&launcher).unwrap(),
+            serde_json::json!({
+                "stapler-class": "hudson.slaves.JNLPLauncher",
+                "$class": "hudson.slaves.JNLPLauncher",
+                "webSocket": true,
+                "tunnel": "",
+                "vmargs": ""
+            })
+        );
+    }
+
+    #[test]
+    fn can_write_launcher_config_xml() {
+        let launcher: Launcher = SshLauncher::new("agent-1.example.com", "agent-key")
+            .with_host_key_verification(HostKeyVerification::ManuallyProvided {
+                key: "ssh-ed25519 AAAAC3Nz".to_string(),
+            })
+            .into();
+
+        assert_eq!(
+            launcher.to_config_xml(),
+            "<launcher class=\"hudson.plugins.sshslaves.SSHLauncher\">\
+             <host>agent-1.example.com</host><port>22</port><credentialsId>agent-key</credentialsId>\
+             <jvmOptions></jvmOptions><javaPath></javaPath>\
+             <sshHostKeyVerificationStrategy class=\"hudson.plugins.sshslaves.verifiers.ManuallyProvidedKeyVerificationStrategy\">\
+             <key><algorithm>ssh-ed25519</algorithm><key>AAAAC3Nz</key></key>\
+             </sshHostKeyVerificationStrategy></launcher>"
+        );
+    }
+}
@@ -2,11 +2,35 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::client_internals::{Name, Path, Result};
+use crate::client;
+use crate::client_internals::{Name, Path, ResponseExt, Result};
 use crate::Jenkins;
 
+mod agent;
+pub use self::agent::PermanentAgent;
+mod availability;
+pub use self::availability::{AvailabilityHistory, AvailabilitySample};
 pub mod computer;
+mod labels;
+pub mod launcher;
 pub mod monitor;
+mod running;
+pub use self::running::RunningBuild;
+
+/// Name of the built-in node since Jenkins 2.307
+const BUILTIN_NODE_NAME: &str = "(built-in)";
+/// Name of the built-in node before Jenkins 2.307
+const LEGACY_BUILTIN_NODE_NAME: &str = "(master)";
+
+fn is_not_found(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    matches!(
+        error.downcast_ref::<client::Error>(),
+        Some(client::Error::NotFound { .. })
+    ) || error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+        == Some(reqwest::StatusCode::NOT_FOUND)
+}
 
 /// List of `Computer` associated to the `Jenkins` instance
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,13 +47,74 @@ pub struct ComputerSet {
     pub computers: Vec<computer::CommonComputer>,
 }
 
+/// Filter on the computers of a `ComputerSet`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeFilter {
+    exclude_builtin: bool,
+    labels: Vec<String>,
+}
+
+impl NodeFilter {
+    /// Create a filter keeping all the computers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only the agents, excluding the built-in node
+    pub fn agents_only(mut self) -> Self {
+        self.exclude_builtin = true;
+        self
+    }
+
+    /// Keep only the computers with the label `label`. When called several times, computers
+    /// must have all the labels
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.labels.push(label.to_string());
+        self
+    }
+
+    /// Does `computer` match this filter
+    pub fn matches(&self, computer: &computer::CommonComputer) -> bool {
+        !(self.exclude_builtin && computer.is_builtin())
+            && self.labels.iter().all(|label| computer.has_label(label))
+    }
+}
+
+impl ComputerSet {
+    /// Agents of the set, excluding the built-in node
+    pub fn agents(&self) -> impl Iterator<Item = &computer::CommonComputer> {
+        self.computers
+            .iter()
+            .filter(|computer| !computer.is_builtin())
+    }
+}
+
 impl Jenkins {
     /// Get a `ComputerSet`
     pub async fn get_nodes(&self) -> Result<ComputerSet> {
-        let response = self.get(&Path::Computers).await?.json().await?;
+        let response = self.get(&Path::Computers).await?.parse_json().await?;
         Ok(response)
     }
 
+    /// Get the computers matching `filter`
+    pub async fn get_nodes_matching(
+        &self,
+        filter: &NodeFilter,
+    ) -> Result<Vec<computer::CommonComputer>> {
+        let nodes = self.get_nodes().await?;
+        Ok(nodes
+            .computers
+            .into_iter()
+            .filter(|computer| filter.matches(computer))
+            .collect())
+    }
+
+    /// Get the agents, excluding the built-in node
+    pub async fn get_agents(&self) -> Result<Vec<computer::CommonComputer>> {
+        self.get_nodes_matching(&NodeFilter::new().agents_only())
+            .await
+    }
+
     /// Get a `Computer`
     pub async fn get_node<'a, C>(&self, computer_name: C) -> Result<computer::CommonComputer>
     where
@@ -40,20 +125,477 @@ impl Jenkins {
                 name: Name::Name(computer_name.into().0),
             })
             .await?
-            .json()
+            .parse_json()
             .await?;
         Ok(response)
     }
 
     /// Get the master `Computer`
+    #[deprecated(note = "use `get_builtin_node` instead")]
     pub async fn get_master_node(&self) -> Result<computer::MasterComputer> {
-        let response = self
+        self.get_builtin_node().await
+    }
+
+    /// Get the built-in `Computer`
+    ///
+    /// It is named `(built-in)` since Jenkins 2.307, and `(master)` before. The current
+    /// name is tried first, falling back to the legacy name if it is not found.
+    pub async fn get_builtin_node(&self) -> Result<computer::MasterComputer> {
+        let response = match self
             .get(&Path::Computer {
-                name: Name::Name("(master)"),
+                name: Name::Name(BUILTIN_NODE_NAME),
             })
-            .await?
-            .json()
+            .await
+        {
+            Ok(response) => response,
+            Err(error) if is_not_found(&*error) => {
+                self.get(&Path::Computer {
+                    name: Name::Name(LEGACY_BUILTIN_NODE_NAME),
+                })
+                .await?
+            }
+            Err(error) => return Err(error),
+        };
+        response.parse_json().await
+    }
+
+    /// Change the message explaining why a `Computer` is temporarily offline, without bringing
+    /// it back online. The computer must already be temporarily offline
+    pub async fn set_node_offline_message<'a, C>(
+        &self,
+        computer_name: C,
+        message: &str,
+    ) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self
+            .post_with_body(
+                &Path::ComputerChangeOfflineCause {
+                    name: Name::Name(computer_name.into().0),
+                },
+                url::form_urlencoded::Serializer::new(String::new())
+                    .append_pair("offlineMessage", message)
+                    .finish(),
+                &[],
+            )
             .await?;
-        Ok(response)
+        Ok(())
+    }
+
+    async fn toggle_node_offline(&self, computer_name: &str, message: &str) -> Result<()> {
+        let _ = self
+            .post_with_body(
+                &Path::ComputerToggleOffline {
+                    name: Name::Name(computer_name),
+                },
+                "",
+                &[("offlineMessage", message)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Mark a `Computer` as temporarily offline with `message` explaining why, so that it
+    /// doesn't start new builds. Running builds continue and the agent stays connected. If the
+    /// computer is already temporarily offline, only its message is changed
+    pub async fn set_node_offline<'a, C>(&self, computer_name: C, message: &str) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let computer_name = computer_name.into().0;
+        if self.get_node(computer_name).await?.temporarily_offline {
+            self.set_node_offline_message(computer_name, message).await
+        } else {
+            self.toggle_node_offline(computer_name, message).await
+        }
+    }
+
+    /// Bring a `Computer` marked as temporarily offline back online. Nothing is done if it isn't
+    /// temporarily offline
+    pub async fn set_node_online<'a, C>(&self, computer_name: C) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let computer_name = computer_name.into().0;
+        if self.get_node(computer_name).await?.temporarily_offline {
+            self.toggle_node_offline(computer_name, "").await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Disconnect the agent of a `Computer`, with `message` explaining why. Its running builds
+    /// are aborted, and it is reconnected by its launcher or on demand
+    pub async fn disconnect_node<'a, C>(&self, computer_name: C, message: &str) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self
+            .post_with_body(
+                &Path::ComputerDisconnect {
+                    name: Name::Name(computer_name.into().0),
+                },
+                url::form_urlencoded::Serializer::new(String::new())
+                    .append_pair("offlineMessage", message)
+                    .finish(),
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Delete a `Computer`, disconnecting its agent
+    pub async fn delete_node<'a, C>(&self, computer_name: C) -> Result<()>
+    where
+        C: Into<computer::ComputerName<'a>>,
+    {
+        let _ = self
+            .post(&Path::ComputerDelete {
+                name: Name::Name(computer_name.into().0),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Create a permanent agent
+    pub async fn create_node(&self, agent: &PermanentAgent) -> Result<()> {
+        let _ = self
+            .post_with_body(&Path::ComputerCreateItem, agent.to_form()?, &[])
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the configuration of the permanent agent with the same name as `agent`, keeping
+    /// its retention strategy and node properties
+    pub async fn update_node(&self, agent: &PermanentAgent) -> Result<()> {
+        let path = Path::ComputerConfigXML {
+            name: Name::Name(agent.name()),
+        };
+        let config_xml = self.get_endpoint(&path).await?.text().await?;
+        let config_xml = agent.apply_to_config_xml(&config_xml)?;
+        let _ = self.post_with_xml_body(&path, config_xml, &[]).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::Matcher;
+
+    static BUILTIN_NODE: &str = r#"{
+        "_class": "hudson.model.Hudson$MasterComputer",
+        "displayName": "Built-In Node",
+        "description": "the Jenkins controller's built-in node",
+        "icon": "computer.png",
+        "iconClassName": "icon-computer",
+        "idle": true,
+        "jnlpAgent": false,
+        "launchSupported": true,
+        "manualLaunchAllowed": true,
+        "numExecutors": 2,
+        "offline": false,
+        "temporarilyOffline": false,
+        "monitorData": {},
+        "executors": [],
+        "oneOffExecutors": [],
+        "assignedLabels": [{"name": "built-in"}]
+    }"#;
+
+    #[tokio::test]
+    async fn can_get_builtin_node() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/computer/%28built-in%29/api/json")
+            .match_query(Matcher::Any)
+            .with_body(BUILTIN_NODE)
+            .create();
+
+        let node = jenkins_client.get_builtin_node().await;
+
+        assert!(node.is_ok());
+        assert_eq!(node.unwrap().display_name, "Built-In Node");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_get_builtin_node_with_legacy_name() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let _missing = server
+            .mock("GET", "/computer/%28built-in%29/api/json")
+            .match_query(Matcher::Any)
+            .with_status(404)
+            .create();
+        let legacy = server
+            .mock("GET", "/computer/%28master%29/api/json")
+            .match_query(Matcher::Any)
+            .with_body(BUILTIN_NODE)
+            .create();
+
+        let node = jenkins_client.get_builtin_node().await;
+
+        assert!(node.is_ok());
+        legacy.assert();
+    }
+
+    #[tokio::test]
+    async fn can_get_nodes_matching() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let agent = |name: &str, labels: &[&str]| {
+            let mut agent: serde_json::Value = serde_json::from_str(BUILTIN_NODE).unwrap();
+            agent["_class"] = "hudson.slaves.SlaveComputer".into();
+            agent["displayName"] = name.into();
+            agent["assignedLabels"] = labels
+                .iter()
+                .map(|label| serde_json::json!({ "name": label }))
+                .collect();
+            agent
+        };
+        let mock = server
+            .mock("GET", "/computer/api/json")
+            .match_query(Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "_class": "hudson.model.ComputerSet",
+                    "displayName": "Nodes",
+                    "busyExecutors": 0,
+                    "totalExecutors": 6,
+                    "computer": [
+                        serde_json::from_str::<serde_json::Value>(BUILTIN_NODE).unwrap(),
+                        agent("agent-1", &["agent-1", "linux"]),
+                        agent("agent-2", &["agent-2", "windows"]),
+                    ]
+                })
+                .to_string(),
+            )
+            .expect(2)
+            .create();
+
+        let agents = jenkins_client.get_agents().await.unwrap();
+        let linux = jenkins_client
+            .get_nodes_matching(&super::NodeFilter::new().with_label("linux"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            agents
+                .iter()
+                .map(|agent| agent.display_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["agent-1", "agent-2"]
+        );
+        assert_eq!(linux.len(), 1);
+        assert_eq!(linux[0].display_name, "agent-1");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_set_offline_message() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server
+            .mock("POST", "/computer/agent-1/changeOfflineCause")
+            .match_body("offlineMessage=Maintenance+until+14%3A00")
+            .create();
+
+        jenkins_client
+            .set_node_offline_message("agent-1", "Maintenance until 14:00")
+            .await
+            .unwrap();
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_set_offline_message_of_builtin_node() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let node: super::computer::CommonComputer = serde_json::from_str(BUILTIN_NODE).unwrap();
+        let mock = server
+            .mock("POST", "/computer/%28built-in%29/changeOfflineCause")
+            .match_body("offlineMessage=upgrade")
+            .create();
+
+        node.set_offline_message(&jenkins_client, "upgrade")
+            .await
+            .unwrap();
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_create_node() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server
+            .mock("POST", "/computer/doCreateItem")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("name".to_string(), "agent-1".to_string()),
+                Matcher::UrlEncoded("type".to_string(), "hudson.slaves.DumbSlave".to_string()),
+                Matcher::Regex("%22labelString%22%3A%22linux\\+docker%22".to_string()),
+            ]))
+            .create();
+
+        jenkins_client
+            .create_node(
+                &super::PermanentAgent::new(
+                    "agent-1",
+                    "/home/jenkins",
+                    super::launcher::InboundLauncher::new(),
+                )
+                .with_labels(&["linux", "docker"]),
+            )
+            .await
+            .unwrap();
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_update_node() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let get = server
+            .mock("GET", "/computer/agent-1/config.xml")
+            .with_body(
+                r#"<?xml version="1.1" encoding="UTF-8"?>
+<slave>
+  <name>agent-1</name>
+  <description></description>
+  <remoteFS>/home/jenkins</remoteFS>
+  <numExecutors>1</numExecutors>
+  <mode>NORMAL</mode>
+  <retentionStrategy class="hudson.slaves.RetentionStrategy$Always"/>
+  <launcher class="hudson.slaves.JNLPLauncher">
+    <webSocket>false</webSocket>
+  </launcher>
+  <label>linux</label>
+  <nodeProperties>
+    <hudson.slaves.EnvironmentVariablesNodeProperty>
+      <envVars serialization="custom"/>
+    </hudson.slaves.EnvironmentVariablesNodeProperty>
+  </nodeProperties>
+</slave>"#,
+            )
+            .create();
+        let post = server
+            .mock("POST", "/computer/agent-1/config.xml")
+            .match_header("content-type", "text/xml")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex("<numExecutors>4</numExecutors>".to_string()),
+                Matcher::Regex("<mode>EXCLUSIVE</mode>".to_string()),
+                Matcher::Regex(
+                    "<launcher class=\"hudson.plugins.sshslaves.SSHLauncher\"><host>agent-1.example.com</host><port>22</port>".to_string(),
+                ),
+                Matcher::Regex("<label>linux docker</label>".to_string()),
+                Matcher::Regex("<hudson.slaves.EnvironmentVariablesNodeProperty>".to_string()),
+            ]))
+            .create();
+
+        jenkins_client
+            .update_node(
+                &super::PermanentAgent::new(
+                    "agent-1",
+                    "/home/jenkins",
+                    super::launcher::SshLauncher::new("agent-1.example.com", "agent-key"),
+                )
+                .with_executors(4)
+                .with_labels(&["linux", "docker"])
+                .exclusive(),
+            )
+            .await
+            .unwrap();
+
+        get.assert();
+        post.assert();
+    }
+
+    #[tokio::test]
+    async fn can_manage_node_state() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mut offline: serde_json::Value = serde_json::from_str(BUILTIN_NODE).unwrap();
+        offline["displayName"] = "agent-1".into();
+        offline["temporarilyOffline"] = true.into();
+        let online_node = server
+            .mock("GET", "/computer/agent-1/api/json")
+            .match_query(Matcher::Any)
+            .with_body(BUILTIN_NODE)
+            .expect(1)
+            .create();
+        let offline_node = server
+            .mock("GET", "/computer/agent-1/api/json")
+            .match_query(Matcher::Any)
+            .with_body(offline.to_string())
+            .expect_at_least(1)
+            .create();
+        let toggles = server
+            .mock("POST", "/computer/agent-1/toggleOffline")
+            .match_query(Matcher::Any)
+            .expect(2)
+            .create();
+        let change_message = server
+            .mock("POST", "/computer/agent-1/changeOfflineCause")
+            .match_body(Matcher::UrlEncoded(
+                "offlineMessage".to_string(),
+                "disk full".to_string(),
+            ))
+            .create();
+        let disconnect = server
+            .mock("POST", "/computer/agent-1/doDisconnect")
+            .match_body(Matcher::UrlEncoded(
+                "offlineMessage".to_string(),
+                "upgrade".to_string(),
+            ))
+            .create();
+        let delete = server.mock("POST", "/computer/agent-1/doDelete").create();
+
+        jenkins_client
+            .set_node_offline("agent-1", "maintenance")
+            .await
+            .unwrap();
+        jenkins_client
+            .set_node_offline("agent-1", "disk full")
+            .await
+            .unwrap();
+        jenkins_client.set_node_online("agent-1").await.unwrap();
+        jenkins_client
+            .disconnect_node("agent-1", "upgrade")
+            .await
+            .unwrap();
+        jenkins_client.delete_node("agent-1").await.unwrap();
+
+        online_node.assert();
+        offline_node.assert();
+        toggles.assert();
+        change_message.assert();
+        disconnect.assert();
+        delete.assert();
     }
 }
@@ -40,7 +40,7 @@ specialize!(CommonMonitorData => MonitorData);
 impl MonitorData for CommonMonitorData {}
 
 /// Swap Space Monitor
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SwapSpaceMonitor {
     /// Available physical memory
@@ -56,7 +56,7 @@ register_class!("hudson.node_monitors.SwapSpaceMonitor$MemoryUsage2" => SwapSpac
 impl MonitorData for SwapSpaceMonitor {}
 
 /// Swap Space Monitor
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DiskSpaceMonitorDescriptor {
     /// Timestamp
@@ -70,7 +70,7 @@ register_class!("hudson.node_monitors.DiskSpaceMonitorDescriptor$DiskSpace" => D
 impl MonitorData for DiskSpaceMonitorDescriptor {}
 
 /// Response Time Monitor
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseTimeMonitor {
     /// Timestamp
@@ -82,7 +82,7 @@ register_class!("hudson.node_monitors.ResponseTimeMonitor$Data" => ResponseTimeM
 impl MonitorData for ResponseTimeMonitor {}
 
 /// Clock Difference Monitor
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ClockDifference {
     /// Clock difference
@@ -0,0 +1,206 @@
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::client::{Path, Result, TreeBuilder, TreeQueryParam};
+use crate::Jenkins;
+
+/// A build running on an executor
+#[derive(Debug, Clone)]
+pub struct RunningBuild {
+    /// Name of the node running the build
+    pub node: String,
+    /// Full name of the job, with its folders separated by `/`
+    pub job: String,
+    /// Build number
+    pub number: u32,
+    /// URL of the build
+    pub url: String,
+    /// Progress of the build in percent, if it can be estimated
+    pub progress: Option<u32>,
+    /// Estimated time until the build completes, if it can be estimated. A build taking longer
+    /// than estimated has an ETA of zero
+    pub eta: Option<Duration>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Executable {
+    number: Option<u32>,
+    url: Option<String>,
+    timestamp: Option<u64>,
+    estimated_duration: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BusyExecutor {
+    current_executable: Option<Executable>,
+    progress: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ComputerExecutors {
+    display_name: String,
+    #[serde(default)]
+    executors: Vec<BusyExecutor>,
+    #[serde(default)]
+    one_off_executors: Vec<BusyExecutor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComputersExecutors {
+    computer: Vec<ComputerExecutors>,
+}
+
+fn executors_tree(name: &str) -> TreeBuilder {
+    TreeBuilder::object(name)
+        .with_subfield("progress")
+        .with_subfield(
+            TreeBuilder::object("currentExecutable")
+                .with_subfield("number")
+                .with_subfield("url")
+                .with_subfield("timestamp")
+                .with_subfield("estimatedDuration"),
+        )
+}
+
+/// Get the full name of a job from the URL of one of its builds
+fn job_full_name(build_url: &str) -> String {
+    let mut segments = build_url.split('/');
+    let mut names = vec![];
+    while let Some(segment) = segments.next() {
+        if segment == "job" {
+            if let Some(name) = segments.next() {
+                names.push(
+                    urlencoding::decode(name)
+                        .map(|name| name.into_owned())
+                        .unwrap_or_else(|_| name.to_string()),
+                );
+            }
+        }
+    }
+    names.join("/")
+}
+
+impl Jenkins {
+    /// Get the builds currently running on all nodes
+    pub async fn get_running_builds(&self) -> Result<Vec<RunningBuild>> {
+        let tree: TreeQueryParam = TreeBuilder::new()
+            .with_field(
+                TreeBuilder::object("computer")
+                    .with_subfield("displayName")
+                    .with_subfield(executors_tree("executors"))
+                    .with_subfield(executors_tree("oneOffExecutors")),
+            )
+            .build();
+        let computers: ComputersExecutors = self
+            .get_object_as(Path::Raw { path: "/computer" }, tree)
+            .await?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_millis() as u64)
+            .unwrap_or(0);
+        Ok(computers
+            .computer
+            .into_iter()
+            .flat_map(|computer| {
+                let node = computer.display_name;
+                computer
+                    .executors
+                    .into_iter()
+                    .chain(computer.one_off_executors)
+                    .filter_map(move |executor| {
+                        let executable = executor.current_executable?;
+                        let url = executable.url?;
+                        let eta = match (executable.timestamp, executable.estimated_duration) {
+                            (Some(timestamp), Some(estimated_duration))
+                                if estimated_duration >= 0 =>
+                            {
+                                let end = timestamp + estimated_duration as u64;
+                                Some(Duration::from_millis(end.saturating_sub(now)))
+                            }
+                            _ => None,
+                        };
+                        Some(RunningBuild {
+                            node: node.clone(),
+                            job: job_full_name(&url),
+                            number: executable.number?,
+                            url,
+                            progress: executor
+                                .progress
+                                .and_then(|progress| u32::try_from(progress).ok()),
+                            eta,
+                        })
+                    })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_get_running_builds() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/computer/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "computer[displayName,executors[progress,currentExecutable[number,url,timestamp,estimatedDuration]],oneOffExecutors[progress,currentExecutable[number,url,timestamp,estimatedDuration]]]".to_string(),
+            ))
+            .with_body(format!(
+                r#"{{"computer": [
+                    {{
+                        "displayName": "Built-In Node",
+                        "executors": [{{"currentExecutable": null, "progress": -1}}],
+                        "oneOffExecutors": [{{
+                            "currentExecutable": {{
+                                "number": 7,
+                                "url": "{0}/job/team/job/deploy%20app/7/",
+                                "timestamp": 1000,
+                                "estimatedDuration": 60000
+                            }},
+                            "progress": 99
+                        }}]
+                    }},
+                    {{
+                        "displayName": "agent-1",
+                        "executors": [
+                            {{
+                                "currentExecutable": {{
+                                    "number": 12,
+                                    "url": "{0}/job/tests/12/",
+                                    "timestamp": 4102444800000,
+                                    "estimatedDuration": -1
+                                }},
+                                "progress": -1
+                            }},
+                            {{"currentExecutable": {{"_class": "org.jenkinsci.plugins.workflow.support.steps.ExecutorStepExecution$PlaceholderTask$PlaceholderExecutable"}}, "progress": 10}}
+                        ]
+                    }}
+                ]}}"#,
+                server.url()
+            ))
+            .create();
+
+        let builds = jenkins_client.get_running_builds().await.unwrap();
+
+        assert_eq!(builds.len(), 2);
+        assert_eq!(builds[0].node, "Built-In Node");
+        assert_eq!(builds[0].job, "team/deploy app");
+        assert_eq!(builds[0].number, 7);
+        assert_eq!(builds[0].progress, Some(99));
+        assert_eq!(builds[0].eta, Some(std::time::Duration::ZERO));
+        assert_eq!(builds[1].node, "agent-1");
+        assert_eq!(builds[1].job, "tests");
+        assert_eq!(builds[1].progress, None);
+        assert_eq!(builds[1].eta, None);
+        mock.assert();
+    }
+}
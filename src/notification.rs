@@ -0,0 +1,376 @@
+//! Notifications configured on jobs
+//!
+//! Notifiers are read from the `config.xml` of jobs, and are only found on jobs configured from
+//! the UI: notifications sent from the `Jenkinsfile` of a pipeline are not listed.
+
+use std::collections::HashMap;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::client_internals::Result;
+
+/// Element of the Email Extension plugin publisher
+const EMAIL_EXT_PUBLISHER: &str = "hudson.plugins.emailext.ExtendedEmailPublisher";
+/// Element of the Slack plugin notifier
+const SLACK_NOTIFIER: &str = "jenkins.plugins.slack.SlackNotifier";
+
+fn split_recipients(recipient_list: &str) -> impl Iterator<Item = &str> {
+    recipient_list
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|recipient| !recipient.is_empty())
+}
+
+/// An event sending an email, configured in the Email Extension plugin
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EmailTrigger {
+    /// Class of the trigger, like `hudson.plugins.emailext.plugins.trigger.FailureTrigger`
+    pub class: String,
+    /// Recipients added for this trigger, separated by commas
+    pub recipient_list: Option<String>,
+    /// Classes of the providers of recipients, like
+    /// `hudson.plugins.emailext.plugins.recipients.DevelopersRecipientProvider`
+    pub recipient_providers: Vec<String>,
+    /// Subject of the email
+    pub subject: Option<String>,
+}
+
+impl EmailTrigger {
+    /// Name of the trigger, like `FailureTrigger`
+    pub fn name(&self) -> &str {
+        self.class.rsplit('.').next().unwrap_or(&self.class)
+    }
+
+    /// Recipients added for this trigger, that can include variables like
+    /// `$DEFAULT_RECIPIENTS`
+    pub fn recipients(&self) -> Vec<&str> {
+        self.recipient_list
+            .as_deref()
+            .map(|list| split_recipients(list).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Configuration of the Email Extension plugin publisher of a job
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EmailExtConfig {
+    /// Recipients of all the emails, separated by commas
+    pub recipient_list: Option<String>,
+    /// Reply-To address of the emails
+    pub reply_to: Option<String>,
+    /// Content type of the emails, `default` to use the one of Jenkins
+    pub content_type: Option<String>,
+    /// Subject of the emails
+    pub default_subject: Option<String>,
+    /// Are the emails disabled
+    pub disabled: bool,
+    /// Events sending an email
+    pub triggers: Vec<EmailTrigger>,
+}
+
+impl EmailExtConfig {
+    /// Read the configuration of the Email Extension publisher from the `config.xml` of a job,
+    /// if it has one
+    pub fn from_config_xml(config_xml: &str) -> Result<Option<Self>> {
+        let mut reader = Reader::from_str(config_xml);
+        reader.config_mut().trim_text(true);
+
+        let mut config: Option<EmailExtConfig> = None;
+        // depth of the publisher element
+        let mut publisher_depth = None;
+        let mut fields: HashMap<String, String> = HashMap::new();
+        let mut parents: Vec<String> = vec![];
+        let mut text = String::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) => {
+                    let name = std::str::from_utf8(element.name().as_ref())?.to_string();
+                    match publisher_depth {
+                        None if config.is_none() && name == EMAIL_EXT_PUBLISHER => {
+                            publisher_depth = Some(parents.len());
+                            config = Some(EmailExtConfig::default());
+                        }
+                        Some(depth) => {
+                            if let Some(config) = config.as_mut() {
+                                Self::start_element(config, &parents, depth, &name);
+                            }
+                        }
+                        None => (),
+                    }
+                    parents.push(name);
+                    text.clear();
+                }
+                Event::Empty(element) => {
+                    if let (Some(depth), Some(config)) = (publisher_depth, config.as_mut()) {
+                        let name = element.name();
+                        Self::start_element(
+                            config,
+                            &parents,
+                            depth,
+                            std::str::from_utf8(name.as_ref())?,
+                        );
+                    }
+                }
+                Event::Text(content) => text.push_str(&content.unescape()?),
+                Event::End(_) => {
+                    let name = parents.pop().unwrap_or_default();
+                    if let (Some(depth), Some(config)) = (publisher_depth, config.as_mut()) {
+                        if parents.len() == depth {
+                            publisher_depth = None;
+                        } else if parents.len() == depth + 1 {
+                            let _ = fields.insert(name, text.clone());
+                        } else if parents.len() == depth + 4 && parents[depth + 3] == "email" {
+                            if let Some(trigger) = config.triggers.last_mut() {
+                                match name.as_str() {
+                                    "recipientList" => trigger.recipient_list = Some(text.clone()),
+                                    "subject" => trigger.subject = Some(text.clone()),
+                                    _ => (),
+                                }
+                            }
+                        }
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(config.map(|mut config| {
+            config.recipient_list = fields.remove("recipientList");
+            config.reply_to = fields.remove("replyTo");
+            config.content_type = fields.remove("contentType");
+            config.default_subject = fields.remove("defaultSubject");
+            config.disabled = fields.remove("disabled").as_deref() == Some("true");
+            config
+        }))
+    }
+
+    /// Handle the start of the element `name` inside the publisher at `depth`
+    fn start_element(config: &mut EmailExtConfig, parents: &[String], depth: usize, name: &str) {
+        if parents.len() == depth + 2 && parents[depth + 1] == "configuredTriggers" {
+            config.triggers.push(EmailTrigger {
+                class: name.to_string(),
+                ..Default::default()
+            });
+        } else if parents.len() == depth + 5 && parents[depth + 4] == "recipientProviders" {
+            if let Some(trigger) = config.triggers.last_mut() {
+                trigger.recipient_providers.push(name.to_string());
+            }
+        }
+    }
+
+    /// Recipients of all the emails, that can include variables like `$DEFAULT_RECIPIENTS`
+    pub fn recipients(&self) -> Vec<&str> {
+        self.recipient_list
+            .as_deref()
+            .map(|list| split_recipients(list).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the trigger named `name`, like `FailureTrigger`
+    pub fn trigger(&self, name: &str) -> Option<&EmailTrigger> {
+        self.triggers.iter().find(|trigger| trigger.name() == name)
+    }
+}
+
+/// Configuration of the Slack plugin notifier of a job
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SlackNotifierConfig {
+    /// Channels notified, separated by commas, like `#builds`
+    pub room: Option<String>,
+    /// Slack workspace, when it is not the one configured in Jenkins
+    pub team_domain: Option<String>,
+    /// URL of the Slack webhook, when it is not the one configured in Jenkins
+    pub base_url: Option<String>,
+    /// ID of the credentials holding the token, when it is not the one configured in Jenkins
+    pub token_credential_id: Option<String>,
+    /// Is the token the one of a bot user
+    pub bot_user: bool,
+    /// Events notified, as the name of their flag like `notifyFailure` or
+    /// `startNotification`
+    pub notifications: Vec<String>,
+}
+
+impl SlackNotifierConfig {
+    /// Read the configuration of the Slack notifier from the `config.xml` of a job, if it has one
+    pub fn from_config_xml(config_xml: &str) -> Result<Option<Self>> {
+        let mut reader = Reader::from_str(config_xml);
+        reader.config_mut().trim_text(true);
+
+        let mut config: Option<SlackNotifierConfig> = None;
+        // depth of the notifier element
+        let mut notifier_depth = None;
+        let mut depth = 0;
+        let mut text = String::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) => {
+                    if config.is_none() && element.name().as_ref() == SLACK_NOTIFIER.as_bytes() {
+                        notifier_depth = Some(depth);
+                        config = Some(SlackNotifierConfig::default());
+                    }
+                    depth += 1;
+                    text.clear();
+                }
+                Event::Text(content) => text.push_str(&content.unescape()?),
+                Event::End(element) => {
+                    depth -= 1;
+                    if let (Some(notifier_depth), Some(config)) = (notifier_depth, config.as_mut())
+                    {
+                        if depth == notifier_depth {
+                            return Ok(Some(config.clone()));
+                        } else if depth == notifier_depth + 1 {
+                            let name = std::str::from_utf8(element.name().as_ref())?.to_string();
+                            config.set_field(name, text.clone());
+                        }
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(config)
+    }
+
+    fn set_field(&mut self, name: String, value: String) {
+        let value = Some(value).filter(|value| !value.is_empty());
+        match name.as_str() {
+            "room" => self.room = value,
+            "teamDomain" => self.team_domain = value,
+            "baseUrl" => self.base_url = value,
+            "tokenCredentialId" => self.token_credential_id = value,
+            "botUser" => self.bot_user = value.as_deref() == Some("true"),
+            _ if (name.starts_with("notify") || name == "startNotification")
+                && value.as_deref() == Some("true") =>
+            {
+                self.notifications.push(name)
+            }
+            _ => (),
+        }
+    }
+
+    /// Channels notified
+    pub fn channels(&self) -> Vec<&str> {
+        self.room
+            .as_deref()
+            .map(|room| split_recipients(room).collect())
+            .unwrap_or_default()
+    }
+
+    /// Is the event with the flag `notification`, like `notifyFailure`, notified
+    pub fn notifies(&self, notification: &str) -> bool {
+        self.notifications.iter().any(|other| other == notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_email_ext_config() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <builders/>
+  <publishers>
+    <hudson.plugins.emailext.ExtendedEmailPublisher plugin="email-ext@2.104">
+      <recipientList>team@example.com, $DEFAULT_RECIPIENTS</recipientList>
+      <configuredTriggers>
+        <hudson.plugins.emailext.plugins.trigger.FailureTrigger>
+          <email>
+            <subject>$PROJECT_DEFAULT_SUBJECT</subject>
+            <body>$PROJECT_DEFAULT_CONTENT</body>
+            <recipientProviders>
+              <hudson.plugins.emailext.plugins.recipients.DevelopersRecipientProvider/>
+              <hudson.plugins.emailext.plugins.recipients.CulpritsRecipientProvider/>
+            </recipientProviders>
+            <recipientList>oncall@example.com</recipientList>
+          </email>
+        </hudson.plugins.emailext.plugins.trigger.FailureTrigger>
+        <hudson.plugins.emailext.plugins.trigger.FixedTrigger>
+          <email>
+            <recipientList></recipientList>
+            <recipientProviders>
+              <hudson.plugins.emailext.plugins.recipients.ListRecipientProvider/>
+            </recipientProviders>
+          </email>
+        </hudson.plugins.emailext.plugins.trigger.FixedTrigger>
+      </configuredTriggers>
+      <contentType>default</contentType>
+      <defaultSubject>$DEFAULT_SUBJECT</defaultSubject>
+      <replyTo>$DEFAULT_REPLYTO</replyTo>
+      <disabled>false</disabled>
+    </hudson.plugins.emailext.ExtendedEmailPublisher>
+  </publishers>
+</project>"#;
+
+        let config = EmailExtConfig::from_config_xml(config_xml)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            config.recipients(),
+            vec!["team@example.com", "$DEFAULT_RECIPIENTS"]
+        );
+        assert!(!config.disabled);
+        assert_eq!(
+            config
+                .triggers
+                .iter()
+                .map(EmailTrigger::name)
+                .collect::<Vec<_>>(),
+            vec!["FailureTrigger", "FixedTrigger"]
+        );
+        let failure = config.trigger("FailureTrigger").unwrap();
+        assert_eq!(failure.recipients(), vec!["oncall@example.com"]);
+        assert_eq!(failure.recipient_providers.len(), 2);
+        assert!(config
+            .trigger("FixedTrigger")
+            .unwrap()
+            .recipients()
+            .is_empty());
+
+        assert_eq!(
+            EmailExtConfig::from_config_xml("<project><publishers/></project>").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn can_parse_slack_notifier_config() {
+        let config_xml = r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <publishers>
+    <jenkins.plugins.slack.SlackNotifier plugin="slack@2.48">
+      <baseUrl></baseUrl>
+      <teamDomain>acme</teamDomain>
+      <authToken></authToken>
+      <tokenCredentialId>slack-prod-token</tokenCredentialId>
+      <botUser>true</botUser>
+      <room>#prod-builds,#oncall</room>
+      <startNotification>false</startNotification>
+      <notifySuccess>false</notifySuccess>
+      <notifyFailure>true</notifyFailure>
+      <notifyBackToNormal>true</notifyBackToNormal>
+      <includeTestSummary>true</includeTestSummary>
+    </jenkins.plugins.slack.SlackNotifier>
+  </publishers>
+</project>"#;
+
+        let config = SlackNotifierConfig::from_config_xml(config_xml)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(config.channels(), vec!["#prod-builds", "#oncall"]);
+        assert_eq!(
+            config.token_credential_id.as_deref(),
+            Some("slack-prod-token")
+        );
+        assert_eq!(config.base_url, None);
+        assert!(config.bot_user);
+        assert!(config.notifies("notifyFailure"));
+        assert!(!config.notifies("notifySuccess"));
+        assert_eq!(config.notifications.len(), 2);
+    }
+}
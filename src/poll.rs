@@ -0,0 +1,155 @@
+//! Helpers to poll Jenkins until an object reaches an expected state
+
+use std::convert::TryFrom;
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::Instant;
+pub use tokio_util::sync::CancellationToken;
+
+use crate::client::{self, Result};
+
+/// Call `f` every `interval` until it returns `Some`, giving up after `timeout`
+///
+/// Each wait is lengthened by a random duration up to `jitter`, so that several pollers started
+/// together don't hit Jenkins at the same time. The wait is interrupted as soon as `cancel_token`
+/// is cancelled, returning `Error::PollCancelled`. An error returned by `f` stops polling and is
+/// returned as is.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use jenkins_api::poll::{poll_until, CancellationToken};
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let mut attempts = 0;
+/// let attempts_needed = poll_until(
+///     Duration::from_millis(10),
+///     Duration::from_secs(1),
+///     Duration::from_millis(5),
+///     &CancellationToken::new(),
+///     || {
+///         attempts += 1;
+///         let done = attempts == 3;
+///         async move { Ok(if done { Some(attempts) } else { None }) }
+///     },
+/// )
+/// .await?;
+/// assert_eq!(attempts_needed, 3);
+/// #     Ok(())
+/// # }
+/// ```
+pub async fn poll_until<T, F, Fut>(
+    interval: Duration,
+    timeout: Duration,
+    jitter: Duration,
+    cancel_token: &CancellationToken,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        if cancel_token.is_cancelled() {
+            return Err(client::Error::PollCancelled.into());
+        }
+        if let Some(value) = f().await? {
+            return Ok(value);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(client::Error::PollTimeout { timeout }.into());
+        }
+        let wait = (interval + random_jitter(jitter)).min(deadline - now);
+        if tokio::time::timeout(wait, cancel_token.cancelled())
+            .await
+            .is_ok()
+        {
+            return Err(client::Error::PollCancelled.into());
+        }
+    }
+}
+
+fn random_jitter(jitter: Duration) -> Duration {
+    if jitter.is_zero() {
+        return Duration::ZERO;
+    }
+    let max_nanos = u64::try_from(jitter.as_nanos()).unwrap_or(u64::MAX);
+    Duration::from_nanos(fastrand::u64(0..=max_nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_error(error: &(dyn std::error::Error + Send + Sync + 'static), expected: &str) -> bool {
+        matches!(
+            (error.downcast_ref::<client::Error>(), expected),
+            (Some(client::Error::PollTimeout { .. }), "timeout")
+                | (Some(client::Error::PollCancelled), "cancelled")
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn can_poll_until_value() {
+        let mut calls = 0;
+        let result = poll_until(
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            Duration::from_millis(100),
+            &CancellationToken::new(),
+            || {
+                calls += 1;
+                let done = calls == 4;
+                async move { Ok(if done { Some("done") } else { None }) }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "done");
+        assert_eq!(calls, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn poll_until_times_out() {
+        let start = Instant::now();
+        let result: Result<()> = poll_until(
+            Duration::from_secs(1),
+            Duration::from_secs(5),
+            Duration::ZERO,
+            &CancellationToken::new(),
+            || async { Ok(None) },
+        )
+        .await;
+
+        assert!(is_error(result.unwrap_err().as_ref(), "timeout"));
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn poll_until_can_be_cancelled() {
+        let cancel_token = CancellationToken::new();
+        let canceller = cancel_token.clone();
+        let _canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(1500)).await;
+            canceller.cancel();
+        });
+
+        let start = Instant::now();
+        let result: Result<()> = poll_until(
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+            Duration::ZERO,
+            &cancel_token,
+            || async { Ok(None) },
+        )
+        .await;
+
+        assert!(is_error(result.unwrap_err().as_ref(), "cancelled"));
+        assert_eq!(start.elapsed(), Duration::from_millis(1500));
+    }
+}
@@ -27,22 +27,86 @@ specialize!(CommonProperty => Property);
 impl Property for CommonProperty {}
 
 /// Job is a GitHub project
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GithubProjectProperty {}
 register_class!("com.coravy.hudson.plugins.github.GithubProjectProperty" => GithubProjectProperty);
 impl Property for GithubProjectProperty {}
 
 /// Job is limited in number of concurrent builds
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimitBranchProperty {}
 register_class!("jenkins.branch.RateLimitBranchProperty$JobPropertyImpl" => RateLimitBranchProperty);
 impl Property for RateLimitBranchProperty {}
 
 /// Old builds of job are discarded
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildDiscarderProperty {}
 register_class!("jenkins.model.BuildDiscarderProperty" => BuildDiscarderProperty);
 impl Property for BuildDiscarderProperty {}
+
+/// How builds of a job are throttled
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ThrottleOption {
+    /// Limits are set on the job itself
+    Project,
+    /// Limits are set on the categories of the job
+    Category,
+}
+
+/// Job is throttled by the Throttle Concurrent Builds plugin
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleJobProperty {
+    /// Is throttling enabled
+    #[serde(default)]
+    pub throttle_enabled: bool,
+    /// Are limits set on the job or on its categories
+    pub throttle_option: Option<ThrottleOption>,
+    /// Throttling categories of the job
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Maximum number of concurrent builds of the job on a node, 0 if unlimited
+    #[serde(default)]
+    pub max_concurrent_per_node: u32,
+    /// Maximum number of concurrent builds of the job, 0 if unlimited
+    #[serde(default)]
+    pub max_concurrent_total: u32,
+    /// Only one build with the same parameters can run at a time
+    #[serde(default)]
+    pub limit_one_job_with_matching_params: bool,
+}
+register_class!("hudson.plugins.throttleconcurrents.ThrottleJobProperty" => ThrottleJobProperty);
+impl Property for ThrottleJobProperty {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_get_throttle_job_property() {
+        let property: CommonProperty = serde_json::from_str(
+            r#"{
+                "_class": "hudson.plugins.throttleconcurrents.ThrottleJobProperty",
+                "categories": ["deployments"],
+                "limitOneJobWithMatchingParams": false,
+                "maxConcurrentPerNode": 1,
+                "maxConcurrentTotal": 2,
+                "throttleEnabled": true,
+                "throttleOption": "category"
+            }"#,
+        )
+        .unwrap();
+
+        let throttle = property.as_variant::<ThrottleJobProperty>().unwrap();
+
+        assert!(throttle.throttle_enabled);
+        assert_eq!(throttle.throttle_option, Some(ThrottleOption::Category));
+        assert_eq!(throttle.categories, vec!["deployments"]);
+        assert_eq!(throttle.max_concurrent_per_node, 1);
+        assert_eq!(throttle.max_concurrent_total, 2);
+    }
+}
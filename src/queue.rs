@@ -1,12 +1,17 @@
 //! Jenkins build queue
 
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
-use crate::action::CommonAction;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::action::{CommonAction, PrioritySorterAction};
 use crate::build::ShortBuild;
-use crate::client::{self, Result};
-use crate::client_internals::Path;
+use crate::client::{self, AdvancedQuery, Cursor, Page, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, Path, ResponseExt};
 use crate::job::ShortJob;
+use crate::poll::{poll_until, CancellationToken};
 use crate::Jenkins;
 
 /// Short Queue Item that is returned when building a job
@@ -28,7 +33,7 @@ impl ShortQueueItem {
     pub async fn get_full_queue_item(&self, jenkins_client: &Jenkins) -> Result<QueueItem> {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::QueueItem { .. } = path {
-            Ok(jenkins_client.get(&path).await?.json().await?)
+            Ok(jenkins_client.get(&path).await?.parse_json().await?)
         } else {
             Err(client::Error::InvalidUrl {
                 url: self.url.clone(),
@@ -39,6 +44,75 @@ impl ShortQueueItem {
     }
 }
 
+/// Class of the tasks waiting for an executor for a `node` block of a pipeline
+const PLACEHOLDER_TASK_CLASS: &str =
+    "org.jenkinsci.plugins.workflow.support.steps.ExecutorStepExecution$PlaceholderTask";
+
+/// A task waiting in the queue
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+pub enum QueueTask {
+    /// A job waiting to be built
+    Job(ShortJob),
+    /// A `node` block of a running pipeline waiting for an executor
+    PlaceholderTask(PlaceholderTask),
+    /// Another kind of task
+    Unknown(serde_json::Value),
+}
+impl QueueTask {
+    /// Get the job waiting in the queue, if the task is a job
+    pub fn as_job(&self) -> Option<&ShortJob> {
+        match self {
+            QueueTask::Job(job) => Some(job),
+            _ => None,
+        }
+    }
+
+    /// Get the URL of the task, if it has one
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            QueueTask::Job(job) => Some(&job.url),
+            QueueTask::PlaceholderTask(task) => task.url.as_deref(),
+            QueueTask::Unknown(value) => value.get("url").and_then(serde_json::Value::as_str),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for QueueTask {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let class = value.get("_class").and_then(serde_json::Value::as_str);
+        if class == Some(PLACEHOLDER_TASK_CLASS) {
+            if let Ok(task) = serde_json::from_value(value.clone()) {
+                return Ok(QueueTask::PlaceholderTask(task));
+            }
+        } else if let Ok(job) = serde_json::from_value(value.clone()) {
+            return Ok(QueueTask::Job(job));
+        }
+        Ok(QueueTask::Unknown(value))
+    }
+}
+
+/// A `node` block of a running pipeline waiting for an executor
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceholderTask {
+    /// Name of the task, if it was requested
+    pub name: Option<String>,
+    /// URL to the pipeline running the block, if it was requested
+    pub url: Option<String>,
+
+    #[cfg(not(feature = "extra-fields-visibility"))]
+    #[serde(flatten)]
+    pub(crate) extra_fields: Option<serde_json::Value>,
+    #[cfg(feature = "extra-fields-visibility")]
+    /// Extra fields not parsed for a common object
+    #[serde(flatten)]
+    pub extra_fields: Option<serde_json::Value>,
+}
+
 /// A queued item in Jenkins, with information about the `Job` and why / since when it's waiting
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -53,12 +127,13 @@ pub struct QueueItem {
     pub id: u32,
     /// When was it added to the queue
     pub in_queue_since: u64,
-    /// Task parameters
+    /// Task parameters, as a raw string of `name=value` lines. See `QueueItem::parameters` to
+    /// get them by name
     pub params: String,
     /// Is the job stuck? Node needed is offline, or waitied for very long in queue
     pub stuck: bool,
-    /// Link to the job waiting in the queue
-    pub task: ShortJob,
+    /// Task waiting in the queue
+    pub task: QueueTask,
     /// URL to this queued item
     pub url: String,
     /// Why is this task in the queue
@@ -71,11 +146,60 @@ pub struct QueueItem {
     pub actions: Vec<CommonAction>,
 }
 impl QueueItem {
+    /// Get the parameters of this item by name, parsed from `params`
+    ///
+    /// Values are the short descriptions of the parameters provided by Jenkins, without their
+    /// quotes. Passwords are masked.
+    pub fn parameters(&self) -> BTreeMap<String, String> {
+        parse_params(&self.params)
+    }
+
+    /// Get the priority of this item, if the Priority Sorter plugin is installed
+    pub fn priority(&self) -> Option<PrioritySorterAction> {
+        self.actions
+            .iter()
+            .find_map(|action| action.as_variant::<PrioritySorterAction>().ok())
+    }
+
+    /// Cancel this item, removing it from the queue
+    pub async fn cancel(&self, jenkins_client: &Jenkins) -> Result<()> {
+        jenkins_client.cancel_queue_item(self.id).await
+    }
+
+    /// Poll this item every `interval` until its build starts or it is cancelled, giving up
+    /// after `timeout` with `Error::PollTimeout`
+    pub async fn wait_for_outcome(
+        &self,
+        jenkins_client: &Jenkins,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<QueueItemOutcome> {
+        poll_until(
+            interval,
+            timeout,
+            Duration::ZERO,
+            &CancellationToken::new(),
+            || async move {
+                let item: QueueItem = jenkins_client
+                    .get(&Path::QueueItem { id: self.id as i32 })
+                    .await?
+                    .parse_json()
+                    .await?;
+                Ok(match item.executable {
+                    Some(build) => Some(QueueItemOutcome::Started(build)),
+                    None if item.cancelled == Some(true) => Some(QueueItemOutcome::Cancelled),
+                    None => None,
+                })
+            },
+        )
+        .await
+    }
+
     /// Refresh a `QueueItem`, consuming the existing one and returning a new `QueueItem`
     pub async fn refresh_item(self, jenkins_client: &Jenkins) -> Result<Self> {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::QueueItem { .. } = path {
-            Ok(jenkins_client.get(&path).await?.json().await?)
+            Ok(jenkins_client.get(&path).await?.parse_json().await?)
         } else {
             Err(client::Error::InvalidUrl {
                 url: self.url.clone(),
@@ -86,22 +210,452 @@ impl QueueItem {
     }
 }
 
+/// How a `QueueItem` left the queue
+#[derive(Debug, Clone)]
+pub enum QueueItemOutcome {
+    /// The build of the item started
+    Started(ShortBuild),
+    /// The item was cancelled before its build started
+    Cancelled,
+}
+
+/// Parse the `params` of a `QueueItem`, with one parameter per line like `name='value'` or
+/// `name=value`. Values of text parameters can span several lines while their quote is open
+fn parse_params(params: &str) -> BTreeMap<String, String> {
+    fn is_open_quote(value: &str) -> bool {
+        value.starts_with('\'') && (value.len() == 1 || !value.ends_with('\''))
+    }
+    fn unquote(value: &str) -> &str {
+        value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix('\''))
+            .unwrap_or(value)
+    }
+
+    let mut parameters = BTreeMap::new();
+    let mut current: Option<(&str, String)> = None;
+    for line in params.lines() {
+        match current {
+            Some((_, ref mut value)) if is_open_quote(value) => {
+                value.push('\n');
+                value.push_str(line);
+            }
+            _ => {
+                if let Some((name, value)) = line.split_once('=') {
+                    if let Some((name, value)) = current.take() {
+                        let _ = parameters.insert(name.to_string(), unquote(&value).to_string());
+                    }
+                    current = Some((name, value.to_string()));
+                }
+            }
+        }
+    }
+    if let Some((name, value)) = current {
+        let _ = parameters.insert(name.to_string(), unquote(&value).to_string());
+    }
+    parameters
+}
+
 /// The Jenkins `Queue`, the list of `QueueItem` that are waiting to be built
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Queue {
     /// List of items currently in the queue
     pub items: Vec<QueueItem>,
 }
+impl Queue {
+    /// Sort the items in the order the Priority Sorter plugin would build them: by weight, or
+    /// priority if there is no weight, then by time in queue. Items without priority are last
+    pub fn sort_by_priority(&mut self) {
+        let mut items = std::mem::take(&mut self.items)
+            .into_iter()
+            .map(|item| (item.priority(), item))
+            .collect::<Vec<_>>();
+        items.sort_by(|(priority_a, item_a), (priority_b, item_b)| {
+            let weight = |priority: &Option<PrioritySorterAction>| {
+                priority.map(|priority| priority.weight.unwrap_or(f64::from(priority.priority)))
+            };
+            match (weight(priority_a), weight(priority_b)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+            .then(item_a.in_queue_since.cmp(&item_b.in_queue_since))
+        });
+        self.items = items.into_iter().map(|(_, item)| item).collect();
+    }
+
+    /// Get the items with a priority of at most `priority`, in the order of the queue
+    pub fn items_with_priority_at_most(&self, priority: i32) -> Vec<&QueueItem> {
+        self.items
+            .iter()
+            .filter(|item| {
+                item.priority()
+                    .is_some_and(|item_priority| item_priority.priority <= priority)
+            })
+            .collect()
+    }
+}
 
 impl Jenkins {
     /// Get the Jenkins items queue
     pub async fn get_queue(&self) -> Result<Queue> {
-        Ok(self.get(&Path::Queue).await?.json().await?)
+        self.get(&Path::Queue).await?.parse_json().await
+    }
+
+    /// Get the page of items of the queue at `cursor`, in the order of the queue
+    pub async fn get_queue_at(&self, cursor: Cursor) -> Result<Page<QueueItem>> {
+        let tree = TreeBuilder::new()
+            .with_field(
+                TreeBuilder::object("items")
+                    .with_subfield("blocked")
+                    .with_subfield("buildable")
+                    .with_subfield("cancelled")
+                    .with_subfield("id")
+                    .with_subfield("inQueueSince")
+                    .with_subfield("params")
+                    .with_subfield("stuck")
+                    .with_subfield(
+                        TreeBuilder::object("task")
+                            .with_subfield("_class")
+                            .with_subfield("name")
+                            .with_subfield("url")
+                            .with_subfield("color"),
+                    )
+                    .with_subfield("url")
+                    .with_subfield("why")
+                    .with_subfield("buildableStartMilliseconds")
+                    .with_subfield(
+                        TreeBuilder::object("executable")
+                            .with_subfield("number")
+                            .with_subfield("url"),
+                    )
+                    .with_subfield(
+                        TreeBuilder::object("actions")
+                            .with_subfield("_class")
+                            .with_subfield("priority")
+                            .with_subfield("weight"),
+                    )
+                    .with_cursor(cursor),
+            )
+            .build();
+        let queue: Queue = self
+            .get_with_params(
+                &Path::Queue,
+                InternalAdvancedQueryParams::from(AdvancedQuery::Tree(tree)),
+            )
+            .await?
+            .parse_json()
+            .await?;
+        Ok(Page::new(queue.items, cursor))
     }
 
     /// Get a queue item from it's ID
     pub async fn get_queue_item(&self, id: i32) -> Result<QueueItem> {
-        Ok(self.get(&Path::QueueItem { id }).await?.json().await?)
+        self.get(&Path::QueueItem { id }).await?.parse_json().await
+    }
+
+    /// Cancel the queue item with the ID `id`, removing it from the queue
+    pub async fn cancel_queue_item(&self, id: u32) -> Result<()> {
+        let _ = self
+            .post_with_body(&Path::QueueCancelItem, "", &[("id", &id.to_string())])
+            .await?;
+        Ok(())
+    }
+
+    /// Cancel the items of the queue matching `predicate`, sending up to `concurrency` requests
+    /// at a time. Each matching item is returned with the result of its cancellation, in the
+    /// order of the queue
+    pub async fn cancel_queue_items_where<F>(
+        &self,
+        predicate: F,
+        concurrency: usize,
+    ) -> Result<Vec<(QueueItem, Result<()>)>>
+    where
+        F: Fn(&QueueItem) -> bool,
+    {
+        let queue = self.get_queue().await?;
+        Ok(
+            stream::iter(queue.items.into_iter().filter(|item| predicate(item)))
+                .map(|item| async move {
+                    let result = self.cancel_queue_item(item.id).await;
+                    (item, result)
+                })
+                .buffered(concurrency.max(1))
+                .collect()
+                .await,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue_item(id: u32, in_queue_since: u64, priority: Option<(i32, f64)>) -> serde_json::Value {
+        let actions = match priority {
+            Some((priority, weight)) => serde_json::json!([{
+                "_class": "jenkins.advancedqueue.PrioritySorterAction",
+                "priority": priority,
+                "weight": weight
+            }]),
+            None => serde_json::json!([]),
+        };
+        serde_json::json!({
+            "blocked": false,
+            "buildable": true,
+            "id": id,
+            "inQueueSince": in_queue_since,
+            "params": "",
+            "stuck": false,
+            "task": {"name": "job", "url": "http://localhost/job/job/"},
+            "url": format!("queue/item/{}/", id),
+            "actions": actions
+        })
+    }
+
+    #[test]
+    fn can_sort_queue_by_priority() {
+        let mut queue: Queue = serde_json::from_value(serde_json::json!({
+            "items": [
+                queue_item(1, 100, None),
+                queue_item(2, 300, Some((3, 3.0))),
+                queue_item(3, 200, Some((1, 1.0))),
+                queue_item(4, 100, Some((3, 3.0))),
+            ]
+        }))
+        .unwrap();
+
+        queue.sort_by_priority();
+
+        assert_eq!(
+            queue.items.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![3, 4, 2, 1]
+        );
+        assert_eq!(
+            queue
+                .items_with_priority_at_most(2)
+                .iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn can_parse_params() {
+        let mut item = queue_item(1, 100, None);
+        item["params"] = serde_json::json!(
+            "\nBRANCH='main'\nDRY_RUN=true\nNOTES='first line\nsecond=line'\nTOKEN=****"
+        );
+        let item: QueueItem = serde_json::from_value(item).unwrap();
+
+        let parameters = item.parameters();
+
+        assert_eq!(parameters.len(), 4);
+        assert_eq!(parameters["BRANCH"], "main");
+        assert_eq!(parameters["DRY_RUN"], "true");
+        assert_eq!(parameters["NOTES"], "first line\nsecond=line");
+        assert_eq!(parameters["TOKEN"], "****");
+    }
+
+    #[test]
+    fn can_parse_tasks() {
+        let mut queue: Queue = serde_json::from_value(serde_json::json!({
+            "items": [queue_item(1, 100, None), queue_item(2, 100, None), queue_item(3, 100, None)]
+        }))
+        .unwrap();
+        let mut placeholder = queue_item(2, 100, None);
+        placeholder["task"] = serde_json::json!({ "_class": PLACEHOLDER_TASK_CLASS });
+        queue.items[1] = serde_json::from_value(placeholder).unwrap();
+        let mut other = queue_item(3, 100, None);
+        other["task"] = serde_json::json!({ "_class": "some.plugin.Task", "url": "task/" });
+        queue.items[2] = serde_json::from_value(other).unwrap();
+
+        assert_eq!(
+            queue.items[0].task.as_job().map(|job| job.name.as_str()),
+            Some("job")
+        );
+        assert!(matches!(
+            queue.items[1].task,
+            QueueTask::PlaceholderTask(PlaceholderTask { url: None, .. })
+        ));
+        assert!(matches!(queue.items[2].task, QueueTask::Unknown(_)));
+        assert_eq!(queue.items[2].task.url(), Some("task/"));
+    }
+
+    #[tokio::test]
+    async fn can_cancel_queue_items_where() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mut items = vec![];
+        for id in 1..=4 {
+            let mut item = queue_item(id, 100, None);
+            item["stuck"] = serde_json::json!(id != 2);
+            items.push(item);
+        }
+        let _queue = server
+            .mock("GET", "/queue/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(serde_json::json!({ "items": items }).to_string())
+            .create();
+        let mut cancels = vec![];
+        for (id, status) in [("1", 200), ("3", 404), ("4", 200)] {
+            cancels.push(
+                server
+                    .mock("POST", "/queue/cancelItem")
+                    .match_query(mockito::Matcher::UrlEncoded(
+                        "id".to_string(),
+                        id.to_string(),
+                    ))
+                    .with_status(status)
+                    .create(),
+            );
+        }
+
+        let results = jenkins_client
+            .cancel_queue_items_where(|item| item.stuck, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|(item, result)| (item.id, result.is_ok()))
+                .collect::<Vec<_>>(),
+            vec![(1, true), (3, false), (4, true)]
+        );
+        for cancel in cancels {
+            cancel.assert();
+        }
+    }
+
+    #[tokio::test]
+    async fn can_cancel_and_wait_for_queue_items() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let waiting: QueueItem = serde_json::from_value(queue_item(7, 100, None)).unwrap();
+        let waiting_item = server
+            .mock("GET", "/queue/item/7/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(queue_item(7, 100, None).to_string())
+            .expect(1)
+            .create();
+        let mut started = queue_item(7, 100, None);
+        started["executable"] = serde_json::json!({
+            "number": 12,
+            "url": format!("{}/job/job/12/", server.url())
+        });
+        let started_item = server
+            .mock("GET", "/queue/item/7/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(started.to_string())
+            .expect_at_least(1)
+            .create();
+        let mut cancelled = queue_item(8, 100, None);
+        cancelled["cancelled"] = serde_json::json!(true);
+        let cancelled_item = server
+            .mock("GET", "/queue/item/8/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(cancelled.to_string())
+            .expect_at_least(1)
+            .create();
+        let cancel = server
+            .mock("POST", "/queue/cancelItem")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "id".to_string(),
+                "8".to_string(),
+            ))
+            .create();
+
+        let outcome = waiting
+            .wait_for_outcome(
+                &jenkins_client,
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, QueueItemOutcome::Started(build) if build.number == 12));
+
+        let item: QueueItem = serde_json::from_value(queue_item(8, 100, None)).unwrap();
+        item.cancel(&jenkins_client).await.unwrap();
+        let outcome = item
+            .wait_for_outcome(
+                &jenkins_client,
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, QueueItemOutcome::Cancelled));
+        waiting_item.assert();
+        started_item.assert();
+        cancelled_item.assert();
+        cancel.assert();
+    }
+
+    #[test]
+    fn can_round_trip_queue() {
+        let mut placeholder = queue_item(2, 100, Some((1, 1.0)));
+        placeholder["task"] =
+            serde_json::json!({ "_class": PLACEHOLDER_TASK_CLASS, "name": "part" });
+        let queue: Queue = serde_json::from_value(serde_json::json!({
+            "items": [queue_item(1, 100, None), placeholder]
+        }))
+        .unwrap();
+
+        let json = serde_json::to_value(&queue).unwrap();
+        let reloaded: Queue = serde_json::from_value(json.clone()).unwrap();
+
+        assert!(matches!(
+            reloaded.items[1].task,
+            QueueTask::PlaceholderTask(_)
+        ));
+        assert_eq!(reloaded.items[1].priority().unwrap().priority, 1);
+        assert_eq!(serde_json::to_value(&reloaded).unwrap(), json);
+    }
+
+    #[tokio::test]
+    async fn can_page_through_queue() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let first = server
+            .mock("GET", "/queue/api/json")
+            .match_query(mockito::Matcher::Regex(r"items%5B.*%5D%7B0%2C2%7D".to_string()))
+            .with_body(
+                serde_json::json!({ "items": [queue_item(1, 100, None), queue_item(2, 100, None)] })
+                    .to_string(),
+            )
+            .create();
+        let second = server
+            .mock("GET", "/queue/api/json")
+            .match_query(mockito::Matcher::Regex(
+                r"items%5B.*%5D%7B2%2C4%7D".to_string(),
+            ))
+            .with_body(serde_json::json!({ "items": [queue_item(3, 100, None)] }).to_string())
+            .create();
+
+        let mut ids = vec![];
+        let mut cursor = Some(Cursor::first(2));
+        while let Some(current) = cursor {
+            let page = jenkins_client.get_queue_at(current).await.unwrap();
+            cursor = page.next_page();
+            ids.extend(page.into_iter().map(|item| item.id));
+        }
+
+        assert_eq!(ids, vec![1, 2, 3]);
+        first.assert();
+        second.assert();
     }
 }
@@ -0,0 +1,429 @@
+//! Audit of how long builds and their artifacts are kept
+//!
+//! The build discarder of a job is read from its `config.xml`. Artifact sizes are not part of
+//! the JSON API, they are read with a `HEAD` request on each artifact.
+
+use futures_util::stream::{self, StreamExt};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Deserialize;
+
+use crate::build::{get_artifact_size, Artifact};
+use crate::client::{Result, TreeBuilder};
+use crate::client_internals::path::Path as PrivatePath;
+use crate::Jenkins;
+
+/// Number of builds listed in `RetentionAudit::largest_builds`
+const LARGEST_BUILDS: usize = 10;
+
+/// Number of artifact sizes requested at a time
+const ARTIFACT_REQUESTS: usize = 8;
+
+/// Class of the usual build discarder, whose limits are read from `config.xml`
+const LOG_ROTATOR_CLASS: &str = "hudson.tasks.LogRotator";
+
+/// Strategy discarding old builds of a job, usually a `hudson.tasks.LogRotator`
+///
+/// The limits are only read for a `LogRotator`, other strategies like the ones of the Build
+/// Rotator plugin have all their limits unset.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BuildDiscarder {
+    /// Class of the strategy, missing for the `logRotator` of jobs created before Jenkins 1.503
+    pub class: Option<String>,
+    /// Number of days builds are kept
+    pub days_to_keep: Option<u32>,
+    /// Number of builds kept
+    pub num_to_keep: Option<u32>,
+    /// Number of days artifacts are kept
+    pub artifact_days_to_keep: Option<u32>,
+    /// Number of builds whose artifacts are kept
+    pub artifact_num_to_keep: Option<u32>,
+}
+
+impl BuildDiscarder {
+    /// Read the build discarder from the `config.xml` of a job, if it has one
+    pub fn from_config_xml(config_xml: &str) -> Result<Option<Self>> {
+        let mut reader = Reader::from_str(config_xml);
+        reader.config_mut().trim_text(true);
+
+        let mut discarder: Option<BuildDiscarder> = None;
+        // depth of the element holding the settings of the discarder
+        let mut discarder_depth = None;
+        let mut parents: Vec<String> = vec![];
+        let mut text = String::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) => {
+                    let name = std::str::from_utf8(element.name().as_ref())?.to_string();
+                    let is_discarder = match parents.last().map(String::as_str) {
+                        Some("jenkins.model.BuildDiscarderProperty") => name == "strategy",
+                        // jobs created before Jenkins 1.503 have a rotator at their root
+                        Some(_) => parents.len() == 1 && name == "logRotator",
+                        None => false,
+                    };
+                    if discarder.is_none() && is_discarder {
+                        discarder_depth = Some(parents.len());
+                        discarder = Some(BuildDiscarder {
+                            class: match element.try_get_attribute("class")? {
+                                Some(class) => Some(class.unescape_value()?.into_owned()),
+                                None => None,
+                            },
+                            ..Default::default()
+                        });
+                    }
+                    parents.push(name);
+                    text.clear();
+                }
+                Event::Text(content) => text.push_str(&content.unescape()?),
+                Event::End(_) => {
+                    let name = parents.pop().unwrap_or_default();
+                    if let (Some(depth), Some(discarder)) = (discarder_depth, discarder.as_mut()) {
+                        if depth == parents.len() {
+                            discarder_depth = None;
+                        } else if depth + 1 == parents.len() {
+                            // a negative value means no limit
+                            let value = text.trim().parse().ok();
+                            match name.as_str() {
+                                "daysToKeep" => discarder.days_to_keep = value,
+                                "numToKeep" => discarder.num_to_keep = value,
+                                "artifactDaysToKeep" => discarder.artifact_days_to_keep = value,
+                                "artifactNumToKeep" => discarder.artifact_num_to_keep = value,
+                                _ => (),
+                            }
+                        }
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(discarder)
+    }
+
+    /// Is the strategy a `hudson.tasks.LogRotator`, whose limits are known
+    pub fn is_log_rotator(&self) -> bool {
+        self.class
+            .as_deref()
+            .is_none_or(|class| class == LOG_ROTATOR_CLASS)
+    }
+
+    /// Are artifacts kept forever, as the strategy is a `LogRotator` limiting neither builds
+    /// nor artifacts
+    pub fn keeps_all_artifacts(&self) -> bool {
+        self.is_log_rotator()
+            && self.days_to_keep.is_none()
+            && self.num_to_keep.is_none()
+            && self.artifact_days_to_keep.is_none()
+            && self.artifact_num_to_keep.is_none()
+    }
+}
+
+/// Size of the artifacts of a build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildArtifactsSize {
+    /// Full name of the job
+    pub job_name: String,
+    /// Number of the build
+    pub number: u32,
+    /// URL of the build
+    pub url: String,
+    /// Number of artifacts
+    pub artifacts: usize,
+    /// Total size of the artifacts, in bytes
+    pub size: u64,
+}
+
+/// An artifact whose size couldn't be read during an audit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactSizeError {
+    /// URL of the build
+    pub build_url: String,
+    /// Path to the artifact
+    pub relative_path: String,
+    /// Error received
+    pub error: String,
+}
+
+/// Result of an audit of the retention of builds and artifacts
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RetentionAudit {
+    /// Full names of the jobs without a build discarder, keeping all their builds
+    pub jobs_without_discarder: Vec<String>,
+    /// Full names of the jobs with a build discarder that doesn't limit builds nor artifacts
+    pub jobs_keeping_all_artifacts: Vec<String>,
+    /// Full names of the jobs with a build discarder other than a `LogRotator`, whose limits
+    /// are unknown
+    pub jobs_with_other_discarder: Vec<String>,
+    /// Builds with the largest artifacts, from the largest
+    pub largest_builds: Vec<BuildArtifactsSize>,
+    /// Artifacts whose size couldn't be read, not counted in `largest_builds`
+    pub artifact_errors: Vec<ArtifactSizeError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuildArtifacts {
+    number: u32,
+    url: String,
+    #[serde(default)]
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuditedJob {
+    #[serde(default)]
+    builds: Vec<BuildArtifacts>,
+}
+
+impl Jenkins {
+    /// Audit the retention of builds and artifacts of all the jobs, in all folders
+    ///
+    /// Jobs without a build discarder or keeping all their artifacts are listed, with the 10
+    /// builds with the largest artifacts. Only the builds listed on each job (usually the last
+    /// 100) are considered, and the size of each of their artifacts is requested, 8 at a time.
+    /// Artifacts whose size can't be read are listed instead of failing the audit.
+    pub async fn audit_retention(&self) -> Result<RetentionAudit> {
+        let folders = self
+            .walk_folders::<(), AuditedJob>(
+                |tree| tree,
+                |jobs| {
+                    jobs.with_subfield(
+                        TreeBuilder::object("builds")
+                            .with_subfield("number")
+                            .with_subfield("url")
+                            .with_subfield(
+                                TreeBuilder::object("artifacts")
+                                    .with_subfield("fileName")
+                                    .with_subfield("relativePath"),
+                            ),
+                    )
+                },
+            )
+            .await?;
+
+        let mut audit = RetentionAudit::default();
+        let mut builds = vec![];
+        for job in folders.into_iter().flat_map(|folder| folder.jobs) {
+            if job.is_folder {
+                continue;
+            }
+            let full_name = job.path.full_name();
+            let config_xml = self
+                .get_endpoint(&job.path.to_path_with(|job_name| PrivatePath::ConfigXML {
+                    job_name,
+                    folder_name: None,
+                })?)
+                .await?
+                .text()
+                .await?;
+            match BuildDiscarder::from_config_xml(&config_xml)? {
+                None => audit.jobs_without_discarder.push(full_name.clone()),
+                Some(discarder) if !discarder.is_log_rotator() => {
+                    audit.jobs_with_other_discarder.push(full_name.clone())
+                }
+                Some(discarder) if discarder.keeps_all_artifacts() => {
+                    audit.jobs_keeping_all_artifacts.push(full_name.clone())
+                }
+                Some(_) => (),
+            }
+            builds.extend(
+                job.fields
+                    .builds
+                    .into_iter()
+                    .filter(|build| !build.artifacts.is_empty())
+                    .map(|build| (full_name.clone(), build)),
+            );
+        }
+
+        let sizes: Vec<_> =
+            stream::iter(builds.iter().enumerate().flat_map(|(index, (_, build))| {
+                build
+                    .artifacts
+                    .iter()
+                    .map(move |artifact| (index, build, artifact.clone()))
+            }))
+            .map(|(index, build, artifact)| async move {
+                let relative_path = artifact.relative_path.clone();
+                let size = get_artifact_size(self, &self.url_to_path(&build.url), artifact).await;
+                (index, relative_path, size)
+            })
+            .buffer_unordered(ARTIFACT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut totals = vec![0; builds.len()];
+        for (index, relative_path, size) in sizes {
+            match size {
+                Ok(size) => totals[index] += size.size.unwrap_or(0),
+                Err(error) => audit.artifact_errors.push(ArtifactSizeError {
+                    build_url: builds[index].1.url.clone(),
+                    relative_path,
+                    error: error.to_string(),
+                }),
+            }
+        }
+        audit.largest_builds = builds
+            .into_iter()
+            .zip(totals)
+            .map(|((job_name, build), size)| BuildArtifactsSize {
+                job_name,
+                number: build.number,
+                url: build.url,
+                artifacts: build.artifacts.len(),
+                size,
+            })
+            .collect();
+        audit
+            .largest_builds
+            .sort_by_key(|build| std::cmp::Reverse(build.size));
+        audit.largest_builds.truncate(LARGEST_BUILDS);
+        Ok(audit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_build_discarder() {
+        let discarder = BuildDiscarder::from_config_xml(
+            r#"<?xml version='1.1' encoding='UTF-8'?>
+<project>
+  <properties>
+    <jenkins.model.BuildDiscarderProperty>
+      <strategy class="hudson.tasks.LogRotator">
+        <daysToKeep>30</daysToKeep>
+        <numToKeep>-1</numToKeep>
+        <artifactDaysToKeep>-1</artifactDaysToKeep>
+        <artifactNumToKeep>5</artifactNumToKeep>
+      </strategy>
+    </jenkins.model.BuildDiscarderProperty>
+  </properties>
+</project>"#,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(discarder.class.as_deref(), Some("hudson.tasks.LogRotator"));
+        assert_eq!(discarder.days_to_keep, Some(30));
+        assert_eq!(discarder.num_to_keep, None);
+        assert_eq!(discarder.artifact_num_to_keep, Some(5));
+        assert!(!discarder.keeps_all_artifacts());
+    }
+
+    #[tokio::test]
+    async fn can_audit_retention() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let jenkins_client = crate::JenkinsBuilder::new(&url).build().unwrap();
+
+        let root = server
+            .mock("GET", "/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "jobs": [
+                        {"name": "team", "jobs": [{"name": "app"}], "builds": []},
+                        {"name": "docs", "builds": [{
+                            "number": 3,
+                            "url": format!("{}/job/docs/3/", url),
+                            "artifacts": []
+                        }]},
+                        {"name": "site", "builds": []}
+                    ]
+                })
+                .to_string(),
+            )
+            .create();
+        let team = server
+            .mock("GET", "/job/team/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({
+                    "jobs": [{"name": "app", "builds": [
+                        {
+                            "number": 8,
+                            "url": format!("{}/job/team/job/app/8/", url),
+                            "artifacts": [
+                                {"fileName": "app.jar", "relativePath": "target/app.jar"},
+                                {"fileName": "app.pom", "relativePath": "target/app.pom"},
+                                {"fileName": "app.zip", "relativePath": "target/app.zip"}
+                            ]
+                        },
+                        {
+                            "number": 7,
+                            "url": format!("{}/job/team/job/app/7/", url),
+                            "artifacts": [
+                                {"fileName": "app.jar", "relativePath": "target/app.jar"}
+                            ]
+                        }
+                    ]}]
+                })
+                .to_string(),
+            )
+            .create();
+        let docs_config = server
+            .mock("GET", "/job/docs/config.xml")
+            .with_body("<project><properties/></project>")
+            .create();
+        let app_config = server
+            .mock("GET", "/job/team/job/app/config.xml")
+            .with_body(
+                "<project><properties><jenkins.model.BuildDiscarderProperty><strategy class=\"hudson.tasks.LogRotator\"><daysToKeep>-1</daysToKeep><numToKeep>-1</numToKeep><artifactDaysToKeep>-1</artifactDaysToKeep><artifactNumToKeep>-1</artifactNumToKeep></strategy></jenkins.model.BuildDiscarderProperty></properties></project>",
+            )
+            .create();
+        let site_config = server
+            .mock("GET", "/job/site/config.xml")
+            .with_body(
+                "<project><properties><jenkins.model.BuildDiscarderProperty><strategy class=\"tech.nineworks.jenkins.plugins.buildrotator.BuildRotator\"><count>3</count></strategy></jenkins.model.BuildDiscarderProperty></properties></project>",
+            )
+            .create();
+        let missing = server
+            .mock("HEAD", "/job/team/job/app/8/artifact/target/app.zip")
+            .with_status(404)
+            .create();
+        let mut sizes = vec![];
+        for (artifact, size) in [
+            ("/job/team/job/app/8/artifact/target/app.jar", "2048"),
+            ("/job/team/job/app/8/artifact/target/app.pom", "100"),
+            ("/job/team/job/app/7/artifact/target/app.jar", "4096"),
+        ] {
+            sizes.push(
+                server
+                    .mock("HEAD", artifact)
+                    .with_header("content-length", size)
+                    .create(),
+            );
+        }
+
+        let audit = jenkins_client.audit_retention().await.unwrap();
+
+        assert_eq!(audit.jobs_without_discarder, vec!["docs"]);
+        assert_eq!(audit.jobs_keeping_all_artifacts, vec!["team/app"]);
+        assert_eq!(audit.jobs_with_other_discarder, vec!["site"]);
+        assert_eq!(audit.artifact_errors.len(), 1);
+        assert_eq!(
+            audit.artifact_errors[0].build_url,
+            format!("{}/job/team/job/app/8/", url)
+        );
+        assert_eq!(audit.artifact_errors[0].relative_path, "target/app.zip");
+        assert_eq!(
+            audit
+                .largest_builds
+                .iter()
+                .map(|build| (build.number, build.artifacts, build.size))
+                .collect::<Vec<_>>(),
+            vec![(7, 1, 4096), (8, 3, 2148)]
+        );
+        root.assert();
+        team.assert();
+        docs_config.assert();
+        app_config.assert();
+        site_config.assert();
+        missing.assert();
+        for size in sizes {
+            size.assert();
+        }
+    }
+}
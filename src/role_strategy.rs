@@ -0,0 +1,287 @@
+//! Roles of the Role-based Authorization Strategy plugin
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_internals::{Path, ResponseExt, Result};
+use crate::Jenkins;
+
+/// Type of a role, deciding on what it grants permissions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleType {
+    /// Role granting permissions on all of Jenkins
+    Global,
+    /// Role granting permissions on the items matching its pattern
+    Project,
+    /// Role granting permissions on the nodes matching its pattern
+    Agent,
+}
+
+impl RoleType {
+    fn as_str(self) -> &'static str {
+        match self {
+            RoleType::Global => "globalRoles",
+            RoleType::Project => "projectRoles",
+            RoleType::Agent => "slaveRoles",
+        }
+    }
+}
+
+/// Kind of a SID assigned to a role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SidType {
+    /// The SID is a user
+    User,
+    /// The SID is a group
+    Group,
+    /// The SID can be a user or a group, as assigned by older versions of the plugin
+    Either,
+}
+
+/// A user or group assigned to a role
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignedSid {
+    /// Name of the user or group
+    pub sid: String,
+    /// Kind of the SID
+    pub sid_type: SidType,
+}
+
+/// Older versions of the plugin list SIDs as strings, newer ones with their type
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawSid {
+    Typed {
+        #[serde(rename = "type")]
+        sid_type: SidType,
+        sid: String,
+    },
+    Name(String),
+}
+
+impl From<RawSid> for AssignedSid {
+    fn from(sid: RawSid) -> Self {
+        match sid {
+            RawSid::Typed { sid_type, sid } => AssignedSid { sid, sid_type },
+            RawSid::Name(sid) => AssignedSid {
+                sid,
+                sid_type: SidType::Either,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRole {
+    #[serde(default)]
+    permission_ids: BTreeMap<String, bool>,
+    pattern: Option<String>,
+    #[serde(default)]
+    sids: Vec<RawSid>,
+}
+
+/// A role of the Role-based Authorization Strategy plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Role {
+    /// Name of the role
+    pub name: String,
+    /// Type of the role
+    pub role_type: RoleType,
+    /// Regular expression matching the items or nodes on which the role applies, for project and
+    /// agent roles
+    pub pattern: Option<String>,
+    /// IDs of the permissions granted by the role, like `hudson.model.Item.Build`
+    pub permissions: Vec<String>,
+    /// Users and groups assigned to the role
+    pub sids: Vec<AssignedSid>,
+}
+
+impl Jenkins {
+    /// Get the names of the roles of type `role_type`, with the SIDs assigned to them
+    pub async fn get_role_assignments(
+        &self,
+        role_type: RoleType,
+    ) -> Result<BTreeMap<String, Vec<AssignedSid>>> {
+        let roles: BTreeMap<String, Vec<RawSid>> = self
+            .get_endpoint_with_params(
+                &Path::RoleStrategy {
+                    method: "getAllRoles",
+                },
+                [("type", role_type.as_str())],
+            )
+            .await?
+            .parse_json()
+            .await?;
+        Ok(roles
+            .into_iter()
+            .map(|(name, sids)| (name, sids.into_iter().map(AssignedSid::from).collect()))
+            .collect())
+    }
+
+    /// Get the role of type `role_type` named `name`
+    pub async fn get_role(&self, role_type: RoleType, name: &str) -> Result<Role> {
+        let role: RawRole = self
+            .get_endpoint_with_params(
+                &Path::RoleStrategy { method: "getRole" },
+                [("type", role_type.as_str()), ("roleName", name)],
+            )
+            .await?
+            .parse_json()
+            .await?;
+        Ok(Role {
+            name: name.to_string(),
+            role_type,
+            pattern: role.pattern,
+            permissions: role
+                .permission_ids
+                .into_iter()
+                .filter(|(_, granted)| *granted)
+                .map(|(permission, _)| permission)
+                .collect(),
+            sids: role.sids.into_iter().map(AssignedSid::from).collect(),
+        })
+    }
+
+    /// Get all the roles of type `role_type`, ordered by name
+    pub async fn get_roles(&self, role_type: RoleType) -> Result<Vec<Role>> {
+        let mut roles = vec![];
+        for name in self.get_role_assignments(role_type).await?.keys() {
+            roles.push(self.get_role(role_type, name).await?);
+        }
+        Ok(roles)
+    }
+
+    /// Assign the role of type `role_type` named `role` to the user or group `sid`
+    pub async fn assign_role(&self, role_type: RoleType, role: &str, sid: &str) -> Result<()> {
+        self.change_role_assignment("assignRole", role_type, role, sid)
+            .await
+    }
+
+    /// Remove the role of type `role_type` named `role` from the user or group `sid`
+    pub async fn unassign_role(&self, role_type: RoleType, role: &str, sid: &str) -> Result<()> {
+        self.change_role_assignment("unassignRole", role_type, role, sid)
+            .await
+    }
+
+    async fn change_role_assignment(
+        &self,
+        method: &str,
+        role_type: RoleType,
+        role: &str,
+        sid: &str,
+    ) -> Result<()> {
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("type", role_type.as_str())
+            .append_pair("roleName", role)
+            .append_pair("sid", sid)
+            .finish();
+        let _ = self
+            .post_with_body(&Path::RoleStrategy { method }, body, &[])
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_roles() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let all_roles = server
+            .mock("GET", "/role-strategy/strategy/getAllRoles")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "type".to_string(),
+                "projectRoles".to_string(),
+            ))
+            .with_body(r#"{"deployers":[{"type":"GROUP","sid":"ops"}],"readers":["alice"]}"#)
+            .create();
+        let deployers = server
+            .mock("GET", "/role-strategy/strategy/getRole")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "roleName".to_string(),
+                "deployers".to_string(),
+            ))
+            .with_body(
+                r#"{"permissionIds":{"hudson.model.Item.Build":true,"hudson.model.Item.Delete":false},"pattern":"deploy-.*","sids":[{"type":"GROUP","sid":"ops"}]}"#,
+            )
+            .create();
+        let readers = server
+            .mock("GET", "/role-strategy/strategy/getRole")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "roleName".to_string(),
+                "readers".to_string(),
+            ))
+            .with_body(
+                r#"{"permissionIds":{"hudson.model.Item.Read":true},"pattern":".*","sids":["alice"]}"#,
+            )
+            .create();
+
+        let roles = jenkins_client.get_roles(RoleType::Project).await.unwrap();
+
+        assert_eq!(
+            roles,
+            vec![
+                Role {
+                    name: "deployers".to_string(),
+                    role_type: RoleType::Project,
+                    pattern: Some("deploy-.*".to_string()),
+                    permissions: vec!["hudson.model.Item.Build".to_string()],
+                    sids: vec![AssignedSid {
+                        sid: "ops".to_string(),
+                        sid_type: SidType::Group,
+                    }],
+                },
+                Role {
+                    name: "readers".to_string(),
+                    role_type: RoleType::Project,
+                    pattern: Some(".*".to_string()),
+                    permissions: vec!["hudson.model.Item.Read".to_string()],
+                    sids: vec![AssignedSid {
+                        sid: "alice".to_string(),
+                        sid_type: SidType::Either,
+                    }],
+                },
+            ]
+        );
+        all_roles.assert();
+        deployers.assert();
+        readers.assert();
+    }
+
+    #[tokio::test]
+    async fn can_assign_and_unassign_role() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let assign = server
+            .mock("POST", "/role-strategy/strategy/assignRole")
+            .match_body("type=globalRoles&roleName=admin&sid=bob")
+            .create();
+        let unassign = server
+            .mock("POST", "/role-strategy/strategy/unassignRole")
+            .match_body("type=slaveRoles&roleName=builders&sid=bob")
+            .create();
+
+        jenkins_client
+            .assign_role(RoleType::Global, "admin", "bob")
+            .await
+            .unwrap();
+        jenkins_client
+            .unassign_role(RoleType::Agent, "builders", "bob")
+            .await
+            .unwrap();
+
+        assign.assert();
+        unassign.assert();
+    }
+}
@@ -25,7 +25,7 @@ specialize!(CommonBrowser => Browser);
 impl Browser for CommonBrowser {}
 
 /// Github web browser
-#[derive(Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GithubWeb {}
 register_class!("hudson.plugins.git.browser.GithubWeb" =>  GithubWeb);
@@ -8,7 +8,7 @@ mod browser;
 pub use self::browser::*;
 
 /// SCM merge options
-#[derive(Default, Deserialize, Debug)]
+#[derive(Default, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 #[allow(dead_code)]
 pub struct MergeOptions {
@@ -45,7 +45,7 @@ specialize!(CommonSCM => SCM);
 impl SCM for CommonSCM {}
 
 /// No SCM
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct NullSCM {
     /// Browser
@@ -55,7 +55,7 @@ register_class!("hudson.scm.NullSCM" =>  NullSCM);
 impl SCM for NullSCM {}
 
 /// Git SCM
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GitSCM {
     /// Browser
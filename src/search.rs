@@ -0,0 +1,64 @@
+//! Jenkins search, to find items by name
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_internals::{Path, ResponseExt, Result};
+use crate::Jenkins;
+
+/// An item matching a search
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSuggestion {
+    /// Name of the item, as it can be entered in the search box of Jenkins
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchSuggestions {
+    suggestions: Vec<SearchSuggestion>,
+}
+
+impl Jenkins {
+    /// Search jobs, builds, views, nodes and users whose name contains `query`
+    ///
+    /// This uses the same index as the search box of Jenkins, so it only returns the items
+    /// visible to the current user.
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchSuggestion>> {
+        let response: SearchSuggestions = self
+            .get_endpoint_with_params(&Path::SearchSuggest, [("query", query)])
+            .await?
+            .parse_json()
+            .await?;
+        Ok(response.suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn can_search() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/search/suggest")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "query".to_string(),
+                "my job".to_string(),
+            ))
+            .with_body(r#"{"suggestions":[{"name":"my job"},{"name":"my job #2"}]}"#)
+            .create();
+
+        let suggestions = jenkins_client.search("my job").await.unwrap();
+
+        assert_eq!(
+            suggestions
+                .iter()
+                .map(|suggestion| suggestion.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["my job", "my job #2"]
+        );
+        mock.assert();
+    }
+}
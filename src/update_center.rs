@@ -0,0 +1,408 @@
+//! Jenkins update center, to manage plugins
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_internals::{Path, ResponseExt, Result};
+use crate::poll::{poll_until, CancellationToken};
+use crate::Jenkins;
+
+/// A plugin available on an update site
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSitePlugin {
+    /// Short name of the plugin
+    pub name: String,
+    /// Version available
+    pub version: String,
+    /// Title of the plugin
+    pub title: Option<String>,
+    /// URL to download the plugin
+    pub url: Option<String>,
+    /// Description of the plugin
+    pub excerpt: Option<String>,
+    /// Minimum version of Jenkins needed by the plugin
+    pub required_core: Option<String>,
+    /// ID of the update site
+    pub source_id: Option<String>,
+}
+
+/// An update site, where plugins are downloaded from
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSite {
+    /// ID of the update site
+    pub id: String,
+    /// URL of the update site
+    pub url: String,
+    /// Are there updates for installed plugins
+    #[serde(default)]
+    pub has_updates: bool,
+    /// When was the data of the update site last retrieved
+    pub data_timestamp: Option<i64>,
+    /// Updates available for installed plugins
+    #[serde(default)]
+    pub updates: Vec<UpdateSitePlugin>,
+}
+
+/// State of a job of the update center
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InstallationState {
+    /// The job is waiting to start
+    Pending,
+    /// The plugin is being downloaded and installed
+    Installing,
+    /// The plugin has been installed
+    Success,
+    /// The plugin has been installed, and will be enabled after a restart
+    SuccessButRequiresRestart,
+    /// The installation failed
+    Failure,
+    /// The installation was skipped, the plugin was already installed
+    Skipped,
+    /// The installation was canceled
+    Canceled,
+    /// Any other state
+    #[serde(other)]
+    Unknown,
+}
+
+impl InstallationState {
+    /// Is the job finished, successfully or not
+    pub fn is_finished(self) -> bool {
+        !matches!(
+            self,
+            InstallationState::Pending | InstallationState::Installing
+        )
+    }
+}
+
+/// Status of a job of the update center
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallationStatus {
+    /// State of the job
+    #[serde(rename = "type")]
+    pub state: InstallationState,
+    /// Did the job succeed
+    #[serde(default)]
+    pub success: bool,
+}
+
+/// A job of the update center, like the installation of a plugin
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCenterJob {
+    /// ID of the job
+    pub id: u32,
+    /// Type of job, like `InstallationJob` or `ConnectionCheckJob`
+    #[serde(rename = "type")]
+    pub job_type: String,
+    /// Name of the plugin, for installation jobs
+    pub name: Option<String>,
+    /// Error message, if the job failed
+    pub error_message: Option<String>,
+    /// Status of the job, for installation jobs
+    pub status: Option<InstallationStatus>,
+}
+
+/// The update center of Jenkins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCenter {
+    /// Plugins available for installation
+    #[serde(default)]
+    pub availables: Vec<UpdateSitePlugin>,
+    /// Jobs of the update center, in progress or finished
+    #[serde(default)]
+    pub jobs: Vec<UpdateCenterJob>,
+    /// Update sites
+    #[serde(default)]
+    pub sites: Vec<UpdateSite>,
+    /// Is a restart needed to complete the installation of plugins
+    pub restart_required_for_completion: bool,
+}
+
+impl UpdateCenter {
+    /// Get the updates available for installed plugins, on all update sites
+    pub fn updates(&self) -> impl Iterator<Item = &UpdateSitePlugin> {
+        self.sites.iter().flat_map(|site| site.updates.iter())
+    }
+}
+
+/// Status of the installation of a plugin
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInstallStatus {
+    /// Short name of the plugin
+    pub name: String,
+    /// Version of the plugin being installed
+    pub version: Option<String>,
+    /// Title of the plugin
+    pub title: Option<String>,
+    /// State of the installation
+    pub install_status: InstallationState,
+}
+
+/// Installation of plugins started with `Jenkins::install_plugins`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInstallation {
+    /// ID of the installation, identifying its jobs in the update center
+    pub correlation_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallStatus {
+    jobs: Vec<PluginInstallStatus>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstallPlugins<'a> {
+    dynamic_load: bool,
+    plugins: &'a [&'a str],
+}
+
+impl PluginInstallation {
+    /// Get the status of the installation of each plugin
+    pub async fn get_status(&self, jenkins_client: &Jenkins) -> Result<Vec<PluginInstallStatus>> {
+        let response: StatusResponse<InstallStatus> = jenkins_client
+            .get_endpoint_with_params(
+                &Path::UpdateCenterInstallStatus,
+                [("correlationId", self.correlation_id.as_str())],
+            )
+            .await?
+            .parse_json()
+            .await?;
+        Ok(response.data.jobs)
+    }
+
+    /// Poll the status of the installation every `interval` until every plugin is installed or
+    /// one failed, giving up after `timeout`. `progress` is called with the status of each
+    /// plugin every time it is polled
+    pub async fn wait_for_completion<F>(
+        &self,
+        jenkins_client: &Jenkins,
+        interval: Duration,
+        timeout: Duration,
+        progress: F,
+    ) -> Result<Vec<PluginInstallStatus>>
+    where
+        F: FnMut(&[PluginInstallStatus]),
+    {
+        // shared by the futures polling the status
+        let progress = std::sync::Mutex::new(progress);
+        poll_until(
+            interval,
+            timeout,
+            Duration::ZERO,
+            &CancellationToken::new(),
+            || {
+                let status = self.get_status(jenkins_client);
+                let progress = &progress;
+                async move {
+                    let status = status.await?;
+                    if let Ok(mut progress) = progress.lock() {
+                        progress(&status);
+                    }
+                    let failed = status
+                        .iter()
+                        .any(|plugin| plugin.install_status == InstallationState::Failure);
+                    let finished = status
+                        .iter()
+                        .all(|plugin| plugin.install_status.is_finished());
+                    Ok(if failed || finished {
+                        Some(status)
+                    } else {
+                        None
+                    })
+                }
+            },
+        )
+        .await
+    }
+}
+
+impl Jenkins {
+    /// Get the update center, with the available plugins and updates, and the jobs in progress
+    pub async fn get_update_center(&self) -> Result<UpdateCenter> {
+        self.get_with_params(&Path::UpdateCenter, [("depth", "2")])
+            .await?
+            .parse_json()
+            .await
+    }
+
+    /// Install the latest version of a plugin, with its dependencies
+    pub async fn install_plugin(&self, name: &str) -> Result<PluginInstallation> {
+        self.install_plugins(&[name]).await
+    }
+
+    /// Install the latest version of plugins, with their dependencies. Plugins are loaded
+    /// without restarting Jenkins when possible
+    ///
+    /// The installation continues in the background, its progress can be followed with
+    /// `PluginInstallation::wait_for_completion`
+    pub async fn install_plugins(&self, names: &[&str]) -> Result<PluginInstallation> {
+        let response: StatusResponse<PluginInstallation> = self
+            .post_with_json_body(
+                &Path::PluginManagerInstallPlugins,
+                &InstallPlugins {
+                    dynamic_load: true,
+                    plugins: names,
+                },
+            )
+            .await?
+            .parse_json()
+            .await?;
+        Ok(response.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_update_center() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/updateCenter/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "depth".to_string(),
+                "2".to_string(),
+            ))
+            .with_body(
+                r#"{
+                    "_class": "hudson.model.UpdateCenter",
+                    "availables": [
+                        {"name": "slack", "version": "2.48", "title": "Slack Notification", "sourceId": "default"}
+                    ],
+                    "jobs": [
+                        {
+                            "_class": "hudson.model.UpdateCenter$ConnectionCheckJob",
+                            "errorMessage": null,
+                            "id": 1,
+                            "type": "ConnectionCheckJob"
+                        },
+                        {
+                            "_class": "hudson.model.UpdateCenter$InstallationJob",
+                            "errorMessage": null,
+                            "id": 2,
+                            "type": "InstallationJob",
+                            "name": "git",
+                            "status": {
+                                "_class": "hudson.model.UpdateCenter$DownloadJob$SuccessButRequiresRestart",
+                                "success": true,
+                                "type": "SuccessButRequiresRestart"
+                            }
+                        }
+                    ],
+                    "restartRequiredForCompletion": true,
+                    "sites": [
+                        {
+                            "id": "default",
+                            "url": "https://updates.jenkins.io/update-center.json",
+                            "hasUpdates": true,
+                            "dataTimestamp": 1600000000000,
+                            "updates": [
+                                {"name": "git", "version": "4.4.0", "requiredCore": "2.204.1"}
+                            ]
+                        }
+                    ]
+                }"#,
+            )
+            .create();
+
+        let update_center = jenkins_client.get_update_center().await.unwrap();
+
+        assert!(update_center.restart_required_for_completion);
+        assert_eq!(update_center.availables[0].name, "slack");
+        assert_eq!(
+            update_center.jobs[1].status.unwrap().state,
+            InstallationState::SuccessButRequiresRestart
+        );
+        assert_eq!(
+            update_center
+                .updates()
+                .map(|plugin| plugin.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["git"]
+        );
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_wait_for_plugin_installation() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let install = server
+            .mock("POST", "/pluginManager/installPlugins")
+            .match_header("content-type", "application/json")
+            .match_body(r#"{"dynamicLoad":true,"plugins":["git"]}"#)
+            .with_body(r#"{"status":"ok","data":{"correlationId":"d1b2"}}"#)
+            .create();
+        let status = |state: &str| {
+            format!(
+                r#"{{"status":"ok","data":{{"state":"RUNNING","jobs":[
+                    {{"name":"git-client","version":"3.4.0","installStatus":"Success","correlationId":"d1b2"}},
+                    {{"name":"git","version":"4.4.0","installStatus":"{}","correlationId":"d1b2"}}
+                ]}}}}"#,
+                state
+            )
+        };
+        let installing = server
+            .mock("GET", "/updateCenter/installStatus")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "correlationId".to_string(),
+                "d1b2".to_string(),
+            ))
+            .with_body(status("Installing"))
+            .expect(1)
+            .create();
+
+        let installation = jenkins_client.install_plugin("git").await.unwrap();
+        assert_eq!(installation.correlation_id, "d1b2");
+        assert_eq!(
+            installation.get_status(&jenkins_client).await.unwrap()[1].install_status,
+            InstallationState::Installing
+        );
+        installing.assert();
+        installing.remove();
+
+        let _installed = server
+            .mock("GET", "/updateCenter/installStatus")
+            .match_query(mockito::Matcher::Any)
+            .with_body(status("SuccessButRequiresRestart"))
+            .create();
+        let mut polls = 0;
+        let plugins = installation
+            .wait_for_completion(
+                &jenkins_client,
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+                |_| polls += 1,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(polls, 1);
+        assert_eq!(
+            plugins[1].install_status,
+            InstallationState::SuccessButRequiresRestart
+        );
+        install.assert();
+    }
+}
@@ -1,11 +1,15 @@
 //! Jenkins Views, use to group Jobs
 
+use std::collections::HashSet;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use log::warn;
 use serde::{self, Deserialize, Serialize};
 
-use crate::helpers::Class;
+use crate::helpers::{Class, JenkinsClass};
 
-use crate::client::{self, Result};
-use crate::client_internals::{Name, Path};
+use crate::client::{self, AdvancedQuery, Cursor, Page, Result, TreeBuilder};
+use crate::client_internals::{InternalAdvancedQueryParams, Name, Path, ResponseExt};
 use crate::job::{JobName, ShortJob};
 use crate::property::CommonProperty;
 use crate::Jenkins;
@@ -33,7 +37,7 @@ impl ShortView {
     pub async fn get_full_view(&self, jenkins_client: &Jenkins) -> Result<CommonView> {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::View { .. } = path {
-            Ok(jenkins_client.get(&path).await?.json().await?)
+            Ok(jenkins_client.get(&path).await?.parse_json().await?)
         } else {
             Err(client::Error::InvalidUrl {
                 url: self.url.clone(),
@@ -62,18 +66,85 @@ impl<'a> From<&'a ShortView> for ViewName<'a> {
         ViewName(&v.name)
     }
 }
-impl<'a, T: View> From<&'a T> for ViewName<'a> {
+impl<'a, T: View + ?Sized> From<&'a T> for ViewName<'a> {
     fn from(v: &'a T) -> ViewName<'a> {
         ViewName(v.name())
     }
 }
 
+/// Number of jobs fetched per request when iterating over the jobs of a view
+const JOBS_PAGE_SIZE: u32 = 100;
+
 /// Trait implemented by specialization of view
 pub trait View {
     /// Get the name of the view
     fn name(&self) -> &str;
 }
 
+#[derive(Deserialize)]
+struct JobNames {
+    jobs: Vec<JobNameOnly>,
+}
+
+#[derive(Deserialize)]
+struct JobNameOnly {
+    name: String,
+}
+
+async fn get_view_jobs_page(
+    jenkins_client: &Jenkins,
+    view_name: &str,
+    cursor: Cursor,
+) -> Result<Page<ShortJob>> {
+    #[derive(Deserialize)]
+    struct Jobs {
+        jobs: Vec<ShortJob>,
+    }
+
+    let page: Jobs = jenkins_client
+        .get_with_params(
+            &Path::View {
+                name: Name::Name(view_name),
+            },
+            InternalAdvancedQueryParams::from(AdvancedQuery::Tree(
+                TreeBuilder::new()
+                    .with_field(
+                        TreeBuilder::object("jobs")
+                            .with_subfield("name")
+                            .with_subfield("url")
+                            .with_subfield("color")
+                            .with_cursor(cursor),
+                    )
+                    .build(),
+            )),
+        )
+        .await?
+        .parse_json()
+        .await?;
+    Ok(Page::new(page.jobs, cursor))
+}
+
+fn iter_view_jobs<'a>(
+    jenkins_client: &'a Jenkins,
+    view_name: &'a str,
+) -> impl Stream<Item = Result<ShortJob>> + 'a {
+    stream::unfold(
+        Some(Cursor::first(JOBS_PAGE_SIZE)),
+        move |cursor| async move {
+            let page = get_view_jobs_page(jenkins_client, view_name, cursor?).await;
+            let (items, next): (Vec<Result<ShortJob>>, _) = match page {
+                Ok(page) => {
+                    let next = page.next_page();
+                    (page.into_iter().map(Ok).collect(), next)
+                }
+                Err(error) => (vec![Err(error)], None),
+            };
+            Some((stream::iter(items), next))
+        },
+    )
+    .flatten()
+}
+
 /// A Jenkins `View` with a list of `ShortJob`
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -101,6 +172,12 @@ pub struct CommonView {
     pub extra_fields: serde_json::Value,
 }
 specialize!(CommonView => View);
+impl CommonView {
+    /// `_class` of the view, to check its kind without parsing it as a specialization
+    pub fn jenkins_class(&self) -> Option<JenkinsClass> {
+        self.class.as_deref().map(JenkinsClass::from)
+    }
+}
 impl View for CommonView {
     fn name(&self) -> &str {
         &self.name
@@ -108,7 +185,7 @@ impl View for CommonView {
 }
 
 /// A Jenkins `View` with a list of `ShortJob`
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListView {
     /// Description of the view
@@ -137,13 +214,15 @@ impl ListView {
     {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::View { name } = path {
-            let _ = jenkins_client
-                .post(&Path::AddJobToView {
-                    job_name: Name::Name(job_name.into().0),
-                    view_name: name,
-                })
-                .await?;
-            Ok(())
+            jenkins_client
+                .post_view_jobs(
+                    &Path::AddJobToView {
+                        job_name: Name::Name(job_name.into().0),
+                        view_name: name,
+                    },
+                    &self.name,
+                )
+                .await
         } else {
             Err(client::Error::InvalidUrl {
                 url: self.url.clone(),
@@ -160,13 +239,15 @@ impl ListView {
     {
         let path = jenkins_client.url_to_path(&self.url);
         if let Path::View { name } = path {
-            let _ = jenkins_client
-                .post(&Path::RemoveJobFromView {
-                    job_name: Name::Name(job_name.into().0),
-                    view_name: name,
-                })
-                .await?;
-            Ok(())
+            jenkins_client
+                .post_view_jobs(
+                    &Path::RemoveJobFromView {
+                        job_name: Name::Name(job_name.into().0),
+                        view_name: name,
+                    },
+                    &self.name,
+                )
+                .await
         } else {
             Err(client::Error::InvalidUrl {
                 url: self.url.clone(),
@@ -177,48 +258,550 @@ impl ListView {
     }
 }
 
+/// Names of the jobs of a view, fetched once by `Jenkins::get_view_job_names`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewJobNames {
+    names: HashSet<String>,
+}
+
+impl ViewJobNames {
+    /// Is the job `job_name` in the view
+    pub fn contains_job<'a, J>(&self, job_name: J) -> bool
+    where
+        J: Into<JobName<'a>>,
+    {
+        self.names.contains(job_name.into().0)
+    }
+
+    /// Iterate over the names of the jobs of the view, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Number of jobs in the view
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Is the view empty
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Changes made to a view by `Jenkins::sync_view_jobs`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewSyncReport {
+    /// Jobs added to the view
+    pub added: Vec<String>,
+    /// Jobs removed from the view
+    pub removed: Vec<String>,
+}
+
+impl ViewSyncReport {
+    /// Was the view already in sync
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
 impl Jenkins {
+    /// Get a `View`, specifying the depth or tree parameters
+    pub async fn get_view_as<'a, V, Q, T>(&self, view_name: V, parameters: Q) -> Result<T>
+    where
+        V: Into<ViewName<'a>>,
+        Q: Into<Option<AdvancedQuery>>,
+        for<'de> T: Deserialize<'de>,
+    {
+        self.get_object_as(
+            client::Path::View {
+                name: view_name.into().0,
+            },
+            parameters,
+        )
+        .await
+    }
+
+    /// Iterate over the jobs of the view `view_name`, fetching them 100 at a time with ranged
+    /// tree queries
+    ///
+    /// This works on views containing thousands of jobs, like the "all" view of big instances.
+    /// The stream ends after the first error.
+    pub fn iter_view_jobs<'a, V>(
+        &'a self,
+        view_name: V,
+    ) -> impl Stream<Item = Result<ShortJob>> + 'a
+    where
+        V: Into<ViewName<'a>>,
+    {
+        iter_view_jobs(self, view_name.into().0)
+    }
+
+    /// Get the names of the jobs of the view `view_name`, fetching only their names
+    ///
+    /// Checking the membership of many jobs is then a lookup in the returned names, without
+    /// fetching the view again.
+    pub async fn get_view_job_names<'a, V>(&self, view_name: V) -> Result<ViewJobNames>
+    where
+        V: Into<ViewName<'a>>,
+    {
+        let view: JobNames = self
+            .get_view_as(
+                view_name,
+                TreeBuilder::new()
+                    .with_field(TreeBuilder::object("jobs").with_subfield("name"))
+                    .build(),
+            )
+            .await?;
+        Ok(ViewJobNames {
+            names: view.jobs.into_iter().map(|job| job.name).collect(),
+        })
+    }
+
     /// Get a `View`
     pub async fn get_view<'a, V>(&self, view_name: V) -> Result<CommonView>
     where
         V: Into<ViewName<'a>>,
     {
-        Ok(self
-            .get(&Path::View {
-                name: Name::Name(view_name.into().0),
-            })
-            .await?
-            .json()
-            .await?)
+        self.get(&Path::View {
+            name: Name::Name(view_name.into().0),
+        })
+        .await?
+        .parse_json()
+        .await
+    }
+
+    /// Add or remove a job of the view `view_name` with `path`, checking that Jenkins didn't
+    /// answer with an error page
+    async fn post_view_jobs(&self, path: &Path<'_>, view_name: &str) -> Result<()> {
+        let response = self.post(path).await?;
+        // a view that can't list jobs answers the request itself with an error page, while a
+        // login page is reached through a redirection
+        let requested = reqwest::Url::parse(&self.url(&path.to_string()))?;
+        if response.url().path() != requested.path() {
+            return Err(client::Error::UnexpectedRedirect {
+                path: path.to_string(),
+                status: response.status().as_u16(),
+                location: Some(response.url().to_string()),
+            }
+            .into());
+        }
+        match response.html_page().await? {
+            Some(snippet) => Err(client::Error::ViewNotModifiable {
+                view: view_name.to_string(),
+                snippet,
+            }
+            .into()),
+            None => Ok(()),
+        }
     }
 
     /// Add the job `job_name` to the view `view_name`
+    ///
+    /// # Errors
+    /// If the view can't list jobs, like the `all` view, returns an
+    /// [`Error::ViewNotModifiable`](../client/enum.Error.html#variant.ViewNotModifiable)
     pub async fn add_job_to_view<'a, 'b, V, J>(&self, view_name: V, job_name: J) -> Result<()>
     where
         V: Into<ViewName<'a>>,
-        J: Into<JobName<'a>>,
+        J: Into<JobName<'b>>,
     {
-        let _ = self
-            .post(&Path::AddJobToView {
+        let view_name = view_name.into().0;
+        self.post_view_jobs(
+            &Path::AddJobToView {
                 job_name: Name::Name(job_name.into().0),
-                view_name: Name::Name(view_name.into().0),
-            })
-            .await?;
-        Ok(())
+                view_name: Name::Name(view_name),
+            },
+            view_name,
+        )
+        .await
+    }
+
+    /// Make the view `view_name` contain exactly the jobs `desired_jobs`, adding and removing
+    /// only the jobs that differ
+    ///
+    /// If a change fails, the changes already made are reverted before returning the error, so
+    /// the view is left as it was as far as possible.
+    pub async fn sync_view_jobs<'a, V>(
+        &self,
+        view_name: V,
+        desired_jobs: &[&str],
+    ) -> Result<ViewSyncReport>
+    where
+        V: Into<ViewName<'a>>,
+    {
+        let view_name = view_name.into().0;
+        let current = self.get_view_job_names(view_name).await?;
+
+        let mut report = ViewSyncReport::default();
+        for job in desired_jobs {
+            if current.contains_job(*job) || report.added.iter().any(|name| name == job) {
+                continue;
+            }
+            if let Err(error) = self.add_job_to_view(view_name, *job).await {
+                self.revert_view_sync(view_name, &report).await;
+                return Err(error);
+            }
+            report.added.push(job.to_string());
+        }
+        let mut removed: Vec<&str> = current
+            .iter()
+            .filter(|job| !desired_jobs.contains(job))
+            .collect();
+        removed.sort();
+        for job in removed {
+            if let Err(error) = self.remove_job_from_view(view_name, job).await {
+                self.revert_view_sync(view_name, &report).await;
+                return Err(error);
+            }
+            report.removed.push(job.to_string());
+        }
+        Ok(report)
+    }
+
+    /// Undo the changes of a partial sync, ignoring errors as the sync already failed
+    async fn revert_view_sync(&self, view_name: &str, report: &ViewSyncReport) {
+        for job in &report.added {
+            if let Err(error) = self.remove_job_from_view(view_name, job).await {
+                warn!(
+                    "could not remove {} from view {}: {}",
+                    job, view_name, error
+                );
+            }
+        }
+        for job in &report.removed {
+            if let Err(error) = self.add_job_to_view(view_name, job).await {
+                warn!(
+                    "could not add back {} to view {}: {}",
+                    job, view_name, error
+                );
+            }
+        }
     }
 
     /// Remove the job `job_name` from the view `view_name`
+    ///
+    /// # Errors
+    /// If the view can't list jobs, like the `all` view, returns an
+    /// [`Error::ViewNotModifiable`](../client/enum.Error.html#variant.ViewNotModifiable)
     pub async fn remove_job_from_view<'a, 'b, V, J>(&self, view_name: V, job_name: J) -> Result<()>
     where
         V: Into<ViewName<'a>>,
-        J: Into<JobName<'a>>,
+        J: Into<JobName<'b>>,
     {
-        let _ = self
-            .post(&Path::AddJobToView {
+        let view_name = view_name.into().0;
+        self.post_view_jobs(
+            &Path::RemoveJobFromView {
                 job_name: Name::Name(job_name.into().0),
-                view_name: Name::Name(view_name.into().0),
+                view_name: Name::Name(view_name),
+            },
+            view_name,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_add_and_remove_job_of_view() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let add = server
+            .mock("POST", "/view/nightly/addJobToView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "reports".to_string(),
+            ))
+            .create();
+        let remove = server
+            .mock("POST", "/view/nightly/removeJobFromView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "reports".to_string(),
+            ))
+            .create();
+
+        jenkins_client
+            .add_job_to_view("nightly", "reports")
+            .await
+            .unwrap();
+        jenkins_client
+            .remove_job_from_view("nightly", "reports")
+            .await
+            .unwrap();
+
+        add.assert();
+        remove.assert();
+    }
+
+    #[tokio::test]
+    async fn can_detect_view_not_modifiable() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let mock = server
+            .mock("POST", "/view/all/addJobToView")
+            .match_query(mockito::Matcher::Any)
+            .with_header("content-type", "text/html;charset=utf-8")
+            .with_body("<html><body><h1>Oops!</h1><p>A problem occurred.</p></body></html>")
+            .create();
+
+        let error = jenkins_client
+            .add_job_to_view("all", "reports")
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<client::Error>() {
+            Some(client::Error::ViewNotModifiable { view, snippet }) => {
+                assert_eq!(view, "all");
+                assert_eq!(snippet, "Oops! A problem occurred.");
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_tell_login_page_from_view_not_modifiable() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let add = server
+            .mock("POST", "/view/nightly/addJobToView")
+            .match_query(mockito::Matcher::Any)
+            .with_status(302)
+            .with_header("location", "/login?from=%2Fview%2Fnightly%2FaddJobToView")
+            .create();
+        let login = server
+            .mock("GET", "/login")
+            .match_query(mockito::Matcher::Any)
+            .with_header("content-type", "text/html;charset=utf-8")
+            .with_body("<html><body><h1>Sign in to Jenkins</h1></body></html>")
+            .create();
+
+        let error = jenkins_client
+            .add_job_to_view("nightly", "reports")
+            .await
+            .unwrap_err();
+
+        match error.downcast_ref::<client::Error>() {
+            Some(client::Error::UnexpectedRedirect { path, location, .. }) => {
+                assert_eq!(path, "/view/nightly/addJobToView?name=reports");
+                assert!(location.as_deref().unwrap().contains("/login?from="));
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+        add.assert();
+        login.assert();
+    }
+
+    #[tokio::test]
+    async fn can_iter_jobs_of_view() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let url = server.url();
+        let jobs = |range: std::ops::Range<u32>| {
+            serde_json::json!({
+                "jobs": range
+                    .map(|i| serde_json::json!({"name": format!("job-{}", i), "url": format!("{}/job/job-{}/", url, i), "color": "blue"}))
+                    .collect::<Vec<_>>()
             })
-            .await?;
-        Ok(())
+            .to_string()
+        };
+        let first_page = server
+            .mock("GET", "/view/all/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "jobs[name,url,color]{0,100}".to_string(),
+            ))
+            .with_body(jobs(0..100))
+            .create();
+        let second_page = server
+            .mock("GET", "/view/all/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "jobs[name,url,color]{100,200}".to_string(),
+            ))
+            .with_body(jobs(100..130))
+            .create();
+        let view: CommonView = serde_json::from_value(serde_json::json!({
+            "name": "all",
+            "url": format!("{}/view/all/", server.url()),
+            "jobs": [],
+            "property": []
+        }))
+        .unwrap();
+
+        let names = jenkins_client
+            .iter_view_jobs(&view)
+            .map(|job| job.unwrap().name)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(names.len(), 130);
+        assert_eq!(names[129], "job-129");
+        first_page.assert();
+        second_page.assert();
+    }
+
+    #[tokio::test]
+    async fn can_check_job_in_view() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url()).build().unwrap();
+
+        let mock = server
+            .mock("GET", "/view/release/api/json")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "tree".to_string(),
+                "jobs[name]".to_string(),
+            ))
+            .with_body(r#"{"jobs":[{"name":"build"},{"name":"deploy"}]}"#)
+            .expect(1)
+            .create();
+        let view: CommonView = serde_json::from_value(serde_json::json!({
+            "name": "release",
+            "url": format!("{}/view/release/", server.url()),
+            "jobs": [],
+            "property": []
+        }))
+        .unwrap();
+
+        let view: &dyn View = &view;
+        let names = jenkins_client.get_view_job_names(view).await.unwrap();
+        assert!(names.contains_job("deploy"));
+        assert!(names.contains_job("build"));
+        assert!(!names.contains_job("test"));
+        assert_eq!(names.len(), 2);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn can_sync_view_jobs() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let view = server
+            .mock("GET", "/view/release/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"jobs":[{"name":"build"},{"name":"legacy"}]}"#)
+            .create();
+        let add = server
+            .mock("POST", "/view/release/addJobToView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "deploy".to_string(),
+            ))
+            .create();
+        let remove = server
+            .mock("POST", "/view/release/removeJobFromView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "legacy".to_string(),
+            ))
+            .create();
+
+        let report = jenkins_client
+            .sync_view_jobs("release", &["build", "deploy"])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            ViewSyncReport {
+                added: vec!["deploy".to_string()],
+                removed: vec!["legacy".to_string()],
+            }
+        );
+        view.assert();
+        add.assert();
+        remove.assert();
+    }
+
+    #[tokio::test]
+    async fn reverts_failed_view_sync() {
+        let mut server = mockito::Server::new_async().await;
+        let jenkins_client = crate::JenkinsBuilder::new(&server.url())
+            .disable_csrf()
+            .build()
+            .unwrap();
+
+        let _view = server
+            .mock("GET", "/view/release/api/json")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"jobs":[]}"#)
+            .create();
+        let _add_build = server
+            .mock("POST", "/view/release/addJobToView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "build".to_string(),
+            ))
+            .create();
+        let _add_missing = server
+            .mock("POST", "/view/release/addJobToView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "missing".to_string(),
+            ))
+            .with_status(404)
+            .create();
+        let revert = server
+            .mock("POST", "/view/release/removeJobFromView")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".to_string(),
+                "build".to_string(),
+            ))
+            .create();
+
+        assert!(jenkins_client
+            .sync_view_jobs("release", &["build", "missing"])
+            .await
+            .is_err());
+        revert.assert();
+    }
+
+    #[test]
+    fn can_round_trip_list_view() {
+        let view: ListView = serde_json::from_value(serde_json::json!({
+            "_class": "hudson.model.ListView",
+            "description": null,
+            "name": "nightly",
+            "url": "http://jenkins/view/nightly/",
+            "jobs": [
+                {
+                    "_class": "hudson.model.FreeStyleProject",
+                    "name": "reports",
+                    "url": "http://jenkins/job/reports/",
+                    "color": "blue"
+                }
+            ],
+            "property": []
+        }))
+        .unwrap();
+
+        let json = serde_json::to_value(&view).unwrap();
+        let reloaded: ListView = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(reloaded.jobs[0].name, "reports");
+        assert_eq!(serde_json::to_value(&reloaded).unwrap(), json);
     }
 }
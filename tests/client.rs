@@ -619,6 +619,7 @@ async fn can_get_maven_job() {
         assert_that!(
             maven_project
                 .last_build
+                .as_ref()
                 .unwrap()
                 .get_full_build(&jenkins)
                 .await
@@ -639,6 +640,13 @@ async fn can_get_maven_job() {
         )
         .named("getting last run of a maven module")
         .is_ok();
+        let modules = maven_project.get_modules(&jenkins).await;
+        assert_that!(modules)
+            .named("getting all modules of a maven project")
+            .is_ok();
+        assert_that!(modules.unwrap()[0].get_builds(&jenkins).await)
+            .named("getting builds of a maven module")
+            .is_ok();
     }
 
     let build = jenkins.get_build("matrix job", 1).await;
@@ -680,6 +688,7 @@ async fn can_get_nodes() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn can_get_master() {
     setup();
     let jenkins = JenkinsBuilder::new(JENKINS_URL)
@@ -688,6 +697,7 @@ async fn can_get_master() {
         .unwrap();
     assert_that!(jenkins.get_node("(master)").await).is_ok();
     assert_that!(jenkins.get_master_node().await).is_ok();
+    assert_that!(jenkins.get_builtin_node().await).is_ok();
 }
 
 #[tokio::test]
@@ -703,14 +713,15 @@ async fn can_get_master_while_building() {
     assert!(job.is_ok());
     assert!(job.unwrap().build(&jenkins).await.is_ok());
 
-    println!("{:#?}", jenkins.get_master_node().await);
-    assert_that!(jenkins.get_master_node().await).is_ok();
+    println!("{:#?}", jenkins.get_builtin_node().await);
+    assert_that!(jenkins.get_builtin_node().await).is_ok();
 }
 
 #[tokio::test]
 async fn can_get_by_path_with_tree() {
     #[derive(Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
     struct LastBuild {
         number: u8,
         duration: u8,
@@ -718,6 +729,7 @@ async fn can_get_by_path_with_tree() {
     }
     #[derive(Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
     struct LasBuildOfJob {
         display_name: String,
         last_build: LastBuild,